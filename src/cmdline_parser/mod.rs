@@ -12,11 +12,13 @@ use crate::options::clear::ClearOptions;
 use crate::options::command::RuwiCommand;
 use crate::options::GlobalOptions;
 use crate::prelude::*;
+use crate::select::display_template::DisplayTemplate;
 use crate::strum_utils::{get_val_as_enum, possible_string_vals};
 
 use std::env;
+use std::time::Duration;
 
-use clap::{App, Arg, ArgMatches, SubCommand};
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
 use strum::AsStaticRef;
 
 const BLUETOOTH_TOKEN: &str = "bluetooth";
@@ -30,10 +32,20 @@ const WIRED_CONNECT_TOKEN: &str = "connect";
 
 const WIFI_SELECT_TOKEN: &str = "select";
 const WIFI_CONNECT_TOKEN: &str = "connect";
+const WIFI_LIST_TOKEN: &str = "list";
+const WIFI_INFO_TOKEN: &str = "info";
+const WIFI_FORGET_TOKEN: &str = "forget";
 
 const BLUETOOTH_DEV_NAME_TOKEN: &str = "device_name";
 const BLUETOOTH_DEV_ADDR_TOKEN: &str = "device_addr";
 
+pub(crate) const SELECTION_METHOD_TOKEN: &str = "selection_method";
+const DISPLAY_TEMPLATE_TOKEN: &str = "display_template";
+const SELECT_TIMEOUT_TOKEN: &str = "select_timeout";
+const SELECT_TIMEOUT_FALLBACK_TOKEN: &str = "select_timeout_fallback";
+const SORT_POLICY_TOKEN: &str = "sort_policy";
+const ESSID_PRIORITY_TOKEN: &str = "essid_priority";
+
 const PRETEND_TO_BE_ROOT_TOKEN: &str = "PRETEND_TO_BE_ROOT";
 
 const CMDLINE_BAILOUT_TOKEN: &str = "ONLY_PARSE_CMDLINE";
@@ -64,12 +76,28 @@ fn get_arg_app<'a, 'b>() -> App<'a, 'b> {
         .short("F")
         .long("input-file")
         .takes_value(true)
-        .help("Instead of running a scan, use scan results from specified file.");
+        .multiple(true)
+        .number_of_values(1)
+        .help("Instead of running a scan, use scan results from specified file. Can be given more than once to combine results. The scan type is detected automatically unless -s is given.");
+
+    let sort_policy = Arg::with_name(SORT_POLICY_TOKEN)
+        .long("sort")
+        .takes_value(true)
+        .possible_values(&possible_string_vals::<SortPolicy, _>())
+        .help("How to order networks, which also decides which network is picked first by the auto modes. \"known_first\" (the default) and \"signal\" are what they say, \"prefer_5ghz\" puts 5GHz and 6GHz networks first, \"open_last\" puts networks without a password last, and \"priority\" puts the networks given with `--essid-priority` first, in that order.");
+
+    let essid_priority = Arg::with_name(ESSID_PRIORITY_TOKEN)
+        .long("essid-priority")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .use_delimiter(true)
+        .help("Network names (ESSIDs) to put first, most preferred first. Can be comma-separated or given more than once. Implies `--sort priority` unless another sort policy is given.");
 
     let input_stdin = Arg::with_name("input_stdin")
         .short("I")
         .long("input-stdin")
-        .help("Instead of running a scan, use scan results from stdin.");
+        .help("Instead of running a scan, use scan results from stdin. The scan type is detected automatically unless -s is given.");
 
     let auto = Arg::with_name("auto").short("a").long("auto").help(
         "Connect to the strongest known network seen. Will prompt for selection if no known networks are seen. Shorthand for `-A known_or_ask`. Takes precedence over `-A`.",
@@ -92,6 +120,10 @@ fn get_arg_app<'a, 'b>() -> App<'a, 'b> {
         .long("ignore-known")
         .help("Do not try to determine if networks are already known. Passwords will be requested always in this mode.");
 
+    let strict_parse = Arg::with_name("strict_parse")
+        .long("strict-parse")
+        .help("Fail if any line of the scan output cannot be parsed, instead of silently skipping it.");
+
     let networking_interface = Arg::with_name("interface")
         .short("i")
         .long("interface")
@@ -104,6 +136,17 @@ fn get_arg_app<'a, 'b>() -> App<'a, 'b> {
         .takes_value(true)
         .help("Manually specify wireless network name (aka SSID or ESSID). Will be asked for if not given. Assumes the network is open, use `-P` to prompt for password or `-p`  to pass one in directly.");
 
+    let hidden = Arg::with_name("hidden")
+        .long("hidden")
+        .requires("essid")
+        .help("The network given with `-e` is hidden (does not broadcast its SSID), so it must be connected to without being seen in a scan first.");
+
+    let essid_pattern = Arg::with_name("match")
+        .long("match")
+        .takes_value(true)
+        .conflicts_with("essid")
+        .help("Scan, and connect to the best network whose name starts with the given text, or matches the given regular expression from its start. Fails, listing similar network names, if nothing matches. Useful for scripts and hotkeys.");
+
     let password = Arg::with_name("password")
         .short("p")
         .long("password")
@@ -115,6 +158,31 @@ fn get_arg_app<'a, 'b>() -> App<'a, 'b> {
         .long("force-ask-password")
         .help("Will always prompt for a password when selecting a network, or passing an SSID with `-e`. Ignored with `-p`, or on connection/output types where a password wouldn't be used anyway.");
 
+    let confirm_password = Arg::with_name("confirm_password")
+        .long("confirm-password")
+        .help("Ask for the password twice, and fail if the two do not match.");
+
+    let plaintext_password = Arg::with_name("plaintext_password")
+        .long("plaintext-password")
+        .help("Write the password itself to new netctl configs. By default, the WPA key (PSK) derived from the password and the network name is written instead, which is all that's needed to connect, but doesn't reveal a password that might be used elsewhere.");
+
+    let forget_essid = Arg::with_name("essid")
+        .short("e")
+        .long("essid")
+        .takes_value(true)
+        .help("The name (SSID) of the known network to forget. Will be selected from the list of known networks if not given.");
+
+    let skip_confirmation = Arg::with_name("yes")
+        .short("y")
+        .long("yes")
+        .help("Don't ask before forgetting the network.");
+
+    let password_prompt = Arg::with_name("password_prompt")
+        .long("password-prompt")
+        .takes_value(true)
+        .possible_values(&possible_string_vals::<PasswordPromptMethod, _>())
+        .help("How to ask for a password. \"selector\" uses the password mode of the selection method (with typed characters hidden), \"pinentry\" uses the same dialog as gpg-agent, which is handy in graphical sessions.");
+
     let wifi_scan_type = Arg::with_name("scan_type")
         .short("s")
         .long("scan-type")
@@ -122,13 +190,47 @@ fn get_arg_app<'a, 'b>() -> App<'a, 'b> {
         .possible_values(&possible_string_vals::<WifiScanType, _>())
         .help("The wifi scanning program to use to get results.");
 
-    let selection_method = Arg::with_name("selection_method")
+    let selection_method = Arg::with_name(SELECTION_METHOD_TOKEN)
         .short("m")
         .long("selection-method")
         .takes_value(true)
-        .default_value(&SelectionMethod::default().as_static())
         .possible_values(&possible_string_vals::<SelectionMethod, _>())
-        .help("The program to use to prompt for input.");
+        .help("The program to use to prompt for input. \"builtin\" uses a simple fuzzy-filtering selector which runs directly in the terminal, for systems without fzf or a display server. \"custom\" runs the command given with `--selection-command`. If not given, will pick a selector based on which programs are installed and whether a terminal or display server is available.");
+
+    let selection_command = Arg::with_name("selection_command")
+        .long("selection-command")
+        .takes_value(true)
+        .required_if(SELECTION_METHOD_TOKEN, SelectionMethod::Custom.as_static())
+        .help("The command to run for `-m custom`, e.g. \"my-menu --prompt '{prompt}'\". Choices are fed to it one per line on stdin, and it should print the chosen line to stdout. \"{prompt}\" is replaced with the prompt text. Words can be grouped with single or double quotes.");
+
+    let display_template = Arg::with_name(DISPLAY_TEMPLATE_TOKEN)
+        .long("display-template")
+        .takes_value(true)
+        .help("How to show each network or device in the selector, e.g. \"{signal_bars} {essid} {security} {known}\". The fields are {signal_bars}, {dbm}, {quality}, {essid} (or {name}), {band}, {security}, {known} and {bssid}. Fields which don't apply (e.g. {band} for a Bluetooth device) are left empty. Use \"{{\" and \"}}\" for literal braces.");
+
+    let display_icons = Arg::with_name("display_icons")
+        .long("display-icons")
+        .requires(DISPLAY_TEMPLATE_TOKEN)
+        .help("Use Unicode icons for {signal_bars}, {security} and {known} in the `--display-template`.");
+
+    let align_columns = Arg::with_name("align_columns")
+        .long("align-columns")
+        .requires(DISPLAY_TEMPLATE_TOKEN)
+        .help("Pad each field of the `--display-template` to the same width on every line, so that they line up in columns.");
+
+    let select_timeout = Arg::with_name(SELECT_TIMEOUT_TOKEN)
+        .long("select-timeout")
+        .takes_value(true)
+        .value_name("SECONDS")
+        .validator(validate_select_timeout)
+        .help("If no choice is made in the selector within this many seconds, close it and choose automatically, as given by `--select-timeout-fallback`. Useful when ruwi is bound to a hotkey. Applies to the selectors which run an external program (fzf, dmenu, etc.), but not to password prompts.");
+
+    let select_timeout_fallback = Arg::with_name(SELECT_TIMEOUT_FALLBACK_TOKEN)
+        .long("select-timeout-fallback")
+        .takes_value(true)
+        .requires(SELECT_TIMEOUT_TOKEN)
+        .possible_values(&possible_string_vals::<SelectTimeoutFallback, _>())
+        .help("What to choose when `--select-timeout` is reached: the first \"known\" network (the default), the \"first\" network, or \"fail\" to exit with an error.");
 
     let wifi_connect_via = Arg::with_name("connect_via")
         .short("c")
@@ -137,6 +239,13 @@ fn get_arg_app<'a, 'b>() -> App<'a, 'b> {
         .possible_values(&possible_string_vals::<WifiConnectionType, _>())
         .help("Which network management suite to use to connect to the selected SSID on the given interface. Will attempt to automatically determine the best connector based on which programs are currently running/installed on the system.");
 
+    let wifi_list_format = Arg::with_name("format")
+        .long("format")
+        .takes_value(true)
+        .default_value(&WifiListFormat::default().as_static())
+        .possible_values(&possible_string_vals::<WifiListFormat, _>())
+        .help("The output format to print seen networks in.");
+
     let wired_connect_via = Arg::with_name("connect_via")
         .short("c")
         .long("connect-via")
@@ -179,6 +288,12 @@ fn get_arg_app<'a, 'b>() -> App<'a, 'b> {
         .arg(debug)
         .arg(dry_run)
         .arg(selection_method)
+        .arg(selection_command)
+        .arg(display_template)
+        .arg(display_icons)
+        .arg(align_columns)
+        .arg(select_timeout)
+        .arg(select_timeout_fallback)
         .subcommand(SubCommand::with_name(CLEAR_TOKEN)
             .about("Stop all managed networking services (netctl, NetworkManager, wpa_supplicant, etc.)")
         )
@@ -206,17 +321,48 @@ fn get_arg_app<'a, 'b>() -> App<'a, 'b> {
             .arg(input_stdin)
             .arg(force_synchronous)
             .arg(networking_interface)
+            .arg(strict_parse)
+            .arg(sort_policy)
+            .arg(essid_priority)
             .arg(wifi_scan_type)
             .subcommand(SubCommand::with_name(WIFI_CONNECT_TOKEN)
                 .arg(auto.clone())
                 .arg(auto_mode.clone())
-                .arg(wifi_connect_via)
+                .arg(wifi_connect_via.clone())
                 .arg(essid)
+                .arg(hidden)
+                .arg(essid_pattern)
                 .arg(force_ask_password)
+                .arg(confirm_password)
+                .arg(plaintext_password)
+                .arg(password_prompt)
                 .arg(password))
             .subcommand(SubCommand::with_name(WIFI_SELECT_TOKEN)
+                .about("Scan for wireless networks, and print the name of the selected network.")
                 .arg(auto)
                 .arg(auto_mode)
+                .arg(wifi_connect_via.clone())
+            )
+            .subcommand(SubCommand::with_name(WIFI_LIST_TOKEN)
+                .about("Scan for wireless networks, and print out everything seen.")
+                .arg(wifi_connect_via.clone())
+                .arg(wifi_list_format)
+            )
+            .subcommand(SubCommand::with_name(WIFI_FORGET_TOKEN)
+                .about("Select a known network, and remove its saved profile from the network manager.")
+                .arg(wifi_connect_via)
+                .arg(forget_essid)
+                .arg(skip_confirmation)
+            )
+            .subcommand(SubCommand::with_name(WIFI_INFO_TOKEN)
+                .about("Print the details of a network from a selector's list. Used for the fzf preview pane.")
+                .setting(AppSettings::Hidden)
+                .arg(Arg::with_name("details_file")
+                    .long("details-file")
+                    .takes_value(true)
+                    .required(true))
+                .arg(Arg::with_name("selected_line")
+                    .required(true))
             )
         )
 }
@@ -231,7 +377,23 @@ fn get_command_from_command_line_impl(m: &ArgMatches) -> Result<RuwiCommand, Ruw
     let pretend_to_be_root = is_env_var_set_to_1(PRETEND_TO_BE_ROOT_TOKEN);
 
     let debug = m.is_present("debug");
-    let selection_method = get_val_as_enum::<SelectionMethod>(&m, "selection_method");
+    let selection_command = m.value_of("selection_command").map(String::from);
+    let display_template = m
+        .value_of(DISPLAY_TEMPLATE_TOKEN)
+        .map(|template| {
+            DisplayTemplate::parse(template).map(|template| {
+                template
+                    .with_icons(m.is_present("display_icons"))
+                    .with_aligned_columns(m.is_present("align_columns"))
+            })
+        })
+        .transpose()?;
+    let select_timeout = m.value_of(SELECT_TIMEOUT_TOKEN).map(|secs| {
+        SelectTimeout::new(
+            Duration::from_secs(secs.parse().unwrap_or_default()),
+            get_val_as_enum::<SelectTimeoutFallback>(m, SELECT_TIMEOUT_FALLBACK_TOKEN),
+        )
+    });
 
     let dry_run = m.is_present("dry_run");
     if dry_run {
@@ -239,10 +401,19 @@ fn get_command_from_command_line_impl(m: &ArgMatches) -> Result<RuwiCommand, Ruw
         eprintln!("[NOTE]: Running in dryrun mode! Will not run any external commands (besides the requested prompt command) or write/read configs on disk, and will only use cached scan results.");
     }
 
+    let selection_method = if m.is_present(SELECTION_METHOD_TOKEN) {
+        Some(get_val_as_enum::<SelectionMethod>(&m, SELECTION_METHOD_TOKEN))
+    } else {
+        None
+    };
+
     let globals = GlobalOptions::builder()
         .debug(debug)
         .dry_run(dry_run)
         .selection_method(selection_method)
+        .selection_command(selection_command)
+        .display_template(display_template)
+        .select_timeout(select_timeout)
         .pretend_to_be_root(pretend_to_be_root)
         .build();
 
@@ -268,6 +439,15 @@ fn get_command_from_command_line_impl(m: &ArgMatches) -> Result<RuwiCommand, Ruw
     Ok(cmd)
 }
 
+// clap 2 validators take the value by String.
+#[allow(clippy::needless_pass_by_value)]
+fn validate_select_timeout(secs: String) -> Result<(), String> {
+    match secs.parse::<u64>() {
+        Ok(secs) if secs > 0 => Ok(()),
+        _ => Err(format!("\"{}\" is not a whole number of seconds greater than zero.", secs)),
+    }
+}
+
 fn is_env_var_set_to_1(name: &str) -> bool {
     let var_res = env::var(name);
     match var_res {
@@ -297,6 +477,9 @@ mod tests {
 
     use crate::options::bluetooth::connect::BluetoothConnectOptions;
     use crate::options::wifi::connect::WifiConnectOptions;
+    use crate::options::wifi::forget::WifiForgetOptions;
+    use crate::options::wifi::list::WifiListOptions;
+    use crate::options::wifi::select::WifiSelectOptions;
     use crate::options::wired::connect::WiredConnectOptions;
 
     use clap::ArgMatches;
@@ -341,6 +524,30 @@ mod tests {
         }
     }
 
+    fn expect_wifi_list_opts(cmd: RuwiCommand) -> WifiListOptions {
+        if let RuwiCommand::Wifi(RuwiWifiCommand::List(opts)) = cmd {
+            opts
+        } else {
+            panic!("Expected command to be 'wifi list', but got: {:?}", cmd);
+        }
+    }
+
+    fn expect_wifi_select_opts(cmd: RuwiCommand) -> WifiSelectOptions {
+        if let RuwiCommand::Wifi(RuwiWifiCommand::Select(opts)) = cmd {
+            opts
+        } else {
+            panic!("Expected command to be 'wifi select', but got: {:?}", cmd);
+        }
+    }
+
+    fn expect_wifi_forget_opts(cmd: RuwiCommand) -> WifiForgetOptions {
+        if let RuwiCommand::Wifi(RuwiWifiCommand::Forget(opts)) = cmd {
+            opts
+        } else {
+            panic!("Expected command to be 'wifi forget', but got: {:?}", cmd);
+        }
+    }
+
     fn expect_wired_connect_opts(cmd: RuwiCommand) -> WiredConnectOptions {
         if let RuwiCommand::Wired(RuwiWiredCommand::Connect(opts)) = cmd {
             opts
@@ -477,6 +684,28 @@ mod tests {
         assert_eq![opts.get_scan_type(), &wifi_scan_type];
     }
 
+    #[test]
+    fn test_scan_method_multiple_files() {
+        let scan_method = ScanMethod::FromFiles(vec!["a.txt".to_string(), "b.txt".to_string()]);
+        let opts = expect_wifi_connect_opts(getopts(&["wifi", "-F", "a.txt", "-F", "b.txt"]));
+        assert_eq![opts.get_scan_method(), &scan_method];
+    }
+
+    #[test]
+    fn test_detect_scan_type() {
+        let opts = expect_wifi_connect_opts(getopts(&["wifi"]));
+        assert![!opts.get_detect_scan_type()];
+
+        let opts = expect_wifi_connect_opts(getopts(&["wifi", "-I"]));
+        assert![opts.get_detect_scan_type()];
+
+        let opts = expect_wifi_connect_opts(getopts(&["wifi", "-F", "a.txt"]));
+        assert![opts.get_detect_scan_type()];
+
+        let opts = expect_wifi_connect_opts(getopts(&["wifi", "-F", "a.txt", "-s", "iw"]));
+        assert![!opts.get_detect_scan_type()];
+    }
+
     #[test]
     fn test_invalid_type_and_method() {
         let wst = WifiScanType::RuwiJSON;
@@ -484,6 +713,19 @@ mod tests {
         assert![opts.is_err()];
     }
 
+    #[test]
+    fn test_wifi_info() {
+        let cmd = getopts(&["-m", "fzf", "wifi", "info", "--details-file", "/tmp/x.json", "3)"]);
+        if let RuwiCommand::Wifi(RuwiWifiCommand::Info(opts)) = cmd {
+            assert_eq![opts.get_details_file(), "/tmp/x.json"];
+            assert_eq![opts.get_selected_line(), "3)"];
+        } else {
+            panic!("Expected command to be 'wifi info', but got: {:?}", cmd);
+        }
+
+        assert![getopts_safe(&["wifi", "info", "3)"]).is_err()];
+    }
+
     #[test]
     fn test_selection_method() {
         let expected = SelectionMethod::Fzf;
@@ -496,17 +738,191 @@ mod tests {
             expected.to_string().as_ref(),
         ]));
         assert_eq![opts.get_selection_method(), &expected];
+
+        let opts = expect_wifi_connect_opts(getopts(&["-m", "builtin"]));
+        assert_eq![opts.get_selection_method(), &SelectionMethod::Builtin];
+
+        for method in &["bemenu", "fuzzel", "rofi", "wofi"] {
+            let opts = expect_wifi_connect_opts(getopts(&["-m", method]));
+            assert_eq![opts.get_selection_method().to_string(), *method];
+        }
+    }
+
+    #[test]
+    fn test_selection_method_default_from_system() {
+        let opts = expect_wifi_connect_opts(getopts(&["wifi"]));
+        assert_eq![opts.get_selection_method(), &SelectionMethod::default()];
+        assert_eq![opts.get_selection_command(), &None];
+    }
+
+    #[test]
+    fn test_display_template() {
+        let opts = expect_wifi_connect_opts(getopts(&["wifi", "connect"]));
+        assert_eq![opts.get_display_template(), &None];
+
+        let opts = expect_wifi_connect_opts(getopts(&[
+            "--display-template",
+            "{signal_bars} {essid}",
+            "--display-icons",
+            "wifi",
+            "connect",
+        ]));
+        let expected = DisplayTemplate::parse("{signal_bars} {essid}")
+            .unwrap()
+            .with_icons(true);
+        assert_eq![opts.get_display_template(), &Some(expected)];
+
+        let res = getopts_safe(&["--display-template", "{essid} {nope}", "wifi", "connect"]);
+        assert_eq![res.err().unwrap().kind, RuwiErrorKind::InvalidDisplayTemplate];
+
+        let res = getopts_safe(&["--align-columns", "wifi", "connect"]);
+        assert![res.is_err()];
+    }
+
+    #[test]
+    fn test_select_timeout() {
+        let opts = expect_wifi_connect_opts(getopts(&["wifi", "connect"]));
+        assert_eq![opts.get_select_timeout(), &None];
+
+        let opts = expect_wifi_connect_opts(getopts(&["--select-timeout", "30", "wifi", "connect"]));
+        assert_eq![
+            opts.get_select_timeout(),
+            &Some(SelectTimeout::new(Duration::from_secs(30), SelectTimeoutFallback::Known))
+        ];
+
+        let opts = expect_wifi_connect_opts(getopts(&[
+            "--select-timeout",
+            "5",
+            "--select-timeout-fallback",
+            "first",
+            "wifi",
+            "connect",
+        ]));
+        assert_eq![
+            opts.get_select_timeout(),
+            &Some(SelectTimeout::new(Duration::from_secs(5), SelectTimeoutFallback::First))
+        ];
+
+        for args in &[
+            vec!["--select-timeout", "0", "wifi", "connect"],
+            vec!["--select-timeout", "soon", "wifi", "connect"],
+            vec!["--select-timeout-fallback", "first", "wifi", "connect"],
+        ] {
+            assert![getopts_safe(args).is_err()];
+        }
+    }
+
+    #[test]
+    fn test_custom_selection_command() {
+        let cmd = "my-menu --prompt '{prompt}'";
+        let opts = expect_wifi_connect_opts(getopts(&["-m", "custom", "--selection-command", cmd]));
+        assert_eq![opts.get_selection_method(), &SelectionMethod::Custom];
+        assert_eq![opts.get_selection_command(), &Some(cmd.to_string())];
+    }
+
+    #[test]
+    fn test_custom_selection_method_requires_command() {
+        let opts = getopts_safe(&["-m", "custom"]);
+        assert![opts.is_err()];
     }
 
     #[test]
     fn test_give_password() {
         let pw = "fakepasswordddd";
         let opts = expect_wifi_connect_opts(getopts(&["wifi", "connect", "-p", pw]));
-        assert_eq![opts.get_given_encryption_key().clone().unwrap(), pw];
+        assert_eq![opts.get_given_encryption_key().as_ref().unwrap().as_str(), pw];
 
         let pw2 = "FAKEP_SSS_A_W_W_W_W";
         let opts = expect_wifi_connect_opts(getopts(&["wifi", "connect", "--password", pw2]));
-        assert_eq![opts.get_given_encryption_key().clone().unwrap(), pw2];
+        assert_eq![opts.get_given_encryption_key().as_ref().unwrap().as_str(), pw2];
+    }
+
+    #[test]
+    fn test_password_prompt_options() {
+        let opts = expect_wifi_connect_opts(getopts(&["wifi", "connect"]));
+        assert![!opts.get_confirm_password()];
+        assert_eq![opts.get_password_prompt_method(), &PasswordPromptMethod::Selector];
+
+        let opts = expect_wifi_connect_opts(getopts(&[
+            "wifi",
+            "connect",
+            "--confirm-password",
+            "--password-prompt",
+            "pinentry",
+        ]));
+        assert![opts.get_confirm_password()];
+        assert_eq![opts.get_password_prompt_method(), &PasswordPromptMethod::Pinentry];
+    }
+
+    #[test]
+    fn test_plaintext_password() {
+        let opts = expect_wifi_connect_opts(getopts(&["wifi", "connect"]));
+        assert![opts.get_hash_password()];
+
+        let opts = expect_wifi_connect_opts(getopts(&["wifi", "connect", "--plaintext-password"]));
+        assert![!opts.get_hash_password()];
+    }
+
+    #[test]
+    fn test_hidden() {
+        let opts = expect_wifi_connect_opts(getopts(&["wifi", "connect", "-e", "HIDDEN_NW"]));
+        assert![!opts.get_given_essid_is_hidden()];
+
+        let opts = expect_wifi_connect_opts(getopts(&[
+            "wifi",
+            "connect",
+            "-e",
+            "HIDDEN_NW",
+            "--hidden",
+        ]));
+        assert_eq![opts.get_given_essid().clone().unwrap(), "HIDDEN_NW"];
+        assert![opts.get_given_essid_is_hidden()];
+
+        let res = getopts_safe(&["wifi", "connect", "--hidden"]);
+        assert![res.is_err()];
+    }
+
+    #[test]
+    fn test_essid_pattern() {
+        let opts = expect_wifi_connect_opts(getopts(&["wifi", "connect"]));
+        assert![opts.get_given_essid_pattern().is_none()];
+
+        let opts = expect_wifi_connect_opts(getopts(&["wifi", "connect", "--match", "Home"]));
+        assert_eq![opts.get_given_essid_pattern().as_ref().unwrap().as_str(), "Home"];
+
+        let res = getopts_safe(&["wifi", "connect", "--match", "Home", "-e", "HomeNet"]);
+        assert![res.is_err()];
+    }
+
+    #[test]
+    fn test_sort_policy() {
+        let opts = expect_wifi_connect_opts(getopts(&["wifi", "connect"]));
+        assert_eq![opts.get_sort_policy(), &SortPolicy::KnownFirst];
+        assert![opts.get_essid_priority().is_empty()];
+
+        let opts = expect_wifi_connect_opts(getopts(&["wifi", "--sort", "prefer_5ghz", "connect"]));
+        assert_eq![opts.get_sort_policy(), &SortPolicy::Prefer5GHz];
+
+        let opts = expect_wifi_connect_opts(getopts(&[
+            "wifi",
+            "--essid-priority",
+            "Home,Work",
+            "--essid-priority",
+            "Cafe",
+            "connect",
+        ]));
+        assert_eq![opts.get_sort_policy(), &SortPolicy::Priority];
+        assert_eq![opts.get_essid_priority(), ["Home", "Work", "Cafe"]];
+
+        let opts = expect_wifi_connect_opts(getopts(&[
+            "wifi",
+            "--sort",
+            "signal",
+            "--essid-priority",
+            "Home",
+            "connect",
+        ]));
+        assert_eq![opts.get_sort_policy(), &SortPolicy::Signal];
     }
 
     #[test]
@@ -579,6 +995,15 @@ mod tests {
         assert![opts.get_force_synchronous_scan()];
     }
 
+    #[test]
+    fn test_strict_parse() {
+        let opts = expect_wifi_connect_opts(getopts(&["wifi"]));
+        assert![!opts.get_strict_parse()];
+
+        let opts = expect_wifi_connect_opts(getopts(&["wifi", "--strict-parse"]));
+        assert![opts.get_strict_parse()];
+    }
+
     #[test]
     fn test_incorrect_enum_variant_invocations() {
         test_fail_incorrect_option(&["wifi", "-s", "BOOOBLOOOBOO"]);
@@ -603,6 +1028,59 @@ mod tests {
         assert_eq![err.kind, RuwiErrorKind::InvalidScanTypeAndConnectType];
     }
 
+    #[test]
+    fn test_wifi_list_format() {
+        let opts = expect_wifi_list_opts(getopts(&["wifi", "list"]));
+        assert_eq![opts.get_format(), &WifiListFormat::Human];
+
+        for format in WifiListFormat::iter() {
+            let opts = expect_wifi_list_opts(getopts(&[
+                "wifi",
+                "list",
+                "--format",
+                &format.to_string(),
+            ]));
+            assert_eq![opts.get_format(), &format];
+        }
+    }
+
+    #[test]
+    fn test_wifi_list_connect_via() {
+        let opts = expect_wifi_list_opts(getopts(&["wifi", "list", "-c", "print"]));
+        assert_eq![opts.get_connect_via(), &WifiConnectionType::Print];
+
+        let err = getopts_safe(&["wifi", "-s", "iw", "list", "-c", "nmcli"]).unwrap_err();
+        assert_eq![err.kind, RuwiErrorKind::InvalidScanTypeAndConnectType];
+    }
+
+    #[test]
+    fn test_wifi_select_connect_via() {
+        let opts = expect_wifi_select_opts(getopts(&["wifi", "select"]));
+        assert_eq![opts.get_connect_via(), &WifiConnectionType::default()];
+
+        let opts = expect_wifi_select_opts(getopts(&["wifi", "select", "-a", "-c", "print"]));
+        assert_eq![opts.get_connect_via(), &WifiConnectionType::Print];
+        assert_eq![opts.get_auto_mode(), &AutoMode::KnownOrAsk];
+
+        let err = getopts_safe(&["wifi", "-s", "iw", "select", "-c", "nmcli"]).unwrap_err();
+        assert_eq![err.kind, RuwiErrorKind::InvalidScanTypeAndConnectType];
+    }
+
+    #[test]
+    fn test_wifi_forget() {
+        let opts = expect_wifi_forget_opts(getopts(&["wifi", "forget"]));
+        assert_eq![opts.get_connect_via(), &WifiConnectionType::default()];
+        assert_eq![opts.get_given_essid(), &None];
+        assert![!opts.get_skip_confirmation()];
+
+        let opts = expect_wifi_forget_opts(getopts(&[
+            "wifi", "forget", "-c", "nmcli", "-e", "Home Network", "-y",
+        ]));
+        assert_eq![opts.get_connect_via(), &WifiConnectionType::Nmcli];
+        assert_eq![opts.get_given_essid().clone().unwrap(), "Home Network"];
+        assert![opts.get_skip_confirmation()];
+    }
+
     #[test]
     fn test_wired_connect_via() {
         for connect_type in WiredConnectionType::iter() {
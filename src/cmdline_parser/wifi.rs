@@ -1,8 +1,15 @@
 use super::utils::handle_cmdline_parsing_error;
-use super::{WIFI_CONNECT_TOKEN, WIFI_SELECT_TOKEN};
+use super::{
+    ESSID_PRIORITY_TOKEN, SORT_POLICY_TOKEN, WIFI_CONNECT_TOKEN, WIFI_FORGET_TOKEN, WIFI_INFO_TOKEN,
+    WIFI_LIST_TOKEN, WIFI_SELECT_TOKEN,
+};
 
+use crate::essid_pattern::EssidPattern;
 use crate::options::command::RuwiWifiCommand;
 use crate::options::wifi::connect::WifiConnectOptions;
+use crate::options::wifi::forget::WifiForgetOptions;
+use crate::options::wifi::info::WifiInfoOptions;
+use crate::options::wifi::list::WifiListOptions;
 use crate::options::wifi::select::WifiSelectOptions;
 use crate::options::wifi::WifiOptions;
 use crate::options::GlobalOptions;
@@ -20,13 +27,22 @@ pub(super) fn get_wifi_cmd(
     maybe_wifi_matcher: Option<&ArgMatches>,
 ) -> Result<RuwiWifiCommand, RuwiError> {
     let cmd = if let Some(wifi_matcher) = maybe_wifi_matcher {
-        let wifi_opts = get_wifi_options(globals, wifi_matcher)?;
-
         let (subcommand_name, subcommand_matcher) = wifi_matcher.subcommand();
+        // `info` is run by fzf once per highlighted line, so it skips the system checks
+        // needed to build the usual wifi options.
+        if let (WIFI_INFO_TOKEN, Some(info_matcher)) = (subcommand_name, subcommand_matcher) {
+            return Ok(RuwiWifiCommand::Info(get_wifi_info_opts(globals, info_matcher)));
+        }
+
+        let wifi_opts = get_wifi_options(globals, wifi_matcher)?;
         if subcommand_name == "" || subcommand_name == WIFI_CONNECT_TOKEN {
             RuwiWifiCommand::Connect(get_wifi_connect_opts(wifi_opts, subcommand_matcher)?)
         } else if subcommand_name == WIFI_SELECT_TOKEN {
             RuwiWifiCommand::Select(get_wifi_select_opts(wifi_opts, subcommand_matcher)?)
+        } else if subcommand_name == WIFI_LIST_TOKEN {
+            RuwiWifiCommand::List(get_wifi_list_opts(wifi_opts, subcommand_matcher)?)
+        } else if subcommand_name == WIFI_FORGET_TOKEN {
+            RuwiWifiCommand::Forget(get_wifi_forget_opts(wifi_opts, subcommand_matcher))
         } else {
             handle_cmdline_parsing_error(subcommand_name, subcommand_matcher)?
         }
@@ -43,7 +59,19 @@ fn get_wifi_options(
     let scan_method = get_scan_method(wifi_matcher);
     let force_synchronous_scan = wifi_matcher.is_present("force_synchronous_scan");
     let ignore_known = wifi_matcher.is_present("ignore_known");
+    let strict_parse = wifi_matcher.is_present("strict_parse");
     let given_interface_name = wifi_matcher.value_of("interface").map(String::from);
+    let essid_priority = wifi_matcher
+        .values_of(ESSID_PRIORITY_TOKEN)
+        .map(|essids| essids.map(String::from).collect::<Vec<_>>())
+        .unwrap_or_default();
+    let sort_policy = if wifi_matcher.is_present(SORT_POLICY_TOKEN) || essid_priority.is_empty() {
+        get_val_as_enum::<SortPolicy>(wifi_matcher, SORT_POLICY_TOKEN)
+    } else {
+        SortPolicy::Priority
+    };
+    let detect_scan_type =
+        scan_method != ScanMethod::ByRunning && !wifi_matcher.is_present(SCAN_TYPE_TOKEN);
     let scan_type = if wifi_matcher.is_present(SCAN_TYPE_TOKEN) {
         get_val_as_enum::<WifiScanType>(&wifi_matcher, SCAN_TYPE_TOKEN)
     } else {
@@ -58,6 +86,10 @@ fn get_wifi_options(
         .given_interface_name(given_interface_name)
         .ignore_known(ignore_known)
         .force_synchronous_scan(force_synchronous_scan)
+        .strict_parse(strict_parse)
+        .detect_scan_type(detect_scan_type)
+        .sort_policy(sort_policy)
+        .essid_priority(essid_priority)
         .build();
     validate_wifi_options(wifi_opts)
 }
@@ -97,8 +129,14 @@ fn get_wifi_connect_opts(
 ) -> Result<WifiConnectOptions, RuwiError> {
     let connect_opts = if let Some(connect_matcher) = maybe_connect_matcher {
         let force_ask_password = connect_matcher.is_present("force_ask_password");
+        let confirm_password = connect_matcher.is_present("confirm_password");
+        let hash_password = !connect_matcher.is_present("plaintext_password");
+        let password_prompt_method =
+            get_val_as_enum::<PasswordPromptMethod>(connect_matcher, "password_prompt");
         let given_essid = connect_matcher.value_of("essid").map(String::from);
-        let given_encryption_key = connect_matcher.value_of("password").map(String::from);
+        let given_essid_is_hidden = connect_matcher.is_present("hidden");
+        let given_essid_pattern = connect_matcher.value_of("match").map(EssidPattern::new);
+        let given_encryption_key = connect_matcher.value_of("password").map(EncryptionKey::from);
 
         let auto_mode = if connect_matcher.is_present("auto") {
             AutoMode::KnownOrAsk
@@ -117,9 +155,14 @@ fn get_wifi_connect_opts(
             .wifi(wifi_opts)
             .connect_via(connect_via)
             .given_essid(given_essid)
+            .given_essid_is_hidden(given_essid_is_hidden)
+            .given_essid_pattern(given_essid_pattern)
             .given_encryption_key(given_encryption_key)
             .auto_mode(auto_mode)
             .force_ask_password(force_ask_password)
+            .confirm_password(confirm_password)
+            .hash_password(hash_password)
+            .password_prompt_method(password_prompt_method)
             .build()
     } else {
         get_default_wifi_connect_opts_from_system(wifi_opts)
@@ -132,24 +175,117 @@ fn get_wifi_select_opts(
     wifi_opts: WifiOptions,
     maybe_select_matcher: Option<&ArgMatches>,
 ) -> Result<WifiSelectOptions, RuwiError> {
-    let select_builder = WifiSelectOptions::builder().wifi(wifi_opts);
-    let select_opts = if let Some(select_matcher) = maybe_select_matcher {
+    let (auto_mode, given_connect_via) = if let Some(select_matcher) = maybe_select_matcher {
         let auto_mode = if select_matcher.is_present("auto") {
             AutoMode::KnownOrAsk
         } else {
             get_val_as_enum::<AutoMode>(&select_matcher, "auto_mode")
         };
-
-        select_builder.auto_mode(auto_mode).build()
+        let given_connect_via = if select_matcher.is_present(CONNECT_VIA_TOKEN) {
+            Some(get_val_as_enum::<WifiConnectionType>(
+                select_matcher,
+                CONNECT_VIA_TOKEN,
+            ))
+        } else {
+            None
+        };
+        (auto_mode, given_connect_via)
     } else {
-        select_builder.build()
+        (AutoMode::default(), None)
     };
+
+    // Used to find which of the seen networks are known.
+    let connect_via = given_connect_via.unwrap_or_else(|| {
+        let checker = SystemCheckerReal::new(&wifi_opts);
+        WifiConnectionType::choose_best_from_system(&checker, CONNECT_VIA_TOKEN)
+    });
+
+    let select_opts = WifiSelectOptions::builder()
+        .wifi(wifi_opts)
+        .auto_mode(auto_mode)
+        .connect_via(connect_via)
+        .build();
     validate_wifi_select_options(select_opts)
 }
 
+fn get_wifi_list_opts(
+    wifi_opts: WifiOptions,
+    maybe_list_matcher: Option<&ArgMatches>,
+) -> Result<WifiListOptions, RuwiError> {
+    let (format, given_connect_via) = if let Some(list_matcher) = maybe_list_matcher {
+        let format = get_val_as_enum::<WifiListFormat>(list_matcher, "format");
+        let given_connect_via = if list_matcher.is_present(CONNECT_VIA_TOKEN) {
+            Some(get_val_as_enum::<WifiConnectionType>(
+                list_matcher,
+                CONNECT_VIA_TOKEN,
+            ))
+        } else {
+            None
+        };
+        (format, given_connect_via)
+    } else {
+        (WifiListFormat::default(), None)
+    };
+
+    let connect_via = given_connect_via.unwrap_or_else(|| {
+        let checker = SystemCheckerReal::new(&wifi_opts);
+        WifiConnectionType::choose_best_from_system(&checker, CONNECT_VIA_TOKEN)
+    });
+
+    let list_opts = WifiListOptions::builder()
+        .wifi(wifi_opts)
+        .connect_via(connect_via)
+        .format(format)
+        .build();
+    validate_wifi_list_options(list_opts)
+}
+
+fn get_wifi_forget_opts(
+    wifi_opts: WifiOptions,
+    maybe_forget_matcher: Option<&ArgMatches>,
+) -> WifiForgetOptions {
+    let (given_essid, skip_confirmation, given_connect_via) =
+        if let Some(forget_matcher) = maybe_forget_matcher {
+            let given_essid = forget_matcher.value_of("essid").map(String::from);
+            let skip_confirmation = forget_matcher.is_present("yes");
+            let given_connect_via = if forget_matcher.is_present(CONNECT_VIA_TOKEN) {
+                Some(get_val_as_enum::<WifiConnectionType>(
+                    forget_matcher,
+                    CONNECT_VIA_TOKEN,
+                ))
+            } else {
+                None
+            };
+            (given_essid, skip_confirmation, given_connect_via)
+        } else {
+            (None, false, None)
+        };
+
+    // Known networks are looked up in, and removed from, this connection manager.
+    let connect_via = given_connect_via.unwrap_or_else(|| {
+        let checker = SystemCheckerReal::new(&wifi_opts);
+        WifiConnectionType::choose_best_from_system(&checker, CONNECT_VIA_TOKEN)
+    });
+
+    WifiForgetOptions::builder()
+        .wifi(wifi_opts)
+        .connect_via(connect_via)
+        .given_essid(given_essid)
+        .skip_confirmation(skip_confirmation)
+        .build()
+}
+
+fn get_wifi_info_opts(globals: GlobalOptions, info_matcher: &ArgMatches) -> WifiInfoOptions {
+    WifiInfoOptions::builder()
+        .globals(globals)
+        .details_file(info_matcher.value_of("details_file").unwrap_or_default().to_string())
+        .selected_line(info_matcher.value_of("selected_line").unwrap_or_default().to_string())
+        .build()
+}
+
 fn get_scan_method(m: &ArgMatches) -> ScanMethod {
-    if let Some(filename) = m.value_of("input_file").map(String::from) {
-        ScanMethod::FromFile(filename)
+    if let Some(filenames) = m.values_of("input_file") {
+        ScanMethod::FromFiles(filenames.map(String::from).collect())
     } else if m.is_present("input_stdin") {
         ScanMethod::FromStdin
     } else {
@@ -173,13 +309,26 @@ fn validate_wifi_options(options: WifiOptions) -> Result<WifiOptions, RuwiError>
 fn validate_wifi_connect_options(
     options: WifiConnectOptions,
 ) -> Result<WifiConnectOptions, RuwiError> {
+    validate_scan_type_and_connect_via(&options)?;
+    Ok(options)
+}
+
+fn validate_wifi_list_options(options: WifiListOptions) -> Result<WifiListOptions, RuwiError> {
+    validate_scan_type_and_connect_via(&options)?;
+    Ok(options)
+}
+
+fn validate_scan_type_and_connect_via<O>(options: &O) -> Result<(), RuwiError>
+where
+    O: Wifi + WifiConnectionManager,
+{
     let scan_method = options.get_scan_method();
     let scan_type = options.get_scan_type();
     let connect_via = options.get_connect_via();
     match (scan_method, connect_via) {
         (ScanMethod::ByRunning, WifiConnectionType::Nmcli) => {
             if let WifiScanType::Nmcli = scan_type {
-                Ok(options)
+                Ok(())
             } else {
                 Err(rerr!(
                     RuwiErrorKind::InvalidScanTypeAndConnectType,
@@ -188,12 +337,13 @@ fn validate_wifi_connect_options(
                 ))
             }
         }
-        _ => Ok(options),
+        _ => Ok(()),
     }
 }
 
 fn validate_wifi_select_options(
     options: WifiSelectOptions,
 ) -> Result<WifiSelectOptions, RuwiError> {
+    validate_scan_type_and_connect_via(&options)?;
     Ok(options)
 }
@@ -1,20 +1,21 @@
 use crate::prelude::*;
 use crate::interface_management::ip_interfaces::WifiIPInterface;
-use crate::netctl::NetctlConfigHandler;
+use crate::netctl::{NetctlConfigHandler, NetctlIdentifier};
 
 pub(crate) fn possibly_configure_network<O>(
     options: &O,
     interface: &WifiIPInterface,
     network: &AnnotatedWirelessNetwork,
-    encryption_key: &Option<String>,
-) -> Result<(), RuwiError>
+    encryption_key: &Option<EncryptionKey>,
+) -> Result<Option<NetctlIdentifier>, RuwiError>
 where
     O: Global + Wifi + WifiConnect,
 {
-    let res = if !network.is_known() || options.get_given_encryption_key().is_some() {
+    // Known networks are only reconfigured when a new key was given or asked for.
+    let res = if !network.is_known() || encryption_key.is_some() {
         configure_network(options, interface, network, encryption_key)
     } else {
-        Ok(())
+        Ok(None)
     };
 
     if options.d() {
@@ -28,16 +29,19 @@ fn configure_network<O>(
     options: &O,
     interface: &WifiIPInterface,
     network: &AnnotatedWirelessNetwork,
-    encryption_key: &Option<String>,
-) -> Result<(), RuwiError>
+    encryption_key: &Option<EncryptionKey>,
+) -> Result<Option<NetctlIdentifier>, RuwiError>
 where
     O: Global + Wifi + WifiConnect,
 {
     let cv = options.get_connect_via();
     match cv {
         WifiConnectionType::Netctl => NetctlConfigHandler::new(options)
-            .write_wifi_config(interface, network, encryption_key)
-            .and(Ok(())),
-        WifiConnectionType::Nmcli | WifiConnectionType::None | WifiConnectionType::Print => Ok(()),
+            .write_wifi_config(interface, network, encryption_key, options.get_hash_password())
+            .map(Some),
+        WifiConnectionType::Nmcli
+        | WifiConnectionType::Iwd
+        | WifiConnectionType::None
+        | WifiConnectionType::Print => Ok(None),
     }
 }
@@ -1,6 +1,7 @@
 use crate::prelude::*;
 
 use crate::interface_management::ip_interfaces::{LinuxIPInterface, WifiIPInterface};
+use crate::iwd::{get_iwd_known_network_path, write_iwd_known_network};
 use crate::run_commands::SystemCommandRunner;
 use crate::netctl::utils::netctl_switch_to;
 use crate::netctl::NetctlIdentifier;
@@ -9,7 +10,8 @@ pub(crate) fn connect_to_network<O>(
     options: &O,
     interface: &WifiIPInterface,
     selected_network: &AnnotatedWirelessNetwork,
-    encryption_key: &Option<String>,
+    encryption_key: &Option<EncryptionKey>,
+    configured_netctl_identifier: Option<&NetctlIdentifier>,
 ) -> Result<ConnectionResult, RuwiError>
 where
     O: Global + Wifi + WifiConnect,
@@ -26,7 +28,9 @@ where
                 selected_network.get_public_name()
             );
         }
-        conn_type @ WifiConnectionType::Netctl | conn_type @ WifiConnectionType::Nmcli => {
+        conn_type @ (WifiConnectionType::Netctl
+        | WifiConnectionType::Nmcli
+        | WifiConnectionType::Iwd) => {
             eprintln!(
                 "[NOTE]: Attempting to use {} to connect to: \"{}\"",
                 conn_type,
@@ -36,9 +40,16 @@ where
     }
 
     let res = match connect_via {
-        WifiConnectionType::Netctl => connect_via_netctl(options, interface, selected_network),
+        WifiConnectionType::Netctl => {
+            let netctl_identifier =
+                get_netctl_identifier(selected_network, configured_netctl_identifier);
+            connect_via_netctl(options, interface, &netctl_identifier)
+        }
         WifiConnectionType::Nmcli => {
-            connect_via_networkmanager(options, selected_network, encryption_key)
+            connect_via_networkmanager(options, interface, selected_network, encryption_key)
+        }
+        WifiConnectionType::Iwd => {
+            connect_via_iwd(options, interface, selected_network, encryption_key)
         }
         WifiConnectionType::Print => {
             let essid = selected_network.get_public_name();
@@ -69,7 +80,7 @@ where
                 );
             }
             WifiConnectionType::Print => {}
-            WifiConnectionType::Netctl | WifiConnectionType::Nmcli => {
+            WifiConnectionType::Netctl | WifiConnectionType::Nmcli | WifiConnectionType::Iwd => {
                 eprintln!(
                     "[NOTE]: Successfully connected to: \"{}\"",
                     selected_network.get_public_name()
@@ -94,15 +105,27 @@ where
         scan_service.stop(options)?;
     }
 
+    connect_service.stop_conflicting_services(options, interface)?;
     connect_service.start(options)?;
 
     Ok(())
 }
 
+// A config which was just written may not have the network's usual name, if that name belonged to
+// another network's config, so the name it was written under is used when there is one.
+fn get_netctl_identifier(
+    selected_network: &AnnotatedWirelessNetwork,
+    configured_netctl_identifier: Option<&NetctlIdentifier>,
+) -> NetctlIdentifier {
+    configured_netctl_identifier
+        .cloned()
+        .unwrap_or_else(|| NetctlIdentifier::from(selected_network))
+}
+
 fn connect_via_netctl<O>(
     options: &O,
     interface: &WifiIPInterface,
-    selected_network: &AnnotatedWirelessNetwork,
+    netctl_identifier: &NetctlIdentifier,
 ) -> Result<ConnectionResult, RuwiError>
 where
     O: Global,
@@ -114,8 +137,7 @@ where
     }
     interface.bring_down(options)?;
 
-    let netctl_identifier = NetctlIdentifier::from(selected_network);
-    let netctl_switch_to_res = netctl_switch_to(options, &netctl_identifier);
+    let netctl_switch_to_res = netctl_switch_to(options, netctl_identifier);
 
     netctl_switch_to_res.map(|_| ConnectionResult {
             connection_type: WifiConnectionType::Netctl,
@@ -124,8 +146,9 @@ where
 
 fn connect_via_networkmanager<O>(
     options: &O,
+    interface: &WifiIPInterface,
     selected_network: &AnnotatedWirelessNetwork,
-    encryption_key: &Option<String>,
+    encryption_key: &Option<EncryptionKey>,
 ) -> Result<ConnectionResult, RuwiError>
 where
     O: Global,
@@ -144,6 +167,24 @@ where
     SystemCommandRunner::new(options, "nmcli", &["device", "wifi", "list"])
         .run_command_status_dumb();
 
+    // `nmcli device wifi connect` guesses the key management type, and guesses wrong for
+    // e.g. WPA3-only networks, so new networks with a known security type get a profile
+    // created for them explicitly.
+    let key_mgmt = selected_network
+        .get_security()
+        .and_then(get_networkmanager_key_mgmt);
+    if let (false, Some(key_mgmt), Some(pw)) =
+        (selected_network.is_known(), key_mgmt, encryption_key)
+    {
+        return connect_via_new_networkmanager_profile(
+            options,
+            interface,
+            selected_network,
+            key_mgmt,
+            pw.as_str(),
+        );
+    }
+
     let args = vec![
         "device",
         "wifi",
@@ -151,11 +192,16 @@ where
         selected_network.get_public_name(),
     ];
     let args = if let Some(pw) = encryption_key {
-        let pw_args = vec!["password", pw];
+        let pw_args = vec!["password", pw.as_str()];
         args.into_iter().chain(pw_args).collect()
     } else {
         args
     };
+    let args = if selected_network.is_hidden() {
+        args.into_iter().chain(vec!["hidden", "yes"]).collect()
+    } else {
+        args
+    };
 
     SystemCommandRunner::new(options, "nmcli", &args)
         .run_command_pass(
@@ -167,9 +213,164 @@ where
         })
 }
 
+fn connect_via_new_networkmanager_profile<O>(
+    options: &O,
+    interface: &WifiIPInterface,
+    selected_network: &AnnotatedWirelessNetwork,
+    key_mgmt: &str,
+    encryption_key: &str,
+) -> Result<ConnectionResult, RuwiError>
+where
+    O: Global,
+{
+    let essid = selected_network.get_public_name();
+    let key_field = if key_mgmt == "none" {
+        "wifi-sec.wep-key0"
+    } else {
+        "wifi-sec.psk"
+    };
+
+    let hidden = if selected_network.is_hidden() {
+        "yes"
+    } else {
+        "no"
+    };
+
+    SystemCommandRunner::new(
+        options,
+        "nmcli",
+        &[
+            "connection",
+            "add",
+            "type",
+            "wifi",
+            "con-name",
+            essid,
+            "ifname",
+            interface.get_ifname(),
+            "ssid",
+            essid,
+            "wifi.hidden",
+            hidden,
+            "wifi-sec.key-mgmt",
+            key_mgmt,
+            key_field,
+            encryption_key,
+        ],
+    )
+    .run_command_pass(
+        RuwiErrorKind::FailedToConnectViaNetworkManager,
+        "Failed to create a NetworkManager profile using nmcli!",
+    )?;
+
+    SystemCommandRunner::new(options, "nmcli", &["connection", "up", "id", essid])
+        .run_command_pass(
+            RuwiErrorKind::FailedToConnectViaNetworkManager,
+            "Failed to connect using nmcli!",
+        )
+        .map(|_| ConnectionResult {
+            connection_type: WifiConnectionType::Nmcli,
+        })
+}
+
+// Values for NetworkManager's `802-11-wireless-security.key-mgmt` setting. WEP uses "none",
+// and 802.1X networks need more configuration than ruwi knows about.
+fn get_networkmanager_key_mgmt(security: WifiSecurity) -> Option<&'static str> {
+    match security {
+        WifiSecurity::Wep => Some("none"),
+        WifiSecurity::WpaPsk | WifiSecurity::Wpa2Psk => Some("wpa-psk"),
+        WifiSecurity::Wpa3Sae => Some("sae"),
+        WifiSecurity::Open | WifiSecurity::Enterprise => None,
+    }
+}
+
+fn connect_via_iwd<O>(
+    options: &O,
+    interface: &WifiIPInterface,
+    selected_network: &AnnotatedWirelessNetwork,
+    encryption_key: &Option<EncryptionKey>,
+) -> Result<ConnectionResult, RuwiError>
+where
+    O: Global,
+{
+    if options.get_dry_run() {
+        return Ok(ConnectionResult {
+            connection_type: WifiConnectionType::Iwd,
+        });
+    }
+
+    // Without a passphrase in the network's file, iwctl would interactively prompt for one.
+    if let Some(pw) = encryption_key {
+        let path = get_iwd_known_network_path(selected_network.get_public_name(), true);
+        write_iwd_known_network(&path, pw.as_str())?;
+    }
+    // Hidden networks aren't in iwd's scan results, so must be connected to by name.
+    let connect_cmd = if selected_network.is_hidden() {
+        "connect-hidden"
+    } else {
+        "connect"
+    };
+    SystemCommandRunner::new(
+        options,
+        "iwctl",
+        &[
+            "station",
+            interface.get_ifname(),
+            connect_cmd,
+            selected_network.get_public_name(),
+        ],
+    )
+        .run_command_pass(
+            RuwiErrorKind::FailedToConnectViaIwd,
+            "Failed to connect using iwctl!",
+        )
+        .map(|_| ConnectionResult {
+            connection_type: WifiConnectionType::Iwd,
+        })
+}
+
 #[cfg(test)]
 mod tests {
-    // use super::*;
+    use super::*;
+    use crate::netctl::{NetctlConfigHandler, NetctlRawConfigContents};
+    use crate::options::wifi::connect::WifiConnectOptions;
+
+    #[test]
+    fn test_connect_via_netctl_uses_configured_identifier() -> Result<(), RuwiError> {
+        let opts = WifiConnectOptions::default();
+        let handler = NetctlConfigHandler::builder()
+            .opts(&opts)
+            .given_configs(vec![(
+                NetctlIdentifier::new("Home"),
+                NetctlRawConfigContents::new("Connection=wireless\nESSID='HomeNet'"),
+            )])
+            .build();
+        let network = AnnotatedWirelessNetwork::builder()
+            .essid("Home")
+            .is_encrypted(true)
+            .build();
+        let configured = handler.write_wifi_config(
+            &WifiIPInterface::default(),
+            &network,
+            &Some("hunter22".into()),
+            true,
+        )?;
+
+        let expected = NetctlIdentifier::from_essid_with_suffix("Home");
+        assert_eq![NetctlIdentifier::from(&network), NetctlIdentifier::new("Home")];
+        assert_eq![configured, expected];
+        assert_eq![get_netctl_identifier(&network, Some(&configured)), expected];
+        Ok(())
+    }
+
+    #[test]
+    fn test_connect_via_netctl_to_known_network() {
+        let network = AnnotatedWirelessNetwork::builder()
+            .essid("Cafe Wifi")
+            .service_identifier(NetworkingServiceIdentifier::netctl_nw("cafe"))
+            .build();
+        assert_eq![get_netctl_identifier(&network, None), NetctlIdentifier::new("cafe")];
+    }
 
     //    #[test]
     //    fn test_connect_via_netctl_pass() {
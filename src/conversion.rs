@@ -3,11 +3,10 @@ use crate::prelude::*;
 
 impl From<&AnnotatedWirelessNetwork> for NetctlIdentifier {
     fn from(nw: &AnnotatedWirelessNetwork) -> Self {
-        let ident = match nw.get_service_identifier() {
-            Some(NetworkingServiceIdentifier::Netctl(ident)) => ident.clone(),
-            _ => nw.get_public_name().replace(" ", "_"),
-        };
-        Self::new(ident)
+        match nw.get_service_identifier() {
+            Some(NetworkingServiceIdentifier::Netctl(ident)) => Self::new(ident),
+            _ => Self::from_essid(nw.get_public_name()),
+        }
     }
 }
 
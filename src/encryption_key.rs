@@ -1,24 +1,75 @@
 use crate::prelude::*;
+use crate::select::key_actions::SelectionKeyAction;
 use crate::select::prompt_for_encryption_key::prompt_for_encryption_key;
 
 pub(crate) fn possibly_get_encryption_key<O>(
     options: &O,
     selected_network: &AnnotatedWirelessNetwork,
-) -> Result<Option<String>, RuwiError>
+) -> Result<Option<EncryptionKey>, RuwiError>
 where
     O: Global + Wifi + WifiConnect,
 {
     possibly_get_encryption_key_impl(options, selected_network, prompt_for_encryption_key)
 }
 
+// A key action chosen in the selector overrides the usual logic for whether to ask for a key.
+pub(crate) fn get_encryption_key_for_key_action<O>(
+    options: &O,
+    selected_network: &AnnotatedWirelessNetwork,
+    key_action: Option<&SelectionKeyAction>,
+) -> Result<Option<EncryptionKey>, RuwiError>
+where
+    O: Global + Wifi + WifiConnect,
+{
+    match key_action {
+        Some(SelectionKeyAction::ConnectWithoutPassword) => {
+            check_can_connect_without_password(options, selected_network).map(|()| None)
+        }
+        Some(SelectionKeyAction::ForcePasswordPrompt) => {
+            prompt_for_encryption_key(options, selected_network.get_public_name()).map(Some)
+        }
+        Some(SelectionKeyAction::ForgetNetwork) | None => {
+            possibly_get_encryption_key(options, selected_network)
+        }
+    }
+}
+
+// An unknown encrypted network has no saved password to fall back on, so connecting to it without
+// one can only fail.
+fn check_can_connect_without_password<O>(
+    options: &O,
+    selected_network: &AnnotatedWirelessNetwork,
+) -> Result<(), RuwiError>
+where
+    O: WifiConnect,
+{
+    let needs_password = match options.get_connect_via() {
+        WifiConnectionType::Netctl | WifiConnectionType::Nmcli | WifiConnectionType::Iwd => {
+            selected_network.is_encrypted() && !selected_network.is_known()
+        }
+        WifiConnectionType::None | WifiConnectionType::Print => false,
+    };
+    if needs_password {
+        Err(rerr!(
+            RuwiErrorKind::NoPasswordForUnknownEncryptedNetwork,
+            format!(
+                "\"{}\" is encrypted and isn't a known network, so it can't be connected to without a password. Select it without alt-n to be asked for one.",
+                selected_network.get_public_name()
+            )
+        ))
+    } else {
+        Ok(())
+    }
+}
+
 fn possibly_get_encryption_key_impl<O, F>(
     options: &O,
     selected_network: &AnnotatedWirelessNetwork,
     prompt_func: F,
-) -> Result<Option<String>, RuwiError>
+) -> Result<Option<EncryptionKey>, RuwiError>
 where
     O: Global + Wifi + WifiConnect,
-    F: Fn(&O, &str) -> Result<String, RuwiError>,
+    F: Fn(&O, &str) -> Result<EncryptionKey, RuwiError>,
 {
     // Don't bother asking for a password if:
     // * a password was given on the command line
@@ -28,7 +79,7 @@ where
     let pw = match &options.get_given_encryption_key() {
         Some(pw) => Some(pw.clone()),
         None => match options.get_connect_via() {
-            WifiConnectionType::Netctl | WifiConnectionType::Nmcli => {
+            WifiConnectionType::Netctl | WifiConnectionType::Nmcli | WifiConnectionType::Iwd => {
                 if options.get_force_ask_password()
                     || (!selected_network.is_known() && selected_network.is_encrypted())
                 {
@@ -57,7 +108,7 @@ mod tests {
     use crate::options::wifi::connect::WifiConnectOptions;
     use crate::options::wifi::WifiOptions;
 
-    fn should_not_run(_opt: &WifiConnectOptions, _nw: &str) -> Result<String, RuwiError> {
+    fn should_not_run(_opt: &WifiConnectOptions, _nw: &str) -> Result<EncryptionKey, RuwiError> {
         panic!("Should not run.")
     }
 
@@ -97,8 +148,8 @@ mod tests {
             .is_encrypted(true)
             .build();
         let output =
-            possibly_get_encryption_key_impl(&options, &nw, |_, _| Ok(fake_essid.to_string()))?;
-        assert_eq![output.unwrap(), fake_essid];
+            possibly_get_encryption_key_impl(&options, &nw, |_, _| Ok(fake_essid.into()))?;
+        assert_eq![output.unwrap().as_str(), fake_essid];
         Ok(())
     }
 
@@ -111,7 +162,7 @@ mod tests {
             .build();
         let nw = AnnotatedWirelessNetwork::from_essid_only("FAKE");
         let output = possibly_get_encryption_key_impl(&options, &nw, should_not_run)?;
-        assert_eq![output.unwrap(), given_essid];
+        assert_eq![output.unwrap().as_str(), given_essid];
         Ok(())
     }
 
@@ -125,8 +176,37 @@ mod tests {
         let nw = AnnotatedWirelessNetwork::from_essid_only("FAKE");
         let fake_essid = "FAKE_CLOSURE_VALUE".to_string();
         let output =
-            possibly_get_encryption_key_impl(&options, &nw, |_, _| Ok(fake_essid.clone()))?;
-        assert_eq![output.unwrap(), fake_essid];
+            possibly_get_encryption_key_impl(&options, &nw, |_, _| Ok(fake_essid.clone().into()))?;
+        assert_eq![output.unwrap().as_str(), fake_essid];
+        Ok(())
+    }
+
+    #[test]
+    fn test_connect_without_password_key_action() -> Result<(), RuwiError> {
+        let options = WifiConnectOptions::default();
+        let key_action = Some(&SelectionKeyAction::ConnectWithoutPassword);
+        let unknown = AnnotatedWirelessNetwork::builder()
+            .essid("MADE_UP")
+            .is_encrypted(true)
+            .build();
+        let err = get_encryption_key_for_key_action(&options, &unknown, key_action).unwrap_err();
+        assert_eq![err.kind, RuwiErrorKind::NoPasswordForUnknownEncryptedNetwork];
+
+        let known = AnnotatedWirelessNetwork::builder()
+            .essid("MADE_UP")
+            .service_identifier(NetworkingServiceIdentifier::netctl_nw("I_AM_KNOWN"))
+            .is_encrypted(true)
+            .build();
+        assert![get_encryption_key_for_key_action(&options, &known, key_action)?.is_none()];
+
+        let open = AnnotatedWirelessNetwork::from_essid_only("MADE_UP");
+        assert![get_encryption_key_for_key_action(&options, &open, key_action)?.is_none()];
+
+        let options = WifiConnectOptions::builder()
+            .wifi(WifiOptions::default())
+            .connect_via(WifiConnectionType::Print)
+            .build();
+        assert![get_encryption_key_for_key_action(&options, &unknown, key_action)?.is_none()];
         Ok(())
     }
 
@@ -8,7 +8,7 @@ use crate::interface_management::ip_interfaces::WifiIPInterface;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ScanMethod {
     ByRunning,
-    FromFile(String),
+    FromFiles(Vec<String>),
     FromStdin,
 }
 
@@ -37,8 +37,9 @@ pub enum WifiScanType {
     WpaCli,
     RuwiJSON,
     Nmcli,
-    //#[strum(serialize = "iwlist")]
-    //IWList,
+    #[strum(serialize = "iwlist")]
+    IWList,
+    Iwctl,
 }
 
 impl Default for WifiScanType {
@@ -50,9 +51,15 @@ impl Default for WifiScanType {
 #[derive(Debug, Clone, PartialEq, Eq, EnumString, EnumIter, Display, AsStaticStr)]
 #[strum(serialize_all = "lowercase")]
 pub enum SelectionMethod {
+    Bemenu,
+    Builtin,
+    Custom,
     Dmenu,
+    Fuzzel,
     Fzf,
     NoCurses,
+    Rofi,
+    Wofi,
 }
 
 impl Default for SelectionMethod {
@@ -60,12 +67,60 @@ impl Default for SelectionMethod {
         Self::Fzf
     }
 }
+
+// The fields which can be used in a `--display-template`, e.g. "{signal_bars} {essid}".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, EnumIter, Display, AsStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum DisplayField {
+    SignalBars,
+    Dbm,
+    Quality,
+    #[strum(serialize = "essid", serialize = "name")]
+    Essid,
+    Band,
+    Security,
+    Known,
+    Bssid,
+}
+
+// How networks are ordered in the selector, and so which one the auto modes pick first.
+#[derive(Debug, Clone, PartialEq, Eq, EnumString, EnumIter, Display, AsStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum SortPolicy {
+    KnownFirst,
+    Signal,
+    #[strum(serialize = "prefer_5ghz")]
+    Prefer5GHz,
+    OpenLast,
+    Priority,
+}
+
+impl Default for SortPolicy {
+    fn default() -> Self {
+        Self::KnownFirst
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, EnumString, EnumIter, Display, AsStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum PasswordPromptMethod {
+    Selector,
+    Pinentry,
+}
+
+impl Default for PasswordPromptMethod {
+    fn default() -> Self {
+        Self::Selector
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, EnumString, EnumIter, Display, AsStaticStr)]
 #[strum(serialize_all = "snake_case")]
 pub enum WifiConnectionType {
     None,
     Netctl,
     Nmcli,
+    Iwd,
     Print,
 }
 
@@ -105,6 +160,72 @@ impl Default for AutoMode {
     }
 }
 
+// What to select when the selector is closed by `--select-timeout`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, EnumIter, Display, AsStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum SelectTimeoutFallback {
+    Known,
+    First,
+    Fail,
+}
+
+impl Default for SelectTimeoutFallback {
+    fn default() -> Self {
+        Self::Known
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, EnumString, EnumIter, Display, AsStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum WifiListFormat {
+    Human,
+    Json,
+    Tsv,
+}
+
+impl Default for WifiListFormat {
+    fn default() -> Self {
+        Self::Human
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, EnumIter, Display, AsStaticStr)]
+pub enum WifiSecurity {
+    #[strum(serialize = "Open")]
+    Open,
+    #[strum(serialize = "WEP")]
+    Wep,
+    #[strum(serialize = "WPA-PSK")]
+    WpaPsk,
+    #[strum(serialize = "WPA2-PSK")]
+    Wpa2Psk,
+    #[strum(serialize = "WPA3-SAE")]
+    Wpa3Sae,
+    #[strum(serialize = "802.1X")]
+    Enterprise,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, EnumIter, Display, AsStaticStr)]
+pub enum WifiBand {
+    #[strum(serialize = "2.4GHz")]
+    TwoPointFour,
+    #[strum(serialize = "5GHz")]
+    Five,
+    #[strum(serialize = "6GHz")]
+    Six,
+}
+
+impl WifiBand {
+    pub(crate) fn from_frequency(freq_mhz: u32) -> Option<Self> {
+        match freq_mhz {
+            2400..=2500 => Some(Self::TwoPointFour),
+            4900..=5924 => Some(Self::Five),
+            5925..=7125 => Some(Self::Six),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SynchronousRescanType {
     NoneSeen,
@@ -116,6 +237,7 @@ pub enum SynchronousRescanType {
 pub(crate) enum NetworkingService {
     Netctl,
     NetworkManager,
+    Iwd,
     WpaSupplicant(WifiIPInterface),
     None,
 }
@@ -127,6 +249,7 @@ pub(crate) enum NetworkingService {
 pub enum NetworkingServiceIdentifier {
     Netctl(String),
     NetworkManager,
+    Iwd,
 }
 
 impl NetworkingServiceIdentifier {
@@ -1,4 +1,3 @@
-use crate::rerr;
 use std::error::Error;
 use std::fmt;
 
@@ -7,20 +6,28 @@ use std::fmt;
 #[derive(Debug, PartialEq, Eq)]
 pub enum RuwiErrorKind {
     BinaryWritableByNonRootWhenRunningAsRoot,
+    BuiltinSelectorFailed,
     CmdLineOptParserSafeFailedInTest,
     CommandFindingSpawnError,
     CommandNotFound,
+    EnterpriseNetworksMustBeConfiguredManually,
     ErrorReadingNetctlDir,
     FailedToBringLinuxNetworkingInterfaceDown,
     FailedToBringLinuxNetworkingInterfaceUp,
     FailedToConnectViaBluetoothCtl,
+    FailedToConnectViaIwd,
     FailedToConnectViaNetctl,
     FailedToConnectViaNetworkManager,
+    FailedToDetectScanType,
     FailedToFindDevicesWithBluetoothCtl,
+    FailedToForgetNetworkViaIwd,
+    FailedToForgetNetworkViaNetworkManager,
     FailedToListKnownNetworksWithNetworkManager,
     FailedToListDevicesWithBlurz,
     FailedToLookForWpaSupplicantProc,
     FailedToPairViaBluetoothCtl,
+    FailedToReadIwdKnownNetworks,
+    FailedToReadPreviewDetails,
     FailedToPowerOnBluetoothAdapterWithBlurz,
     FailedToPowerOffBluetoothAdapterWithBlurz,
     FailedToParseIPLinkOutput,
@@ -30,11 +37,16 @@ pub enum RuwiErrorKind {
     FailedToRawConnectViaNmcli,
     FailedToReadScanResultsFromFile,
     FailedToReadScanResultsFromStdin,
+    FailedToRemoveNetctlConfig,
     FailedToRunBluetoothCtlAgentOn,
     FailedToRunBluetoothCtlDefaultAgent,
     FailedToRunBluetoothCtlPowerOff,
     FailedToRunBluetoothCtlPowerOn,
     FailedToRunIPLinkShow,
+    FailedToRunIwctlGetNetworks,
+    FailedToRunIwctlScan,
+    FailedToRunIwctlShow,
+    FailedToRunIWListScan,
     FailedToRunIWScanAbort,
     FailedToRunIWScanDump,
     FailedToRunIWScanSynchronous,
@@ -43,8 +55,10 @@ pub enum RuwiErrorKind {
     FailedToRunNmcliScanSynchronous,
     FailedToScanWithBluetoothCtl,
     FailedToScanWithWPACli,
+    FailedToSerializeNetworkList,
     FailedToSpawnThread,
     FailedToStartBluetoothService,
+    FailedToStartIwd,
     FailedToStartBlurzAdapter,
     FailedToStartBlurzDiscovery,
     FailedToStartBlurzDiscoverySession,
@@ -53,12 +67,20 @@ pub enum RuwiErrorKind {
     FailedToStartNetworkManager,
     FailedToStartWpaSupplicant,
     FailedToStopBluetoothService,
+    FailedToStopIwd,
     FailedToStopNetctl,
     FailedToStopNetworkManager,
     FailedToStopWpaSupplicant,
+    FailedToWriteIwdKnownNetwork,
     FailedToWriteNetctlConfig,
+    FailedToWritePreviewDetails,
+    GivenNetworkIsNotKnown,
+    HiddenNetworkWithoutESSID,
     IWSynchronousScanFailed,
     IWSynchronousScanRanOutOfRetries,
+    IwctlSynchronousScanRanOutOfRetries,
+    InvalidCustomSelectionCommand,
+    InvalidDisplayTemplate,
     InvalidNetctlPath,
     InvalidScanTypeAndConnectType,
     InvalidScanTypeAndMethod,
@@ -66,23 +88,35 @@ pub enum RuwiErrorKind {
     InvalidSubcommand,
     KnownNetworksFetchError,
     LoopProtectionMaxExceeded,
+    MalformedIWListOutput,
     MalformedIWOutput,
+    MalformedIwctlOutput,
+    MalformedRuwiJSON,
+    NetctlConfigBelongsToAnotherNetwork,
     NoInterfaceFoundWithGivenName,
     NoKnownNetworksFound,
     NoMatchingBluetoothDeviceFoundForPrefix,
     NoNetworksFoundMatchingSelectionResult,
     NoNetworksFoundWhenLookingForFirst,
+    NoNetworksMatchingGivenPattern,
+    NoNetworksSeenWithIWListScan,
+    NoNetworksSeenWithIwctl,
     NoNetworksSeenWithIWScanDump,
     NoNetworksSeenWithWPACliScanResults,
+    NoPasswordForUnknownEncryptedNetwork,
     NoWifiInterfacesFound,
     NoWiredInterfacesFound,
     NotImplementedError,
     OnlyParseCmdlineBailout,
+    PasswordConfirmationDidNotMatch,
+    PinentryFailed,
     PromptCommandBailoutRequested,
     PromptCommandFailed,
     PromptCommandSpawnFailed,
-    RefreshRequested,
+    SelectionTimedOut,
     SingleLinePromptFailed,
+    StrictParseFailedOnScanLines,
+    TerminalPasswordPromptFailed,
     TestError,
     TestDeliberatelyFailedToFindNetworks,
     TestNoNetworksFoundWhenLookingForLast,
@@ -92,6 +126,7 @@ pub enum RuwiErrorKind {
     TestUsedAutoWhenNotExpected,
     TestUsedManualWhenNotExpected,
     UnableToReadMetadataForBinary,
+    UnsupportedRuwiJSONVersion,
     WPACliHeaderMalformedOrMissing,
 }
 
@@ -130,19 +165,10 @@ impl RuwiError {
     }
 }
 
-pub(crate) fn nie<T>(prog: T) -> RuwiError
-where
-    T: fmt::Debug,
-{
-    rerr!(
-        RuwiErrorKind::NotImplementedError,
-        format!("Functionality not implemented: {:?}", prog)
-    )
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rerr;
 
     #[test]
     fn check_default_exit_code_1() {
@@ -0,0 +1,135 @@
+use crate::prelude::*;
+
+use regex::{Regex, RegexBuilder};
+
+// A pattern given by the user to pick a network by name. A network matches if its name starts
+// with the pattern, or if the pattern is a regular expression matching the start of the name.
+// Patterns which aren't valid regular expressions (e.g. "Cafe (2nd floor") are only used as
+// prefixes.
+#[derive(Debug, Clone)]
+pub(crate) struct EssidPattern {
+    pattern: String,
+    regex: Option<Regex>,
+    loose_regex: Option<Regex>,
+}
+
+impl EssidPattern {
+    pub(crate) fn new(pattern: &str) -> Self {
+        let regex = Regex::new(&format!("^(?:{})", pattern)).ok();
+        let loose_regex = RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .ok();
+        Self {
+            pattern: pattern.to_string(),
+            regex,
+            loose_regex,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    pub(crate) fn matches(&self, essid: &str) -> bool {
+        essid.starts_with(&self.pattern)
+            || self.regex.as_ref().map_or(false, |regex| regex.is_match(essid))
+    }
+
+    // Names which would have matched if case were ignored, or if the pattern could appear
+    // anywhere in the name. Used to point out likely typos when nothing matches.
+    pub(crate) fn is_near_miss(&self, essid: &str) -> bool {
+        essid.to_lowercase().contains(&self.pattern.to_lowercase())
+            || self
+                .loose_regex
+                .as_ref()
+                .map_or(false, |regex| regex.is_match(essid))
+    }
+
+    pub(crate) fn get_no_match_error<N>(&self, seen_networks: &[N]) -> RuwiError
+    where
+        N: Identifiable,
+    {
+        let near_misses = seen_networks
+            .iter()
+            .map(Identifiable::get_public_name)
+            .filter(|essid| self.is_near_miss(essid))
+            .map(|essid| format!("\"{}\"", essid))
+            .collect::<Vec<_>>();
+
+        let hint = if !near_misses.is_empty() {
+            format!(" Did you mean one of: {}?", near_misses.join(", "))
+        } else if seen_networks.is_empty() {
+            " No networks were seen at all.".to_string()
+        } else {
+            let seen = seen_networks
+                .iter()
+                .map(|nw| format!("\"{}\"", nw.get_public_name()))
+                .collect::<Vec<_>>();
+            format!(" Networks seen: {}", seen.join(", "))
+        };
+
+        rerr!(
+            RuwiErrorKind::NoNetworksMatchingGivenPattern,
+            format!("No networks found matching \"{}\".{}", self.pattern, hint)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_networks(essids: &[&str]) -> Vec<AnnotatedWirelessNetwork> {
+        essids
+            .iter()
+            .map(|essid| AnnotatedWirelessNetwork::builder().essid(*essid).build())
+            .collect()
+    }
+
+    #[test]
+    fn test_prefix_matches() {
+        let pattern = EssidPattern::new("Home");
+        assert![pattern.matches("Home")];
+        assert![pattern.matches("HomeNet 5G")];
+        assert![!pattern.matches("MyHome")];
+        assert![!pattern.matches("home")];
+    }
+
+    #[test]
+    fn test_regex_matches() {
+        let pattern = EssidPattern::new("(Home|Work)Net.*5G");
+        assert![pattern.matches("HomeNet 5G")];
+        assert![pattern.matches("WorkNet_5G")];
+        assert![!pattern.matches("HomeNet")];
+        assert![!pattern.matches("MyHomeNet 5G")];
+    }
+
+    #[test]
+    fn test_invalid_regex_is_used_as_prefix() {
+        let pattern = EssidPattern::new("Cafe (2nd");
+        assert![pattern.matches("Cafe (2nd floor)")];
+        assert![!pattern.matches("Cafe 2nd floor")];
+    }
+
+    #[test]
+    fn test_no_match_error_lists_near_misses() {
+        let pattern = EssidPattern::new("home");
+        let networks = get_networks(&["HomeNet", "MyHome", "Work"]);
+        let err = pattern.get_no_match_error(&networks);
+        assert_eq![err.kind, RuwiErrorKind::NoNetworksMatchingGivenPattern];
+        assert![err.desc.contains("\"HomeNet\", \"MyHome\"")];
+        assert![!err.desc.contains("Work")];
+    }
+
+    #[test]
+    fn test_no_match_error_lists_seen_networks_without_near_misses() {
+        let pattern = EssidPattern::new("Library");
+        let networks = get_networks(&["HomeNet", "Work"]);
+        let err = pattern.get_no_match_error(&networks);
+        assert![err.desc.contains("Networks seen: \"HomeNet\", \"Work\"")];
+
+        let err = pattern.get_no_match_error::<AnnotatedWirelessNetwork>(&[]);
+        assert![err.desc.contains("No networks were seen")];
+    }
+}
@@ -0,0 +1,116 @@
+use crate::netctl::utils::netctl_stop;
+use crate::netctl::{NetctlConfigHandler, NetctlIdentifier};
+use crate::prelude::*;
+use crate::run_commands::SystemCommandRunner;
+use crate::select::prompt_for_confirmation::prompt_for_confirmation;
+use crate::select::selection_outcome::SelectionOutcome;
+use crate::select::Selector;
+
+#[derive(Debug)]
+struct KnownNetworksToForget(Vec<AnnotatedWirelessNetwork>);
+
+impl Selector for KnownNetworksToForget {
+    type Item = AnnotatedWirelessNetwork;
+
+    fn get_networks(&self) -> &[AnnotatedWirelessNetwork] {
+        &self.0
+    }
+
+    fn get_selection_target<O>(&self, _options: &O) -> String
+    where
+        O: Global,
+    {
+        "network to forget".to_string()
+    }
+}
+
+// Asks which of the given networks to forget. Anything other than picking a network (e.g.
+// refreshing) forgets nothing, and the caller goes back to whatever it was doing.
+pub(crate) fn select_and_forget_known_network<O>(
+    options: &O,
+    networks: &[AnnotatedWirelessNetwork],
+) -> Result<(), RuwiError>
+where
+    O: Global,
+{
+    if let Some(network) = select_known_network_to_forget(options, networks)? {
+        confirm_and_forget_network(options, &network)?;
+    }
+    Ok(())
+}
+
+// Returns None if something other than a network was picked, e.g. refresh.
+pub(crate) fn select_known_network_to_forget<O>(
+    options: &O,
+    networks: &[AnnotatedWirelessNetwork],
+) -> Result<Option<AnnotatedWirelessNetwork>, RuwiError>
+where
+    O: Global,
+{
+    let known_networks =
+        KnownNetworksToForget(networks.iter().filter(|nw| nw.is_known()).cloned().collect());
+    match known_networks.prompt_user_for_selection(options)? {
+        SelectionOutcome::Selected(network, _) => Ok(Some(network)),
+        _ => Ok(None),
+    }
+}
+
+// Forgetting a network removes its saved password, so the user is asked first.
+pub(crate) fn confirm_and_forget_network<O>(
+    options: &O,
+    network: &AnnotatedWirelessNetwork,
+) -> Result<(), RuwiError>
+where
+    O: Global,
+{
+    let essid = network.get_public_name();
+    if !network.is_known() {
+        return forget_network(options, network);
+    }
+    if prompt_for_confirmation(options, &format!("Forget \"{}\"?", essid))? {
+        forget_network(options, network)
+    } else {
+        eprintln!("[NOTE]: Not forgetting \"{}\".", essid);
+        Ok(())
+    }
+}
+
+pub(crate) fn forget_network<O>(
+    options: &O,
+    network: &AnnotatedWirelessNetwork,
+) -> Result<(), RuwiError>
+where
+    O: Global,
+{
+    let essid = network.get_public_name();
+    match network.get_service_identifier() {
+        Some(NetworkingServiceIdentifier::Netctl(ident)) => {
+            let identifier = NetctlIdentifier::new(ident);
+            netctl_stop(options, &identifier)?;
+            NetctlConfigHandler::new(options).remove_config(&identifier)
+        }
+        Some(NetworkingServiceIdentifier::NetworkManager) => SystemCommandRunner::new(
+            options,
+            "nmcli",
+            &["connection", "delete", "id", essid],
+        )
+        .run_command_pass(
+            RuwiErrorKind::FailedToForgetNetworkViaNetworkManager,
+            &format!("Failed to delete NetworkManager connection \"{}\"!", essid),
+        ),
+        Some(NetworkingServiceIdentifier::Iwd) => {
+            SystemCommandRunner::new(options, "iwctl", &["known-networks", essid, "forget"])
+                .run_command_pass(
+                    RuwiErrorKind::FailedToForgetNetworkViaIwd,
+                    &format!("Failed to forget \"{}\" with iwctl!", essid),
+                )
+        }
+        None => {
+            eprintln!(
+                "[NOTE]: \"{}\" is not a known network, so there is nothing to forget.",
+                essid
+            );
+            Ok(())
+        }
+    }
+}
@@ -54,7 +54,6 @@ pub(crate) trait TypedLinuxInterfaceFinder: TryFrom<LinuxIPLinkInterface> {
 }
 
 // TODO: make sure ip is installed by default on Ubuntu, check the package name
-// TODO: implement selectable
 // TODO: find correct way to identify wifi vs. wired
 
 // A direct representation of what `ip -j link show` gives back to us in JSON.
@@ -1,4 +1,5 @@
 use super::LinuxIPInterface;
+use crate::prelude::*;
 use super::ip_link::{WifiLinuxIPLinkInterface, WiredLinuxIPLinkInterface};
 
 string_container!{WifiIPInterface, WiredIPInterface}
@@ -18,6 +19,26 @@ impl LinuxIPInterface for WifiIPInterface {
     }
 }
 
+impl Identifiable for WifiIPInterface {
+    fn get_public_name(&self) -> &str {
+        self.get_ifname()
+    }
+}
+
+// Interfaces are listed for selection alongside networks, but there is nothing to remember
+// about them.
+impl Known for WifiIPInterface {
+    type ServiceIdentifier = ();
+
+    fn is_known(&self) -> bool {
+        false
+    }
+
+    fn get_service_identifier(&self) -> Option<&()> {
+        None
+    }
+}
+
 impl Default for WiredIPInterface {
     fn default() -> Self {
         Self::new(super::DRYRUN_FAKE_INTERFACE_NAME)
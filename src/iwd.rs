@@ -0,0 +1,255 @@
+use crate::prelude::*;
+use crate::utils::write_private_file_atomically;
+
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+use zeroize::Zeroizing;
+
+pub(crate) const DEFAULT_IWD_STATE_DIR: &str = "/var/lib/iwd/";
+
+const IWD_KNOWN_NETWORK_EXTENSIONS: &[&str] = &["psk", "open"];
+const IWD_HEX_ENCODED_NAME_PREFIX: char = '=';
+const IWD_SECURITY_SECTION: &str = "[Security]";
+const IWD_PASSPHRASE_KEY: &str = "Passphrase";
+// Derived by iwd from the passphrase, and cached alongside it.
+const IWD_DERIVED_KEY_PREFIXES: &[&str] = &["PreSharedKey", "SAE-PT"];
+
+// iwd stores one file per known network in its state directory, named after the SSID and
+// suffixed with the security type (e.g. "xfinitywifi.open", "Valparaiso_Guest_House 1.psk").
+// SSIDs which aren't purely alphanumeric plus " _-" are hex-encoded and prefixed with '='.
+pub(crate) fn find_known_iwd_essids(iwd_state_dir: &str) -> Result<Vec<String>, RuwiError> {
+    let entries = match fs::read_dir(iwd_state_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(err_failed_to_read_iwd_known_networks(iwd_state_dir, &e)),
+    };
+
+    let mut essids = vec![];
+    for entry in entries {
+        let entry = entry.map_err(|e| err_failed_to_read_iwd_known_networks(iwd_state_dir, &e))?;
+        if let Some(essid) = get_essid_from_iwd_filename(&entry.path()) {
+            essids.push(essid);
+        }
+    }
+    essids.sort();
+    Ok(essids)
+}
+
+pub(crate) fn get_iwd_known_network_path(essid: &str, is_encrypted: bool) -> String {
+    let is_plain_name = essid
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == ' ' || c == '_' || c == '-');
+    let name = if is_plain_name {
+        essid.to_string()
+    } else {
+        essid.bytes().fold(
+            IWD_HEX_ENCODED_NAME_PREFIX.to_string(),
+            |mut name, b| {
+                let _ = write!(name, "{:02x}", b);
+                name
+            },
+        )
+    };
+    let extension = if is_encrypted { "psk" } else { "open" };
+    format!("{}{}.{}", DEFAULT_IWD_STATE_DIR, name, extension)
+}
+
+// iwd reads the passphrase for a network from the network's file, so it's written there (readable
+// only by root) instead of being given to iwctl, where it would show up in the process list. Any
+// other settings already in the file are kept.
+pub(crate) fn write_iwd_known_network(path: &str, passphrase: &str) -> Result<(), RuwiError> {
+    let existing = match fs::read_to_string(path) {
+        Ok(existing) => Zeroizing::new(existing),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Zeroizing::new(String::new()),
+        Err(e) => return Err(err_failed_to_write_iwd_known_network(path, &e)),
+    };
+    let contents = set_iwd_passphrase(&existing, passphrase);
+    write_private_file_atomically(path, &contents)
+        .map_err(|e| err_failed_to_write_iwd_known_network(path, &e))
+}
+
+// The passphrase goes first in the [Security] section, which is added if there isn't one. Keys
+// which iwd derived from the old passphrase are dropped, so that it derives them again.
+fn set_iwd_passphrase(existing: &str, passphrase: &str) -> Zeroizing<String> {
+    let passphrase_line = Zeroizing::new(format!("{}={}", IWD_PASSPHRASE_KEY, passphrase));
+    let mut contents = Zeroizing::new(String::new());
+    let mut in_security_section = false;
+    let mut wrote_passphrase = false;
+    for line in existing.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_security_section = trimmed == IWD_SECURITY_SECTION;
+        } else if in_security_section {
+            let key = trimmed.split('=').next().unwrap_or_default().trim();
+            if key == IWD_PASSPHRASE_KEY
+                || IWD_DERIVED_KEY_PREFIXES
+                    .iter()
+                    .any(|prefix| key.starts_with(prefix))
+            {
+                continue;
+            }
+        }
+        contents.push_str(line);
+        contents.push('\n');
+        if in_security_section && !wrote_passphrase {
+            contents.push_str(&passphrase_line);
+            contents.push('\n');
+            wrote_passphrase = true;
+        }
+    }
+
+    if !wrote_passphrase {
+        if !contents.is_empty() && !contents.ends_with("\n\n") {
+            contents.push('\n');
+        }
+        contents.push_str(IWD_SECURITY_SECTION);
+        contents.push('\n');
+        contents.push_str(&passphrase_line);
+        contents.push('\n');
+    }
+    contents
+}
+
+fn get_essid_from_iwd_filename(path: &Path) -> Option<String> {
+    let extension = path.extension()?.to_str()?;
+    if !IWD_KNOWN_NETWORK_EXTENSIONS.contains(&extension) {
+        return None;
+    }
+
+    let name = path.file_stem()?.to_str()?;
+    if let Some(hex) = name.strip_prefix(IWD_HEX_ENCODED_NAME_PREFIX) {
+        decode_hex_essid(hex)
+    } else {
+        Some(name.to_string())
+    }
+}
+
+fn decode_hex_essid(hex: &str) -> Option<String> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return None;
+    }
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    String::from_utf8(bytes).ok()
+}
+
+fn err_failed_to_write_iwd_known_network(path: &str, e: &io::Error) -> RuwiError {
+    rerr!(
+        RuwiErrorKind::FailedToWriteIwdKnownNetwork,
+        format!(
+            "Failed to write iwd known network \"{}\": {}. Are you running as root?",
+            path, e
+        )
+    )
+}
+
+fn err_failed_to_read_iwd_known_networks(iwd_state_dir: &str, e: &io::Error) -> RuwiError {
+    rerr!(
+        RuwiErrorKind::FailedToReadIwdKnownNetworks,
+        format!(
+            "Failed to read iwd known networks from \"{}\": {}. Are you running as root?",
+            iwd_state_dir, e
+        )
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_find_known_iwd_essids() {
+        let dir = tempdir().unwrap();
+        for filename in &[
+            "xfinitywifi.open",
+            "Valparaiso_Guest_House 1.psk",
+            "=436166c3a9.psk",
+            "Corp Net.8021x",
+            "=zz.psk",
+        ] {
+            File::create(dir.path().join(filename)).unwrap();
+        }
+        fs::create_dir(dir.path().join("hotspot")).unwrap();
+
+        let essids = find_known_iwd_essids(dir.path().to_str().unwrap()).unwrap();
+        assert_eq![
+            essids,
+            vec!["Café", "Valparaiso_Guest_House 1", "xfinitywifi"]
+        ];
+    }
+
+    #[test]
+    fn test_write_iwd_known_network() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Home.psk");
+        let path = path.to_str().unwrap();
+        write_iwd_known_network(path, "hunter22").unwrap();
+        write_iwd_known_network(path, "hunter222").unwrap();
+        assert_eq![
+            fs::read_to_string(path).unwrap(),
+            "[Security]\nPassphrase=hunter222\n"
+        ];
+        assert_eq![fs::metadata(path).unwrap().permissions().mode() & 0o777, 0o600];
+        assert_eq![fs::read_dir(dir.path()).unwrap().count(), 1];
+    }
+
+    #[test]
+    fn test_write_iwd_known_network_keeps_other_settings() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Home.psk");
+        let path = path.to_str().unwrap();
+        fs::write(
+            path,
+            "[Settings]\nAutoConnect=false\n\n[Security]\nPreSharedKey=abcd\nPassphrase=hunter22\nSAE-PT-Group19=ef01\n\n[IPv4]\nAddress=192.168.1.5\n",
+        )
+        .unwrap();
+        write_iwd_known_network(path, "hunter222").unwrap();
+        assert_eq![
+            fs::read_to_string(path).unwrap(),
+            "[Settings]\nAutoConnect=false\n\n[Security]\nPassphrase=hunter222\n\n[IPv4]\nAddress=192.168.1.5\n"
+        ];
+    }
+
+    #[test]
+    fn test_set_iwd_passphrase_adds_security_section() {
+        assert_eq![
+            set_iwd_passphrase("[Settings]\nHidden=true\n", "hunter22").as_str(),
+            "[Settings]\nHidden=true\n\n[Security]\nPassphrase=hunter22\n"
+        ];
+    }
+
+    #[test]
+    fn test_find_known_iwd_essids_missing_dir() {
+        let dir = tempdir().unwrap();
+        let missing = dir.path().join("nonexistent");
+        let essids = find_known_iwd_essids(missing.to_str().unwrap()).unwrap();
+        assert![essids.is_empty()];
+    }
+
+    #[test]
+    fn test_get_iwd_known_network_path() {
+        assert_eq![
+            get_iwd_known_network_path("Valparaiso_Guest_House 1", true),
+            "/var/lib/iwd/Valparaiso_Guest_House 1.psk"
+        ];
+        assert_eq![
+            get_iwd_known_network_path("Café", false),
+            "/var/lib/iwd/=436166c3a9.open"
+        ];
+    }
+
+    #[test]
+    fn test_decode_hex_essid() {
+        assert_eq![decode_hex_essid("436166c3a9"), Some("Café".to_string())];
+        assert_eq![decode_hex_essid("436"), None];
+        assert_eq![decode_hex_essid("zz"), None];
+    }
+}
@@ -3,6 +3,7 @@ use crate::prelude::*;
 #[cfg(not(test))]
 use crate::run_commands::SystemCommandRunner;
 
+use crate::iwd::{find_known_iwd_essids, DEFAULT_IWD_STATE_DIR};
 use crate::netctl::NetctlConfigHandler;
 
 use super::{UnfilteredKnownNetworkNamesAndIdentifiers, WifiKnownNetworks};
@@ -12,7 +13,7 @@ impl WifiKnownNetworks {
         options: &O,
     ) -> Result<WifiKnownNetworks, RuwiError>
     where
-        O: Global + Wifi + WifiConnect,
+        O: Global + Wifi + WifiConnectionManager,
     {
         find_known_networks(options)
     }
@@ -23,7 +24,7 @@ impl WifiKnownNetworks {
 // TODO: unit test the logic in this function
 fn find_known_networks<O>(options: &O) -> Result<WifiKnownNetworks, RuwiError>
 where
-    O: Global + Wifi + WifiConnect,
+    O: Global + Wifi + WifiConnectionManager,
 {
     if options.get_dry_run() || options.get_ignore_known() {
         return Ok(WifiKnownNetworks::default());
@@ -32,6 +33,7 @@ where
     let known_network_list_with_duplicates = match options.get_connect_via() {
         WifiConnectionType::Netctl => find_known_netctl_networks(options)?,
         WifiConnectionType::Nmcli => find_known_networkmanager_networks(options)?,
+        WifiConnectionType::Iwd => find_known_iwd_networks(options)?,
         WifiConnectionType::None | WifiConnectionType::Print => vec![],
     };
 
@@ -95,3 +97,17 @@ where
         .collect();
     Ok(configs)
 }
+
+fn find_known_iwd_networks<O>(
+    _options: &O,
+) -> Result<UnfilteredKnownNetworkNamesAndIdentifiers, RuwiError>
+where
+    O: Global,
+{
+    eprintln!("[NOTE]: Checking iwd state directory for known networks...");
+
+    Ok(find_known_iwd_essids(DEFAULT_IWD_STATE_DIR)?
+        .into_iter()
+        .map(|essid| (essid, NetworkingServiceIdentifier::Iwd))
+        .collect())
+}
@@ -1,4 +1,5 @@
 use crate::enums::NetworkingServiceIdentifier;
+use crate::prelude::*;
 use std::collections::HashMap;
 use std::iter::FromIterator;
 
@@ -39,4 +40,46 @@ impl WifiKnownNetworks {
     ) -> Option<&NetworkingServiceIdentifier> {
         self.essid_to_identifiers.get(essid)
     }
+
+    // All known networks, whether or not they were seen in a scan, sorted by name.
+    pub(crate) fn get_known_networks(&self) -> Vec<AnnotatedWirelessNetwork> {
+        let mut networks = self
+            .essid_to_identifiers
+            .iter()
+            .map(|(essid, identifier)| {
+                AnnotatedWirelessNetwork::builder()
+                    .essid(essid.clone())
+                    .service_identifier(Some(identifier.clone()))
+                    .build()
+            })
+            .collect::<Vec<_>>();
+        networks.sort_by(|a, b| a.get_public_name().cmp(b.get_public_name()));
+        networks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_known_networks() {
+        let known_networks = WifiKnownNetworks::new(vec![
+            ("Work".to_string(), NetworkingServiceIdentifier::NetworkManager),
+            (
+                "Home".to_string(),
+                NetworkingServiceIdentifier::Netctl("Home".to_string()),
+            ),
+        ]);
+        let networks = known_networks.get_known_networks();
+        let names = networks
+            .iter()
+            .map(Identifiable::get_public_name)
+            .collect::<Vec<_>>();
+        assert_eq![names, vec!["Home", "Work"]];
+        assert_eq![
+            networks[0].get_service_identifier(),
+            Some(&NetworkingServiceIdentifier::Netctl("Home".to_string()))
+        ];
+    }
 }
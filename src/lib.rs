@@ -37,8 +37,11 @@ pub(crate) mod configure_network;
 pub(crate) mod connect;
 pub(crate) mod encryption_key;
 pub(crate) mod enums;
+pub(crate) mod essid_pattern;
+pub(crate) mod forget_network;
 pub mod errors;
 pub(crate) mod interface_management;
+pub(crate) mod iwd;
 pub(crate) mod known_networks;
 pub(crate) mod netctl;
 pub(crate) mod networks;
@@ -68,7 +71,7 @@ use errors::RuwiError;
 // TODO(high): determine if verify_command_safety_while_running_as_root should also check parent directories
 // TODO(high): create integration tests for blurz (mock controller that just prints?)
 // TODO(high): document bluetooth usage
-// TODO(high): remove defaults for wired and selection method in cmdline_parser, and have them check system
+// TODO(high): remove the default for wired connect_via in cmdline_parser, and have it check the system
 // TODO(high): add `clear` success messages (and note that it's expected to see some error messages there, or just hide all of them)
 // TODO(high): `clear` should be `wifi clear`? or at least call into it? i guess wired and wireless may use the same services. bluetooth also will have services i suppose. should ip/bt be different service types?
 // TODO(high): implement speed/connection/dns test - see `nmcli networking connectivity` for networkmanager mode
@@ -79,7 +82,6 @@ use errors::RuwiError;
 // TODO(mid): add colors to output / use a real logging library / set debugging levels
 // TODO(mid): configurable `ruwi default` that `ruwi` and `ruwi -a` can use
 // TODO(mid): ability to do -o "wired.connect_via=netctl", overriding config file entries
-// TODO(mid): add a "list seen networks" mode?
 // TODO(mid): use string_container where you would normally pass around String or an existing less-nice string container
 // TODO(mid): kill, or suggest killing, wpa_supplicant if netctl fails to connect (clear does this, can you just suggest clear in error messages?)
 // TODO(mid): have `ruwi -a` detect wired, try to connect to it, then try wifi -a if not. check "/sys/class/net/{IFNAME}/operstate" after bringing up the interface
@@ -94,10 +96,9 @@ use errors::RuwiError;
 // TODO(low): use a custom Result type to reduce Result<_, RuwiError> boilerplate
 // TODO(wishlist): `ruwi wifi get_default_interface` and/or `ruwi wifi select_interface`?
 // TODO(wishlist): JSON output for `select`
-// TODO(wishlist): implement json scan output/input mode
+// TODO(wishlist): implement json scan output mode
 // TODO(wishlist): find a generalized way to do x notifications, for dmenu mode, use to surface failures
 // TODO(wishlist): connection/scan type: wicd-cli
-// TODO(wishlist): fzf keyboard shortcuts for getting more info about a network?
 // TODO(later): make sure fzf and dmenu are listed as dependencies
 
 pub fn run_ruwi_cli() -> Result<(), RuwiError> {
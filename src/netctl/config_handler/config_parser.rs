@@ -1,119 +1,255 @@
-use super::structs::{NetctlRawConfig};
+use super::config_tokenizer::{tokenize_netctl_profile, NetctlAssignment, NetctlValue};
+use super::structs::{
+    NetctlConnectionType, NetctlIPMethod, NetctlParseError, NetctlRawConfig, NetctlSecurity,
+};
 
+use std::str::FromStr;
 use strum::AsStaticRef;
 use strum_macros::AsStaticStr;
-use unescape::unescape;
 
 #[derive(Debug, Clone, PartialEq, Eq, AsStaticStr)]
 enum NetctlFieldKey {
-    #[strum(serialize = "ESSID=")]
+    #[strum(serialize = "ESSID")]
     Essid,
-    #[strum(serialize = "Interface=")]
+    #[strum(serialize = "Interface")]
     Interface,
-    #[strum(serialize = "Connection=")]
+    #[strum(serialize = "Connection")]
     ConnectionType,
-    #[strum(serialize = "Key=")]
+    #[strum(serialize = "Key")]
     EncryptionKey,
+    #[strum(serialize = "Security")]
+    Security,
+    #[strum(serialize = "IP")]
+    Ip,
+    #[strum(serialize = "Address")]
+    Address,
+    #[strum(serialize = "DNS")]
+    Dns,
+    #[strum(serialize = "Hidden")]
+    Hidden,
+    #[strum(serialize = "Priority")]
+    Priority,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct NetctlProfile {
+    assignments: Vec<NetctlAssignment>,
 }
 
 impl<'a> NetctlRawConfig<'a> {
-    fn get_field(&self, field: &NetctlFieldKey) -> Option<String> {
-        let token = field.as_static();
-        self.contents.as_ref().lines().find_map(|line| {
-            if line.starts_with(token) {
-                // TODO: better matching of quote types, since a password/essid could end with an escaped quote
-                let value = line
-                    .trim_start_matches(token)
-                    .trim_start_matches('\'')
-                    .trim_start_matches('"')
-                    .trim_end_matches('\'')
-                    .trim_end_matches('"')
-                    .to_string();
-                Some(value)
-            } else {
-                None
-            }
-        })
+    pub(super) fn parse_profile(&self) -> Result<NetctlProfile, NetctlParseError> {
+        let assignments = tokenize_netctl_profile(self.contents.as_ref())
+            .map_err(|e| NetctlParseError::InvalidSyntax(e.line, e.reason))?;
+        Ok(NetctlProfile { assignments })
     }
 
-    // NOTE: ESSID is unescaped here. This can happen further up the stack if necessary.
     pub(super) fn get_essid(&self) -> Option<String> {
-        self.get_field(&NetctlFieldKey::Essid)
-            .map(|raw_essid_entry| unescape(&raw_essid_entry).unwrap_or(raw_essid_entry))
+        self.parse_profile().ok()?.get_essid().ok()?
+    }
+}
+
+// As in bash, a field which is set more than once takes its last value.
+impl NetctlProfile {
+    fn get_assignment(&self, field: &NetctlFieldKey) -> Option<&NetctlAssignment> {
+        self.assignments
+            .iter()
+            .rev()
+            .find(|assignment| assignment.key == field.as_static())
+    }
+
+    fn get_word(&self, field: &NetctlFieldKey) -> Result<Option<(&str, usize)>, NetctlParseError> {
+        match self.get_assignment(field) {
+            None => Ok(None),
+            Some(NetctlAssignment {
+                value: NetctlValue::Word(word),
+                line,
+                ..
+            }) => Ok(Some((word, *line))),
+            Some(NetctlAssignment { line, .. }) => Err(NetctlParseError::InvalidFieldValue(
+                *line,
+                format!("{} should be a single value, not an array", field.as_static()),
+            )),
+        }
+    }
+
+    fn get_string(&self, field: &NetctlFieldKey) -> Result<Option<String>, NetctlParseError> {
+        Ok(self.get_word(field)?.map(|(word, _)| word.to_string()))
+    }
+
+    // A plain value is used like an array with one element, as netctl does.
+    fn get_list(&self, field: &NetctlFieldKey) -> Vec<String> {
+        match self.get_assignment(field).map(|assignment| &assignment.value) {
+            None => vec![],
+            Some(NetctlValue::Word(word)) if word.is_empty() => vec![],
+            Some(NetctlValue::Word(word)) => vec![word.clone()],
+            Some(NetctlValue::Array(words)) => words.clone(),
+        }
+    }
+
+    fn get_parsed<T: FromStr>(&self, field: &NetctlFieldKey) -> Result<Option<T>, NetctlParseError> {
+        match self.get_word(field)? {
+            None => Ok(None),
+            Some((word, line)) => T::from_str(word).map(Some).map_err(|_| {
+                NetctlParseError::InvalidFieldValue(
+                    line,
+                    format!("unknown value \"{}\" for {}", word, field.as_static()),
+                )
+            }),
+        }
+    }
+
+    pub(super) fn get_essid(&self) -> Result<Option<String>, NetctlParseError> {
+        self.get_string(&NetctlFieldKey::Essid)
+    }
+
+    pub(super) fn get_interface(&self) -> Result<Option<String>, NetctlParseError> {
+        self.get_string(&NetctlFieldKey::Interface)
+    }
+
+    // Connection types which ruwi doesn't handle (bridges, VLANs, etc) aren't errors in the profile.
+    pub(super) fn get_connection_type(&self) -> Result<NetctlConnectionType, NetctlParseError> {
+        match self.get_word(&NetctlFieldKey::ConnectionType)? {
+            None => Err(NetctlParseError::MissingFieldInNetctlConfig(
+                "no connection type was found".to_string(),
+            )),
+            Some((word, _)) => {
+                NetctlConnectionType::from_str(word).map_err(|_| NetctlParseError::IncorrectConnectionType)
+            }
+        }
+    }
+
+    pub(super) fn get_encryption_key(&self) -> Result<Option<String>, NetctlParseError> {
+        self.get_string(&NetctlFieldKey::EncryptionKey)
     }
 
-    pub(super) fn get_interface(&self) -> Option<String> {
-        self.get_field(&NetctlFieldKey::Interface)
+    pub(super) fn get_security(&self) -> Result<Option<NetctlSecurity>, NetctlParseError> {
+        self.get_parsed(&NetctlFieldKey::Security)
     }
 
-    pub(super) fn get_connection_type(&self) -> Option<String> {
-        self.get_field(&NetctlFieldKey::ConnectionType)
+    pub(super) fn get_ip(&self) -> Result<Option<NetctlIPMethod>, NetctlParseError> {
+        self.get_parsed(&NetctlFieldKey::Ip)
     }
 
-    pub(super) fn get_encryption_key(&self) -> Option<String> {
-        self.get_field(&NetctlFieldKey::EncryptionKey)
+    pub(super) fn get_addresses(&self) -> Vec<String> {
+        self.get_list(&NetctlFieldKey::Address)
+    }
+
+    pub(super) fn get_dns(&self) -> Vec<String> {
+        self.get_list(&NetctlFieldKey::Dns)
+    }
+
+    pub(super) fn get_priority(&self) -> Result<Option<i32>, NetctlParseError> {
+        self.get_parsed(&NetctlFieldKey::Priority)
+    }
+
+    // netctl accepts the same spellings for yes and no as its `is_yes` helper.
+    pub(super) fn get_hidden(&self) -> Result<bool, NetctlParseError> {
+        match self.get_word(&NetctlFieldKey::Hidden)? {
+            None => Ok(false),
+            Some((word, line)) => match word.to_lowercase().as_str() {
+                "yes" | "true" | "on" | "1" => Ok(true),
+                "no" | "false" | "off" | "0" => Ok(false),
+                _ => Err(NetctlParseError::InvalidFieldValue(
+                    line,
+                    format!("unknown value \"{}\" for Hidden", word),
+                )),
+            },
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use super::super::structs::NetctlConnectionType;
-    use strum::AsStaticRef;
 
-    fn get_config<'a>(contents: &str) -> NetctlRawConfig<'a> {
+    fn get_profile(contents: &str) -> NetctlProfile {
         NetctlRawConfig::builder()
             .contents(contents)
             .identifier("lawl")
             .location("/tmp/lawl")
             .build()
+            .parse_profile()
+            .unwrap()
     }
 
-    fn get_ethernet_config<'a>() -> NetctlRawConfig<'a> {
-        get_config(super::super::tests::ETHERNET_SAMPLE)
+    #[test]
+    fn test_parse_ethernet() {
+        let profile = get_profile(super::super::tests::ETHERNET_SAMPLE);
+        assert_eq![Some("enp0s25".to_string()), profile.get_interface().unwrap()];
+        assert_eq![NetctlConnectionType::Wired, profile.get_connection_type().unwrap()];
+        assert_eq![Some(NetctlIPMethod::Dhcp), profile.get_ip().unwrap()];
+        assert![profile.get_essid().unwrap().is_none()];
+        assert![profile.get_encryption_key().unwrap().is_none()];
+        assert![profile.get_security().unwrap().is_none()];
     }
 
-    fn get_wireless_open_config<'a>() -> NetctlRawConfig<'a> {
-        get_config(super::super::tests::WIRELESS_OPEN_SAMPLE)
+    #[test]
+    fn test_parse_wireless_open() {
+        let profile = get_profile(super::super::tests::WIRELESS_OPEN_SAMPLE);
+        assert_eq![Some("wlp3s0".to_string()), profile.get_interface().unwrap()];
+        assert_eq![NetctlConnectionType::Wifi, profile.get_connection_type().unwrap()];
+        assert_eq![
+            Some("Chateau de Chine Hotel".to_string()),
+            profile.get_essid().unwrap()
+        ];
+        assert_eq![Some(NetctlSecurity::None), profile.get_security().unwrap()];
+        assert![profile.get_encryption_key().unwrap().is_none()];
     }
 
-    fn get_wireless_encrypted_config<'a>() -> NetctlRawConfig<'a> {
-        get_config(super::super::tests::WIRELESS_ENCRYPTED_SAMPLE)
+    #[test]
+    fn test_parse_wireless_encrypted() {
+        let profile = get_profile(super::super::tests::WIRELESS_ENCRYPTED_SAMPLE);
+        assert_eq![Some("wlp3s1".to_string()), profile.get_interface().unwrap()];
+        assert_eq![NetctlConnectionType::Wifi, profile.get_connection_type().unwrap()];
+        assert_eq![Some("Lobby".to_string()), profile.get_essid().unwrap()];
+        assert_eq![Some("KS211819".to_string()), profile.get_encryption_key().unwrap()];
+        assert_eq![Some(NetctlSecurity::Wpa), profile.get_security().unwrap()];
+        assert![!profile.get_hidden().unwrap()];
+        assert![profile.get_priority().unwrap().is_none()];
     }
 
     #[test]
-    fn test_parse_ethernet() {
-        let config = get_ethernet_config();
-        assert_eq!["enp0s25", config.get_interface().unwrap()];
+    fn test_parse_typed_fields() {
+        let profile = get_profile(
+            "Connection=ethernet\nIP=static\nAddress=('10.1.10.2/24' '10.1.10.3/24')\n\
+             DNS=1.1.1.1\nHidden=Yes\nPriority=-10\nHidden=off\nSecurity=wpa-configsection",
+        );
+        assert_eq![Some(NetctlIPMethod::Static), profile.get_ip().unwrap()];
+        assert_eq![vec!["10.1.10.2/24", "10.1.10.3/24"], profile.get_addresses()];
+        assert_eq![vec!["1.1.1.1"], profile.get_dns()];
+        assert![!profile.get_hidden().unwrap()];
+        assert_eq![Some(-10), profile.get_priority().unwrap()];
         assert_eq![
-            NetctlConnectionType::Wired.as_static(),
-            config.get_connection_type().unwrap()
+            Some(NetctlSecurity::WpaConfigSection),
+            profile.get_security().unwrap()
         ];
-        assert![config.get_essid().is_none()];
-        assert![config.get_encryption_key().is_none()];
     }
 
     #[test]
-    fn test_parse_wireless_open() {
-        let config = get_wireless_open_config();
-        assert_eq!["wlp3s0", config.get_interface().unwrap()];
+    fn test_parse_invalid_fields() {
+        let profile = get_profile(
+            "Connection=bridge\nIP=maybe\nHidden=sometimes\nPriority=high\nESSID=('a' 'b')",
+        );
         assert_eq![
-            NetctlConnectionType::Wifi.as_static(),
-            config.get_connection_type().unwrap()
+            Err(NetctlParseError::IncorrectConnectionType),
+            profile.get_connection_type()
         ];
-        assert_eq!["Chateau de Chine Hotel", config.get_essid().unwrap()];
-        assert![config.get_encryption_key().is_none()];
-    }
-
-    #[test]
-    fn test_parse_wireless_encrypted() {
-        let config = get_wireless_encrypted_config();
-        assert_eq!["wlp3s1", config.get_interface().unwrap()];
         assert_eq![
-            NetctlConnectionType::Wifi.as_static(),
-            config.get_connection_type().unwrap()
+            Err(NetctlParseError::InvalidFieldValue(
+                2,
+                "unknown value \"maybe\" for IP".to_string()
+            )),
+            profile.get_ip()
+        ];
+        assert![profile.get_hidden().is_err()];
+        assert![profile.get_priority().is_err()];
+        assert_eq![
+            Err(NetctlParseError::InvalidFieldValue(
+                5,
+                "ESSID should be a single value, not an array".to_string()
+            )),
+            profile.get_essid()
         ];
-        assert_eq!["Lobby", config.get_essid().unwrap()];
-        assert_eq!["KS211819", config.get_encryption_key().unwrap()];
+        assert![get_profile("").get_connection_type().is_err()];
     }
 }
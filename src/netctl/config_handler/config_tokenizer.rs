@@ -0,0 +1,371 @@
+// netctl profiles are sourced by bash, but only a small part of bash is used in them: variables
+// set to words or to arrays of words, with comments, quoting, and line continuations. That part is
+// read here, and anything else (commands, functions, etc) is reported as an error rather than
+// guessed at. Expansions like "$INTERFACE" are kept as they are, since netctl expands them later.
+use std::iter::Peekable;
+use std::str::Chars;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum NetctlValue {
+    Word(String),
+    Array(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct NetctlAssignment {
+    pub(super) key: String,
+    pub(super) value: NetctlValue,
+    pub(super) line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct NetctlSyntaxError {
+    pub(super) line: usize,
+    pub(super) reason: String,
+}
+
+pub(super) fn tokenize_netctl_profile(
+    contents: &str,
+) -> Result<Vec<NetctlAssignment>, NetctlSyntaxError> {
+    let mut tokenizer = Tokenizer {
+        chars: contents.chars().peekable(),
+        line: 1,
+    };
+    let mut assignments = vec![];
+    while let Some(assignment) = tokenizer.next_assignment()? {
+        assignments.push(assignment);
+    }
+    Ok(assignments)
+}
+
+struct Tokenizer<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+impl Tokenizer<'_> {
+    fn next_assignment(&mut self) -> Result<Option<NetctlAssignment>, NetctlSyntaxError> {
+        self.skip_blanks(true);
+        let line = self.line;
+        let key = match self.chars.peek().copied() {
+            None => return Ok(None),
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => self.read_name(),
+            Some(_) => String::new(),
+        };
+        if key.is_empty() || self.chars.peek() != Some(&'=') {
+            let found = format!("{}{}", key, self.read_word().unwrap_or_default());
+            return Err(syntax_error(
+                line,
+                format!("expected a `Key=value` assignment, found `{}`", found),
+            ));
+        }
+        self.next_char();
+
+        let value = if self.chars.peek() == Some(&'(') {
+            self.next_char();
+            NetctlValue::Array(self.read_array()?)
+        } else {
+            NetctlValue::Word(self.read_word()?)
+        };
+
+        match self.chars.peek().copied() {
+            None | Some(' ' | '\t' | '\n' | ';') => {}
+            Some(c) => return Err(syntax_error(self.line, format!("unexpected `{}`", c))),
+        }
+
+        Ok(Some(NetctlAssignment { key, value, line }))
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    fn is_line_continuation(&self) -> bool {
+        let mut ahead = self.chars.clone();
+        ahead.next() == Some('\\') && ahead.next() == Some('\n')
+    }
+
+    // Newlines and ";" end a statement, so they're only skipped between statements.
+    fn skip_blanks(&mut self, skip_separators: bool) {
+        loop {
+            match self.chars.peek().copied() {
+                Some(' ' | '\t') => {}
+                Some('\n' | ';') if skip_separators => {}
+                Some('\\') if self.is_line_continuation() => {
+                    self.next_char();
+                }
+                Some('#') => {
+                    while !matches!(self.chars.peek(), None | Some('\n')) {
+                        self.next_char();
+                    }
+                    continue;
+                }
+                _ => return,
+            }
+            self.next_char();
+        }
+    }
+
+    fn read_name(&mut self) -> String {
+        let mut name = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if !(c.is_ascii_alphanumeric() || c == '_') {
+                break;
+            }
+            name.push(c);
+            self.next_char();
+        }
+        name
+    }
+
+    fn read_array(&mut self) -> Result<Vec<String>, NetctlSyntaxError> {
+        let start = self.line;
+        let mut elements = vec![];
+        loop {
+            self.skip_blanks(false);
+            match self.chars.peek().copied() {
+                None => return Err(syntax_error(start, "unterminated array")),
+                Some(')') => {
+                    self.next_char();
+                    return Ok(elements);
+                }
+                Some('\n') => {
+                    self.next_char();
+                }
+                Some(c) if is_metacharacter(c) => {
+                    return Err(syntax_error(self.line, format!("unexpected `{}`", c)));
+                }
+                Some(_) => elements.push(self.read_word()?),
+            }
+        }
+    }
+
+    fn read_word(&mut self) -> Result<String, NetctlSyntaxError> {
+        let mut word = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if is_metacharacter(c) {
+                break;
+            }
+            self.next_char();
+            match c {
+                '\'' => self.read_single_quoted(&mut word)?,
+                '"' => self.read_double_quoted(&mut word)?,
+                '$' => match self.chars.peek() {
+                    Some('\'') => {
+                        self.next_char();
+                        self.read_ansi_c_quoted(&mut word)?;
+                    }
+                    // $"..." is a translated string, which is the same as "..." here.
+                    Some('"') => {}
+                    _ => word.push('$'),
+                },
+                '\\' => match self.next_char() {
+                    Some('\n') => {}
+                    Some(c) => word.push(c),
+                    None => word.push('\\'),
+                },
+                c => word.push(c),
+            }
+        }
+        Ok(word)
+    }
+
+    fn read_single_quoted(&mut self, word: &mut String) -> Result<(), NetctlSyntaxError> {
+        let start = self.line;
+        loop {
+            match self.next_char() {
+                None => return Err(syntax_error(start, "unterminated single quote")),
+                Some('\'') => return Ok(()),
+                Some(c) => word.push(c),
+            }
+        }
+    }
+
+    fn read_double_quoted(&mut self, word: &mut String) -> Result<(), NetctlSyntaxError> {
+        let start = self.line;
+        loop {
+            match self.next_char() {
+                None => return Err(syntax_error(start, "unterminated double quote")),
+                Some('"') => return Ok(()),
+                Some('\\') => match self.next_char() {
+                    Some(c @ ('$' | '`' | '"' | '\\')) => word.push(c),
+                    Some('\n') => {}
+                    Some(c) => {
+                        word.push('\\');
+                        word.push(c);
+                    }
+                    None => return Err(syntax_error(start, "unterminated double quote")),
+                },
+                Some(c) => word.push(c),
+            }
+        }
+    }
+
+    // Escaped bytes which aren't valid UTF-8 are replaced, as they couldn't be part of an ESSID
+    // or key that ruwi knows about.
+    fn read_ansi_c_quoted(&mut self, word: &mut String) -> Result<(), NetctlSyntaxError> {
+        let start = self.line;
+        let mut bytes = vec![];
+        loop {
+            let c = match self.next_char() {
+                None => return Err(syntax_error(start, "unterminated $'...' quote")),
+                Some('\'') => break,
+                Some('\\') => self.next_char(),
+                Some(c) => {
+                    push_char(&mut bytes, c);
+                    continue;
+                }
+            };
+            match c {
+                Some('n') => bytes.push(b'\n'),
+                Some('r') => bytes.push(b'\r'),
+                Some('t') => bytes.push(b'\t'),
+                Some('a') => bytes.push(0x07),
+                Some('b') => bytes.push(0x08),
+                Some('e' | 'E') => bytes.push(0x1b),
+                Some('f') => bytes.push(0x0c),
+                Some('v') => bytes.push(0x0b),
+                Some(c @ ('\\' | '\'' | '"' | '?')) => push_char(&mut bytes, c),
+                Some('x') => {
+                    let hex = self.read_digits(16, 2);
+                    match u8::from_str_radix(&hex, 16) {
+                        Ok(byte) => bytes.push(byte),
+                        Err(_) => bytes.extend_from_slice(b"\\x"),
+                    }
+                }
+                Some(c) if c.is_digit(8) => {
+                    let octal = format!("{}{}", c, self.read_digits(8, 2));
+                    // Like bash, values above \377 wrap around.
+                    let value = u32::from_str_radix(&octal, 8).unwrap_or(0);
+                    bytes.push(value.to_le_bytes()[0]);
+                }
+                // Like bash, unknown escapes are kept as they are.
+                Some(c) => {
+                    bytes.push(b'\\');
+                    push_char(&mut bytes, c);
+                }
+                None => return Err(syntax_error(start, "unterminated $'...' quote")),
+            }
+        }
+        word.push_str(&String::from_utf8_lossy(&bytes));
+        Ok(())
+    }
+
+    fn read_digits(&mut self, radix: u32, max_digits: usize) -> String {
+        let mut digits = String::new();
+        while digits.len() < max_digits {
+            match self.chars.peek() {
+                Some(c) if c.is_digit(radix) => digits.push(*c),
+                _ => break,
+            }
+            self.next_char();
+        }
+        digits
+    }
+}
+
+fn is_metacharacter(c: char) -> bool {
+    matches!(
+        c,
+        ' ' | '\t' | '\n' | ';' | '(' | ')' | '<' | '>' | '|' | '&'
+    )
+}
+
+fn push_char(bytes: &mut Vec<u8>, c: char) {
+    let mut buf = [0; 4];
+    bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+}
+
+fn syntax_error(line: usize, reason: impl Into<String>) -> NetctlSyntaxError {
+    NetctlSyntaxError {
+        line,
+        reason: reason.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(key: &str, value: &str, line: usize) -> NetctlAssignment {
+        NetctlAssignment {
+            key: key.to_string(),
+            value: NetctlValue::Word(value.to_string()),
+            line,
+        }
+    }
+
+    fn array(key: &str, values: &[&str], line: usize) -> NetctlAssignment {
+        NetctlAssignment {
+            key: key.to_string(),
+            value: NetctlValue::Array(values.iter().map(|v| (*v).to_string()).collect()),
+            line,
+        }
+    }
+
+    #[test]
+    fn test_tokenize_words() {
+        let profile = "# A comment\n\
+            Description='Bob'\"'\"'s wifi' # and another\n\
+            Interface=wlp3s0; Connection=wireless\n\
+            ESSID=$'caf\\xc3\\xa9\\n\\101' IP=dhcp\n\
+            Key=\"a \\\"quoted\\\" \\$key\\q\"\n\
+            ExecUpPost=\"dhcpcd $INTERFACE\"\n\
+            Hidden=yes\\\n\
+            no\n\
+            Priority=\n";
+        assert_eq![
+            tokenize_netctl_profile(profile),
+            Ok(vec![
+                word("Description", "Bob's wifi", 2),
+                word("Interface", "wlp3s0", 3),
+                word("Connection", "wireless", 3),
+                word("ESSID", "caf\u{e9}\nA", 4),
+                word("IP", "dhcp", 4),
+                word("Key", "a \"quoted\" $key\\q", 5),
+                word("ExecUpPost", "dhcpcd $INTERFACE", 6),
+                word("Hidden", "yesno", 7),
+                word("Priority", "", 9),
+            ])
+        ];
+    }
+
+    #[test]
+    fn test_tokenize_arrays() {
+        let profile = "Address=('192.168.1.23/24' \"192.168.1.87/24\")\n\
+            DNS=(\n  # Cloudflare\n  1.1.1.1\n  '8.8.8.8' # Google\n)\n\
+            WPAConfigSection=( 'ssid=\"x y\"' )\n\
+            IPCustom=()\n";
+        assert_eq![
+            tokenize_netctl_profile(profile),
+            Ok(vec![
+                array("Address", &["192.168.1.23/24", "192.168.1.87/24"], 1),
+                array("DNS", &["1.1.1.1", "8.8.8.8"], 2),
+                array("WPAConfigSection", &["ssid=\"x y\""], 7),
+                array("IPCustom", &[], 8),
+            ])
+        ];
+    }
+
+    #[test]
+    fn test_tokenize_errors() {
+        let err = |profile: &str| tokenize_netctl_profile(profile).err().unwrap();
+
+        assert_eq![err("IP=dhcp\nESSID='Lobby\n").line, 2];
+        assert![err("IP=dhcp\nESSID='Lobby\n").reason.contains("single quote")];
+        assert![err("Key=\"abc").reason.contains("double quote")];
+        assert![err("Key=$'abc").reason.contains("$'...'")];
+        assert![err("DNS=(1.1.1.1\n").reason.contains("unterminated array")];
+        assert_eq![err("IP=dhcp\n\nsource /etc/foo").line, 3];
+        assert![err("IP=dhcp\n\nsource /etc/foo").reason.contains("`source`")];
+        assert![err("ESSID=foo bar").reason.contains("`bar`")];
+        assert![err("ESSID=foo|bar").reason.contains("`|`")];
+        assert![err("Address=(a; b)").reason.contains("`;`")];
+        assert![err("Key+=abc").reason.contains("assignment")];
+        assert![err("=abc").reason.contains("assignment")];
+    }
+}
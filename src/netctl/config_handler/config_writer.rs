@@ -1,12 +1,17 @@
-use super::structs::{NetctlConfig, WifiNetctlConfig, WiredNetctlConfig};
-use super::utils::write_to_netctl_config;
+use super::structs::{NetctlConfig, NetctlRawConfig, WifiNetctlConfig, WiredNetctlConfig};
 use super::{NetctlConfigHandler, NetctlIdentifier};
 use crate::interface_management::ip_interfaces::{
     LinuxIPInterface, WifiIPInterface, WiredIPInterface,
 };
 use crate::prelude::*;
+use crate::utils::write_private_file_atomically;
 
+use pbkdf2::pbkdf2_hmac;
+use sha1::Sha1;
 use std::fmt;
+use std::fmt::Write;
+use std::fs;
+use zeroize::Zeroizing;
 
 // TODO: Check if existing config with ESSID (for wifi) or interface (for raw interface connect)
 // already exists, and if so just use that and don't write to it (unless a particular flag exists?)
@@ -38,7 +43,7 @@ impl<'a, O: Global> NetctlConfigHandler<'a, O> {
                 fullpath, config_text
             );
         } else {
-            write_to_netctl_config(&fullpath, &config_text)
+            write_private_file_atomically(&fullpath, &config_text)
                 .map_err(|e| rerr!(RuwiErrorKind::FailedToWriteNetctlConfig, e.to_string()))?;
             eprintln!("[NOTE]: Wrote netctl config: {}", &fullpath);
             eprintln!(
@@ -50,6 +55,99 @@ impl<'a, O: Global> NetctlConfigHandler<'a, O> {
 
         Ok(())
     }
+
+    // A profile with the same name could have been written by hand for another network, or for
+    // another ESSID which ends up with the same filename (e.g. "Cafe Wifi" and "Cafe_Wifi"). Then
+    // the name is given a suffix made from the ESSID, and ruwi only gives up if that's taken too.
+    // If the netctl directory can't be read, writing to it will fail with a clearer error anyway.
+    pub(super) fn choose_identifier_for_config(
+        &self,
+        config: &WifiNetctlConfig,
+    ) -> Result<NetctlIdentifier, RuwiError> {
+        let identifier = config.get_identifier();
+        let essid = config.get_essid();
+        let existing_configs = self.get_all_configs_text().unwrap_or_default();
+        match get_owner_of_other_network_config(&existing_configs, identifier, essid) {
+            Some(owner) => self.choose_fallback_identifier(config, &existing_configs, &owner),
+            None => Ok(identifier.clone()),
+        }
+    }
+
+    fn choose_fallback_identifier(
+        &self,
+        config: &WifiNetctlConfig,
+        existing_configs: &[NetctlRawConfig],
+        owner: &str,
+    ) -> Result<NetctlIdentifier, RuwiError> {
+        let identifier = config.get_identifier();
+        let essid = config.get_essid();
+        let fallback = NetctlIdentifier::from_essid_with_suffix(essid);
+        if &fallback != identifier
+            && get_owner_of_other_network_config(existing_configs, &fallback, essid).is_none()
+        {
+            eprintln!(
+                "[NOTE]: The netctl config \"{}{}\" {}, so \"{}\" will use \"{}\" instead.",
+                self.get_netctl_cfg_dir(),
+                identifier,
+                owner,
+                essid,
+                fallback,
+            );
+            return Ok(fallback);
+        }
+
+        Err(rerr!(
+            RuwiErrorKind::NetctlConfigBelongsToAnotherNetwork,
+            format!(
+                "Refusing to overwrite the netctl config \"{}{}\", which {}, or \"{}\", which is also for another network. Rename or remove one of them to configure \"{}\".",
+                self.get_netctl_cfg_dir(),
+                identifier,
+                owner,
+                fallback,
+                essid,
+            )
+        ))
+    }
+
+    pub(crate) fn remove_config(&self, identifier: &NetctlIdentifier) -> Result<(), RuwiError> {
+        let fullpath = format!("{}{}", self.get_netctl_cfg_dir(), identifier.as_ref());
+
+        // Known networks aren't looked up in dry-run mode, so the name is only what ruwi would
+        // have called the profile.
+        if self.opts.get_dry_run() {
+            eprintln!(
+                "[NOTE]: Would remove a netctl profile named like \"{}\".",
+                fullpath
+            );
+        } else {
+            fs::remove_file(&fullpath).map_err(|e| {
+                rerr!(
+                    RuwiErrorKind::FailedToRemoveNetctlConfig,
+                    format!("Failed to remove netctl config \"{}\": {}", fullpath, e)
+                )
+            })?;
+            eprintln!("[NOTE]: Removed netctl config: {}", &fullpath);
+        }
+
+        Ok(())
+    }
+}
+
+// Describes who the existing profile with the given name belongs to, if it isn't the given ESSID.
+fn get_owner_of_other_network_config(
+    existing_configs: &[NetctlRawConfig],
+    identifier: &NetctlIdentifier,
+    essid: &str,
+) -> Option<String> {
+    let existing_essid = existing_configs
+        .iter()
+        .find(|raw| &raw.identifier == identifier)?
+        .get_essid();
+    match existing_essid {
+        Some(existing_essid) if existing_essid == essid => None,
+        Some(existing_essid) => Some(format!("is for the network \"{}\"", existing_essid)),
+        None => Some("has no ESSID that ruwi can read".to_string()),
+    }
 }
 
 // TODO: unit test
@@ -57,47 +155,80 @@ impl WifiNetctlConfig {
     pub(super) fn new(
         interface: &WifiIPInterface,
         network: &AnnotatedWirelessNetwork,
-        encryption_key: &Option<String>,
+        encryption_key: &Option<EncryptionKey>,
+        hash_password: bool,
     ) -> Self {
         let identifier = NetctlIdentifier::from(network);
         let interface_name = interface.get_ifname().to_string();
         let essid = network.get_public_name();
-        let encryption_key = encryption_key.clone();
+        let security = network.get_security();
+        let uses_psk = matches!(security, None | Some(WifiSecurity::WpaPsk | WifiSecurity::Wpa2Psk));
+        let encryption_key = encryption_key.as_ref().map(|key| {
+            Some(key)
+                .filter(|_| hash_password && uses_psk)
+                .and_then(|key| get_psk_key(essid, key.as_str()))
+                .map_or_else(|| key.as_str().to_string(), |psk| format!("\"{}", psk))
+        });
+        let hidden = network.is_hidden();
         Self::builder()
             .identifier(identifier)
             .interface_name(interface_name)
             .essid(essid)
             .encryption_key(encryption_key)
+            .security(security)
+            .hidden(hidden)
             .build()
     }
 
+    // netctl's "wpa" security type only handles WPA/WPA2-PSK, so WPA3-SAE networks are
+    // configured by passing the relevant wpa_supplicant settings through directly.
+    fn get_security_and_key_lines(&self) -> (&'static str, String) {
+        match (&self.security, &self.encryption_key) {
+            (Some(WifiSecurity::Open), _) | (_, None) => ("none", String::new()),
+            (Some(WifiSecurity::Wep), Some(key)) => ("wep", format!("Key={}", shell_quote(key))),
+            (Some(WifiSecurity::Wpa3Sae), Some(key)) => (
+                "wpa-configsection",
+                format!(
+                    "WPAConfigSection=(
+    {}
+    'key_mgmt=SAE'
+    {}
+    'ieee80211w=2'
+)",
+                    shell_quote(&format!("ssid={}", wpa_supplicant_quote(&self.essid))),
+                    shell_quote(&format!("sae_password={}", wpa_supplicant_quote(key))),
+                ),
+            ),
+            // TODO: see if encryption status/key can be bundled together
+            (_, Some(key)) => ("wpa", format!("Key={}", quote_wpa_key(key))),
+        }
+    }
+
     fn as_config_text(&self) -> String {
         let is_encrypted = self.encryption_key.is_some();
-        let wpa_line = if let Some(encryption_key) = &self.encryption_key {
-            format!(
-                "Key='{}'",
-                // TODO: see if encryption status/key can be bundled together
-                encryption_key
-            )
-        } else {
-            "".to_string()
-        };
+        let (security, key_lines) = self.get_security_and_key_lines();
+        let hidden_line = if self.hidden { "Hidden=yes\n" } else { "" };
+        let description = format!(
+            "{} wifi - {}",
+            self.essid,
+            if is_encrypted { "wpa" } else { "open" }
+        );
 
         format!(
-            "Description='{} wifi - {}'
+            "Description={}
 Interface={}
 Connection=wireless
 Security={}
-ESSID='{}'
-IP=dhcp
+ESSID={}
+{}IP=dhcp
 {}
 ",
-            self.essid,
-            if is_encrypted { "wpa" } else { "open" },
+            shell_quote(&description),
             self.interface_name,
-            if is_encrypted { "wpa" } else { "none" },
-            self.essid,
-            wpa_line,
+            security,
+            shell_quote(&self.essid),
+            hidden_line,
+            key_lines,
         )
         .trim_end_matches(|x| x == '\n')
         .to_string()
@@ -148,3 +279,360 @@ impl fmt::Display for WiredNetctlConfig {
         f.write_str(&self.as_config_text())
     }
 }
+
+// netctl profiles are sourced by bash (as root), so any value which didn't come from ruwi itself
+// has to be quoted. Everything inside single quotes is taken literally, which covers most values.
+// Single quotes and control characters (e.g. newlines) can't be written that way, so values
+// containing them use bash's $'...' quoting instead, where they can be escaped.
+pub(super) fn shell_quote(value: &str) -> String {
+    if !value.chars().any(|c| c == '\'' || c.is_control()) {
+        return format!("'{}'", value);
+    }
+
+    let mut quoted = String::from("$'");
+    for c in value.chars() {
+        match c {
+            '\'' => quoted.push_str("\\'"),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => push_hex_escaped(&mut quoted, c),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+// wpa_supplicant takes everything up to the last double quote on the line as the value, so
+// quotes inside of it are fine. Control characters would break the line, so values containing
+// them use its printf-style P"..." strings instead.
+fn wpa_supplicant_quote(value: &str) -> String {
+    if !value.chars().any(char::is_control) {
+        return format!("\"{}\"", value);
+    }
+
+    let mut quoted = String::from("P\"");
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            c if c.is_control() => push_hex_escaped(&mut quoted, c),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// netctl hands a Key whose value starts with " to wpa_supplicant as a raw PSK. That's what WPA
+// derives from the passphrase and the ESSID anyway, so it's all that's needed to connect, but it
+// doesn't reveal a passphrase which might be used elsewhere. Passphrases which WPA can't use (not
+// 8 to 63 printable ASCII characters) are left as they are, and 64 hex digits already are a PSK.
+fn get_psk_key(essid: &str, passphrase: &str) -> Option<String> {
+    if is_hex_psk(passphrase) {
+        return Some(passphrase.to_string());
+    }
+    let is_valid_passphrase = (8..=63).contains(&passphrase.len())
+        && passphrase.bytes().all(|b| (b' '..=b'~').contains(&b));
+    if !is_valid_passphrase {
+        return None;
+    }
+
+    let mut psk = Zeroizing::new([0; 32]);
+    pbkdf2_hmac::<Sha1>(passphrase.as_bytes(), essid.as_bytes(), 4096, &mut *psk);
+    let mut key = String::new();
+    for byte in psk.iter() {
+        let _ = write!(key, "{:02x}", byte);
+    }
+    Some(key)
+}
+
+fn is_hex_psk(key: &str) -> bool {
+    key.len() == 64 && key.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+// Written the way netctl's own examples write a raw PSK, as Key=\"<64 hex digits>. Quoting the
+// whole value instead would keep the backslash, and netctl would see a 66-character passphrase.
+fn quote_wpa_key(key: &str) -> String {
+    match key.strip_prefix('"') {
+        Some(psk) if is_hex_psk(psk) => format!("\\\"{}", psk),
+        _ => shell_quote(key),
+    }
+}
+
+fn push_hex_escaped(quoted: &mut String, c: char) {
+    let mut buf = [0; 4];
+    for byte in c.encode_utf8(&mut buf).bytes() {
+        let _ = write!(quoted, "\\x{:02x}", byte);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::structs::NetctlRawConfigContents;
+    use crate::options::wifi::connect::WifiConnectOptions;
+
+    fn get_config(security: Option<WifiSecurity>, encryption_key: Option<&str>) -> WifiNetctlConfig {
+        WifiNetctlConfig::builder()
+            .identifier("Citadel")
+            .essid("Citadel")
+            .interface_name("wlp3s0")
+            .encryption_key(encryption_key.map(String::from))
+            .security(security)
+            .build()
+    }
+
+    #[test]
+    fn test_wifi_config_text_unknown_security() {
+        let text = get_config(None, Some("hunter2")).to_string();
+        assert![text.contains("Security=wpa\n")];
+        assert![text.contains("Key='hunter2'")];
+
+        let text = get_config(None, None).to_string();
+        assert![text.contains("Security=none\n")];
+        assert![!text.contains("Key=")];
+    }
+
+    #[test]
+    fn test_wifi_config_text_wep() {
+        let text = get_config(Some(WifiSecurity::Wep), Some("abcde")).to_string();
+        assert![text.contains("Security=wep\n")];
+        assert![text.contains("Key='abcde'")];
+    }
+
+    #[test]
+    fn test_wifi_config_text_wpa2_psk() {
+        let text = get_config(Some(WifiSecurity::Wpa2Psk), Some("hunter2")).to_string();
+        assert![text.contains("Security=wpa\n")];
+        assert![text.contains("Key='hunter2'")];
+    }
+
+    #[test]
+    fn test_wifi_config_text_wpa3_sae() {
+        let text = get_config(Some(WifiSecurity::Wpa3Sae), Some("hunter2")).to_string();
+        assert![text.contains("Security=wpa-configsection\n")];
+        assert![text.contains("'key_mgmt=SAE'")];
+        assert![text.contains("'sae_password=\"hunter2\"'")];
+        assert![!text.contains("Key=")];
+    }
+
+    #[test]
+    fn test_wifi_config_text_hidden() {
+        let mut config = get_config(Some(WifiSecurity::Wpa2Psk), Some("hunter2"));
+        assert![!config.to_string().contains("Hidden=")];
+
+        config.hidden = true;
+        assert![config.to_string().contains("ESSID='Citadel'\nHidden=yes\nIP=dhcp\n")];
+    }
+
+    #[test]
+    fn test_wifi_config_text_open() {
+        let text = get_config(Some(WifiSecurity::Open), None).to_string();
+        assert![text.contains("Security=none\n")];
+        assert![!text.contains("Key=")];
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq![shell_quote("Citadel"), "'Citadel'"];
+        assert_eq![shell_quote("$(reboot) `id` \"x\""), "'$(reboot) `id` \"x\"'"];
+        assert_eq![shell_quote("Bob's wifi"), "$'Bob\\'s wifi'"];
+        assert_eq![shell_quote("a\\b\nc\u{1b}"), "$'a\\\\b\\nc\\x1b'"];
+    }
+
+    #[test]
+    fn test_wifi_config_text_quoting() {
+        let config = WifiNetctlConfig::builder()
+            .identifier("Bobs_wifi")
+            .essid("Bob's wifi")
+            .interface_name("wlp3s0")
+            .encryption_key(Some("'; reboot; '".to_string()))
+            .security(Some(WifiSecurity::Wpa2Psk))
+            .build();
+        let text = config.to_string();
+        assert![text.contains("Description=$'Bob\\'s wifi wifi - wpa'\n")];
+        assert![text.contains("ESSID=$'Bob\\'s wifi'\n")];
+        assert![text.contains("Key=$'\\'; reboot; \\''")];
+    }
+
+    #[test]
+    fn test_wifi_config_text_wpa3_sae_quoting() {
+        let mut config = get_config(Some(WifiSecurity::Wpa3Sae), Some("it's \"quoted\""));
+        config.essid = "Line\nBreak".to_string();
+        let text = config.to_string();
+        assert![text.contains("'ssid=P\"Line\\x0aBreak\"'")];
+        assert![text.contains("$'sae_password=\"it\\'s \"quoted\"\"'")];
+    }
+
+    #[test]
+    fn test_wifi_config_round_trip() {
+        let values = [
+            "Citadel",
+            "Bob's wifi",
+            "\"double\" 'single'",
+            "$HOME $(reboot) `reboot` ${PATH}",
+            "tab\tnew\nline\rreturn",
+            "back\\slash\\x41\\n\\",
+            "ends with backslash quote\\'",
+            "caf\u{e9} \u{1f4f6} \u{7f}\u{1}\u{85}",
+            " leading and trailing spaces ",
+            "# not a comment ;",
+            "say \"hi\"",
+            "\"",
+        ];
+        for value in &values {
+            let config = WifiNetctlConfig::builder()
+                .identifier("round_trip")
+                .essid(*value)
+                .interface_name("wlp3s0")
+                .encryption_key(Some((*value).to_string()))
+                .security(Some(WifiSecurity::Wpa2Psk))
+                .build();
+            let text = config.to_string();
+            let profile = NetctlRawConfig::builder()
+                .identifier("round_trip")
+                .contents(text.as_str())
+                .location("/tmp/round_trip")
+                .build()
+                .parse_profile()
+                .unwrap();
+            assert_eq![profile.get_essid().unwrap().as_deref(), Some(*value), "{}", text];
+            assert_eq![
+                profile.get_encryption_key().unwrap().as_deref(),
+                Some(*value),
+                "{}",
+                text
+            ];
+        }
+    }
+
+    #[test]
+    fn test_choose_identifier_for_config() -> Result<(), RuwiError> {
+        let opts = WifiConnectOptions::default();
+        let given_configs = vec![
+            (
+                NetctlIdentifier::new("Citadel"),
+                NetctlRawConfigContents::new(get_config(None, Some("hunter2")).to_string()),
+            ),
+            (
+                NetctlIdentifier::new("Cafe_Wifi"),
+                NetctlRawConfigContents::new("Connection=wireless\nESSID='Cafe_Wifi'"),
+            ),
+            (
+                NetctlIdentifier::new("ethernet"),
+                NetctlRawConfigContents::new("Connection=ethernet\nInterface=enp0s25"),
+            ),
+        ];
+        let handler = NetctlConfigHandler::builder()
+            .opts(&opts)
+            .given_configs(given_configs)
+            .build();
+
+        let same_network = get_config(None, Some("hunter3"));
+        assert_eq![handler.choose_identifier_for_config(&same_network)?.as_ref(), "Citadel"];
+
+        let mut new_network = get_config(None, None);
+        new_network.identifier = NetctlIdentifier::new("Library");
+        assert_eq![handler.choose_identifier_for_config(&new_network)?.as_ref(), "Library"];
+
+        let mut other_essid = get_config(None, None);
+        other_essid.identifier = NetctlIdentifier::new("Cafe_Wifi");
+        other_essid.essid = "Cafe Wifi".to_string();
+        assert_eq![
+            handler.choose_identifier_for_config(&other_essid)?,
+            NetctlIdentifier::from_essid_with_suffix("Cafe Wifi")
+        ];
+
+        let mut wired_name = get_config(None, None);
+        wired_name.identifier = NetctlIdentifier::new("ethernet");
+        wired_name.essid = "ethernet".to_string();
+        assert_eq![
+            handler.choose_identifier_for_config(&wired_name)?,
+            NetctlIdentifier::from_essid_with_suffix("ethernet")
+        ];
+        Ok(())
+    }
+
+    #[test]
+    fn test_choose_identifier_for_config_with_fallback_taken() {
+        let opts = WifiConnectOptions::default();
+        let fallback = NetctlIdentifier::from_essid_with_suffix("Cafe Wifi");
+        let given_configs = vec![
+            (
+                NetctlIdentifier::new("Cafe_Wifi"),
+                NetctlRawConfigContents::new("Connection=wireless\nESSID='Cafe_Wifi'"),
+            ),
+            (
+                fallback.clone(),
+                NetctlRawConfigContents::new("Connection=wireless\nESSID='Somewhere else'"),
+            ),
+        ];
+        let handler = NetctlConfigHandler::builder()
+            .opts(&opts)
+            .given_configs(given_configs)
+            .build();
+
+        let mut config = get_config(None, None);
+        config.identifier = NetctlIdentifier::new("Cafe_Wifi");
+        config.essid = "Cafe Wifi".to_string();
+        let err = handler.choose_identifier_for_config(&config).unwrap_err();
+        assert_eq![err.kind, RuwiErrorKind::NetctlConfigBelongsToAnotherNetwork];
+        assert![err.desc.contains("is for the network \"Cafe_Wifi\"")];
+        assert![err.desc.contains(fallback.as_ref())];
+    }
+
+    #[test]
+    fn test_get_psk_key() {
+        // The test vector from IEEE 802.11i, Annex H.4.
+        assert_eq![
+            get_psk_key("IEEE", "password").unwrap(),
+            "f42c6fc52df0ebef9ebb4b90b38a5f902e83fe1b135a70e23aed762e9710a12e"
+        ];
+        let psk = "aB".repeat(32);
+        assert_eq![get_psk_key("IEEE", &psk), Some(psk)];
+
+        assert![get_psk_key("IEEE", "short").is_none()];
+        assert![get_psk_key("IEEE", &"x".repeat(64)).is_none()];
+        assert![get_psk_key("IEEE", "caf\u{e9} latte").is_none()];
+    }
+
+    #[test]
+    fn test_new_wifi_config_hashes_password() {
+        let interface = WifiIPInterface::new("wlp3s0");
+        let key = Some(EncryptionKey::from("password"));
+        let get_network = |security| {
+            AnnotatedWirelessNetwork::builder()
+                .essid("IEEE")
+                .security(security)
+                .build()
+        };
+
+        let config = WifiNetctlConfig::new(&interface, &get_network(None), &key, true);
+        let text = config.to_string();
+        assert![text.contains("\nKey=\\\"f42c6fc52df0ebef9ebb4b90b38a5f902e83fe1b135a70e23aed762e9710a12e")];
+
+        // What netctl sees once the config is sourced by bash.
+        let profile = NetctlRawConfig::builder()
+            .identifier("IEEE")
+            .contents(text.as_str())
+            .location("/tmp/IEEE")
+            .build()
+            .parse_profile()
+            .unwrap();
+        assert_eq![
+            profile.get_encryption_key().unwrap().as_deref(),
+            Some("\"f42c6fc52df0ebef9ebb4b90b38a5f902e83fe1b135a70e23aed762e9710a12e")
+        ];
+
+        let network = get_network(Some(WifiSecurity::Wpa2Psk));
+        let config = WifiNetctlConfig::new(&interface, &network, &key, false);
+        assert![config.to_string().contains("Key='password'")];
+
+        let network = get_network(Some(WifiSecurity::Wpa3Sae));
+        let config = WifiNetctlConfig::new(&interface, &network, &key, true);
+        assert![config.to_string().contains("'sae_password=\"password\"'")];
+    }
+}
@@ -1,6 +1,7 @@
 mod config_finder;
 mod config_parser;
 mod config_reader;
+mod config_tokenizer;
 mod config_writer;
 mod structs;
 mod utils;
@@ -9,9 +10,10 @@ use config_finder::{NetctlConfigFinderCriteria, WiredNetctlConfigFinderCriteria}
 #[cfg(not(test))]
 use config_reader::reader_implementation::read_all_netctl_config_files;
 #[cfg(test)]
-use structs::NetctlRawConfigContents;
+pub(crate) use structs::NetctlRawConfigContents;
 use structs::{
-    NetctlConfig, NetctlRawConfig, NetctlRawParsedFields, WifiNetctlConfig, WiredNetctlConfig,
+    NetctlConfig, NetctlParseError, NetctlRawConfig, NetctlRawParsedFields, WifiNetctlConfig,
+    WiredNetctlConfig,
 };
 
 use crate::interface_management::ip_interfaces::{WifiIPInterface, WiredIPInterface};
@@ -65,7 +67,9 @@ impl<'a, O: Global> NetctlConfigHandler<'a, O> {
         Ok(self
             .get_all_configs_text()?
             .iter()
-            .filter_map(|text| NetctlRawParsedFields::try_from(text).ok())
+            .filter_map(|text| {
+                self.ok_or_report(&text.identifier, NetctlRawParsedFields::try_from(text))
+            })
             .collect())
     }
 
@@ -76,10 +80,36 @@ impl<'a, O: Global> NetctlConfigHandler<'a, O> {
         Ok(self
             .get_all_parsed_but_untyped_configs()?
             .into_iter()
-            .filter_map(|config| C::try_from(config).ok())
+            .filter_map(|config| {
+                let identifier = config.identifier.clone();
+                self.ok_or_report(&identifier, C::try_from(config))
+            })
             .collect::<Vec<C>>())
     }
 
+    // A config which can't be used is skipped, but never silently, so that typos etc. in it
+    // can be found.
+    fn ok_or_report<T>(
+        &self,
+        identifier: &NetctlIdentifier,
+        res: Result<T, NetctlParseError>,
+    ) -> Option<T> {
+        match res {
+            Ok(config) => Some(config),
+            Err(e) => {
+                if e.should_be_reported() {
+                    eprintln!(
+                        "[NOTE]: Ignoring netctl config \"{}{}\": {}",
+                        self.get_netctl_cfg_dir(),
+                        identifier,
+                        e
+                    );
+                }
+                None
+            }
+        }
+    }
+
     pub(crate) fn get_wired_configs(
         &self,
         ifname: &str,
@@ -125,10 +155,21 @@ impl<'a, O: Global> NetctlConfigHandler<'a, O> {
         &self,
         interface: &WifiIPInterface,
         network: &AnnotatedWirelessNetwork,
-        encryption_key: &Option<String>,
+        encryption_key: &Option<EncryptionKey>,
+        hash_password: bool,
     ) -> Result<NetctlIdentifier, RuwiError> {
-        let config = WifiNetctlConfig::new(interface, network, encryption_key);
+        if network.get_security() == Some(WifiSecurity::Enterprise) {
+            return Err(rerr!(
+                RuwiErrorKind::EnterpriseNetworksMustBeConfiguredManually,
+                format!(
+                    "\"{}\" is an 802.1X (enterprise) network, which ruwi cannot configure automatically. Create a netctl profile for it (see `man netctl.profile` and /etc/netctl/examples/wireless-wpa-configsection), then run ruwi again.",
+                    network.get_public_name()
+                )
+            ));
+        }
+        let mut config = WifiNetctlConfig::new(interface, network, encryption_key, hash_password);
 
+        config.identifier = self.choose_identifier_for_config(&config)?;
         self.write_config_to_file(&config)?;
         Ok(config.get_identifier().clone())
     }
@@ -148,7 +189,7 @@ impl<'a, O: Global> NetctlConfigHandler<'a, O> {
 #[cfg(test)]
 mod tests {
     use super::config_finder::WifiNetctlConfigFinderCriteria;
-    use super::structs::NetctlConnectionType;
+    use super::structs::{NetctlConnectionType, NetctlIPMethod, NetctlSecurity};
     use super::*;
 
     use crate::options::wifi::connect::WifiConnectOptions;
@@ -199,6 +240,12 @@ mod tests {
                 interface_name: "enp0s25".to_string(),
                 essid: None,
                 encryption_key: None,
+                security: None,
+                ip: Some(NetctlIPMethod::Dhcp),
+                addresses: vec![],
+                dns: vec![],
+                hidden: false,
+                priority: None,
             },
             _ if filename == WIRELESS_OPEN_SAMPLE_FILENAME => NetctlRawParsedFields {
                 identifier: NetctlIdentifier::new(WIRELESS_OPEN_SAMPLE_FILENAME),
@@ -206,6 +253,12 @@ mod tests {
                 interface_name: "wlp3s0".to_string(),
                 essid: Some("Chateau de Chine Hotel".to_string()),
                 encryption_key: None,
+                security: Some(NetctlSecurity::None),
+                ip: Some(NetctlIPMethod::Dhcp),
+                addresses: vec![],
+                dns: vec![],
+                hidden: false,
+                priority: None,
             },
             _ if filename == WIRELESS_ENCRYPTED_SAMPLE_FILENAME => NetctlRawParsedFields {
                 identifier: NetctlIdentifier::new(WIRELESS_ENCRYPTED_SAMPLE_FILENAME),
@@ -213,6 +266,12 @@ mod tests {
                 interface_name: "wlp3s1".to_string(),
                 essid: Some("Lobby".to_string()),
                 encryption_key: Some("KS211819".to_string()),
+                security: Some(NetctlSecurity::Wpa),
+                ip: Some(NetctlIPMethod::Dhcp),
+                addresses: vec![],
+                dns: vec![],
+                hidden: false,
+                priority: None,
             },
             _ => panic!(format!("Config {} not found!", filename)),
         }
@@ -235,12 +294,16 @@ mod tests {
                 interface_name: "wlp3s0".to_string(),
                 essid: "Chateau de Chine Hotel".to_string(),
                 encryption_key: None,
+                security: None,
+                hidden: false,
             },
             _ if filename == WIRELESS_ENCRYPTED_SAMPLE_FILENAME => WifiNetctlConfig {
                 identifier: NetctlIdentifier::new(WIRELESS_ENCRYPTED_SAMPLE_FILENAME),
                 interface_name: "wlp3s1".to_string(),
                 essid: "Lobby".to_string(),
                 encryption_key: Some("KS211819".to_string()),
+                security: None,
+                hidden: false,
             },
             _ => panic!(format!("Wifi config {} not found!", filename)),
         }
@@ -348,4 +411,57 @@ mod tests {
         let config = configs.first().unwrap();
         assert_eq!["kingship_lobby", &config.identifier];
     }
+
+    #[test]
+    fn test_unusable_configs_are_skipped() {
+        let opts = WifiConnectOptions::default();
+        let mut configs = get_sample_configs();
+        configs.push((
+            NetctlIdentifier::new("unterminated"),
+            NetctlRawConfigContents::new("Connection=wireless\nInterface=wlp3s0\nESSID='Oops"),
+        ));
+        configs.push((
+            NetctlIdentifier::new("no_essid"),
+            NetctlRawConfigContents::new("Connection=wireless\nInterface=wlp3s0"),
+        ));
+        configs.push((
+            NetctlIdentifier::new("bridge"),
+            NetctlRawConfigContents::new("Connection=bridge\nInterface=br0"),
+        ));
+        let handler = NetctlConfigHandler::builder()
+            .opts(&opts)
+            .given_configs(configs)
+            .build();
+
+        let untyped_configs = handler.get_all_parsed_but_untyped_configs().unwrap();
+        assert_eq![4, untyped_configs.len()];
+
+        let wifi_configs: Vec<WifiNetctlConfig> = handler.get_all_typed_configs().unwrap();
+        let mut identifiers = wifi_configs
+            .iter()
+            .map(|config| config.identifier.as_ref())
+            .collect::<Vec<_>>();
+        identifiers.sort_unstable();
+        assert_eq![vec!["Chateau_de_Chine_Hotel", "kingship_lobby"], identifiers];
+    }
+
+    #[test]
+    fn test_parse_error_messages() {
+        let raw = NetctlRawConfig::builder()
+            .identifier("unterminated")
+            .contents("Connection=wireless\nInterface=wlp3s0\nESSID='Oops")
+            .location("samples/")
+            .build();
+        let err = NetctlRawParsedFields::try_from(&raw).err().unwrap();
+        assert![err.should_be_reported()];
+        assert_eq!["line 3: unterminated single quote", err.to_string()];
+
+        let raw = NetctlRawConfig::builder()
+            .identifier("static")
+            .contents("Connection=ethernet\nInterface=eth0\nIP=static")
+            .location("samples/")
+            .build();
+        let err = NetctlRawParsedFields::try_from(&raw).err().unwrap();
+        assert![err.to_string().contains("no Address")];
+    }
 }
@@ -11,11 +11,12 @@ use strum_macros::{Display, EnumString};
 use typed_builder::TypedBuilder;
 
 use std::convert::TryFrom;
-use std::str::FromStr;
 
 string_container! {NetctlRawConfigContents}
 
-pub(super) trait NetctlConfig<'a>: fmt::Display + TryFrom<NetctlRawParsedFields> {
+pub(super) trait NetctlConfig<'a>:
+    fmt::Display + TryFrom<NetctlRawParsedFields, Error = NetctlParseError>
+{
     type Checker: NetctlConfigFinderCriteria<'a>;
 
     fn get_identifier(&self) -> &NetctlIdentifier;
@@ -37,48 +38,50 @@ pub(super) struct NetctlRawParsedFields {
     pub(super) interface_name: String,
     pub(super) essid: Option<String>,
     pub(super) encryption_key: Option<String>,
+    #[builder(default = None)]
+    pub(super) security: Option<NetctlSecurity>,
+    #[builder(default = None)]
+    pub(super) ip: Option<NetctlIPMethod>,
+    #[builder(default)]
+    pub(super) addresses: Vec<String>,
+    #[builder(default)]
+    pub(super) dns: Vec<String>,
+    #[builder(default = false)]
+    pub(super) hidden: bool,
+    #[builder(default = None)]
+    pub(super) priority: Option<i32>,
 }
 
-// TODO: unit test in own file
 impl<'a> TryFrom<&NetctlRawConfig<'a>> for NetctlRawParsedFields {
     type Error = NetctlParseError;
 
     fn try_from(raw: &NetctlRawConfig) -> Result<Self, Self::Error> {
-        // TODO:
-        // [] handle errors gracefully, don't just kill ruwi because a single config doesn't parse
-        let identifier = raw.identifier.clone();
-        // TODO TODO: decide if netctl parse errors should own some error text to present to the
-        // user later
-        let connection_type_text = raw.get_connection_type().ok_or_else(|| {
-            NetctlParseError::MissingFieldInNetctlConfig(format!(
-                "No connection type found for config {}!",
-                raw.identifier.as_ref()
-            ))
-        })?;
-        let connection_type =
-            NetctlConnectionType::from_str(&connection_type_text).map_err(|_e| {
-                NetctlParseError::MissingFieldInNetctlConfig(format!(
-                    "Failed to parse connection type \"{}\" found in config {}!",
-                    &connection_type_text,
-                    raw.identifier.as_ref()
-                ))
-            })?;
-        let interface_name = raw.get_interface().ok_or_else(|| {
-            NetctlParseError::MissingFieldInNetctlConfig(format!(
-                "No interface found for config {}!",
-                raw.identifier.as_ref()
-            ))
+        let profile = raw.parse_profile()?;
+        let connection_type = profile.get_connection_type()?;
+        let interface_name = profile.get_interface()?.ok_or_else(|| {
+            NetctlParseError::MissingFieldInNetctlConfig("no interface was found".to_string())
         })?;
 
-        let essid = raw.get_essid();
-        let encryption_key = raw.get_encryption_key();
+        let ip = profile.get_ip()?;
+        let addresses = profile.get_addresses();
+        if ip == Some(NetctlIPMethod::Static) && addresses.is_empty() {
+            return Err(NetctlParseError::MissingFieldInNetctlConfig(
+                "IP=static is set, but no Address was found".to_string(),
+            ));
+        }
 
         Ok(Self::builder()
-            .identifier(identifier)
+            .identifier(raw.identifier.clone())
             .connection_type(connection_type)
             .interface_name(interface_name)
-            .essid(essid)
-            .encryption_key(encryption_key)
+            .essid(profile.get_essid()?)
+            .encryption_key(profile.get_encryption_key()?)
+            .security(profile.get_security()?)
+            .ip(ip)
+            .addresses(addresses)
+            .dns(profile.get_dns())
+            .hidden(profile.get_hidden()?)
+            .priority(profile.get_priority()?)
             .build())
     }
 }
@@ -91,12 +94,41 @@ pub(super) enum NetctlConnectionType {
     Wired,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString, AsStaticStr)]
+pub(super) enum NetctlSecurity {
+    #[strum(serialize = "none")]
+    None,
+    #[strum(serialize = "wep")]
+    Wep,
+    #[strum(serialize = "wpa")]
+    Wpa,
+    #[strum(serialize = "wpa-configsection")]
+    WpaConfigSection,
+    #[strum(serialize = "wpa-config")]
+    WpaConfig,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString, AsStaticStr)]
+pub(super) enum NetctlIPMethod {
+    #[strum(serialize = "dhcp")]
+    Dhcp,
+    #[strum(serialize = "static")]
+    Static,
+    #[strum(serialize = "no")]
+    No,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, TypedBuilder)]
 pub(super) struct WifiNetctlConfig {
     pub(super) identifier: NetctlIdentifier,
     pub(super) essid: String,
     pub(super) interface_name: String,
+    // As netctl sees it, so a raw PSK starts with ".
     pub(super) encryption_key: Option<String>,
+    #[builder(default = None)]
+    pub(super) security: Option<WifiSecurity>,
+    #[builder(default = false)]
+    pub(super) hidden: bool,
 }
 
 impl<'a> NetctlConfig<'a> for WifiNetctlConfig {
@@ -120,13 +152,14 @@ impl TryFrom<NetctlRawParsedFields> for WifiNetctlConfig {
         check_connection_type(&NetctlConnectionType::Wifi, &f.connection_type)?;
         let identifier = f.identifier;
         let interface_name = f.interface_name;
-        let essid = check_for_field(&f.essid, &identifier, "ESSID")?;
+        let essid = check_for_field(&f.essid, "ESSID")?;
         let encryption_key = f.encryption_key;
         Ok(Self::builder()
             .identifier(identifier)
             .interface_name(interface_name)
             .essid(essid)
             .encryption_key(encryption_key)
+            .hidden(f.hidden)
             .build())
     }
 }
@@ -171,7 +204,29 @@ impl TryFrom<NetctlRawParsedFields> for WiredNetctlConfig {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub(super) enum NetctlParseError {
     IncorrectConnectionType,
+    InvalidFieldValue(usize, String),
+    InvalidSyntax(usize, String),
     MissingFieldInNetctlConfig(String),
 }
+
+impl NetctlParseError {
+    // Configs for another connection type are expected, and just aren't the ones being looked for.
+    pub(super) fn should_be_reported(&self) -> bool {
+        *self != Self::IncorrectConnectionType
+    }
+}
+
+impl fmt::Display for NetctlParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::IncorrectConnectionType => write!(f, "not the expected connection type"),
+            Self::InvalidFieldValue(line, reason) | Self::InvalidSyntax(line, reason) => {
+                write!(f, "line {}: {}", line, reason)
+            }
+            Self::MissingFieldInNetctlConfig(reason) => f.write_str(reason),
+        }
+    }
+}
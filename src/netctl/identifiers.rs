@@ -2,3 +2,112 @@ use crate::string_container;
 
 string_container! {NetctlIdentifier}
 
+// Longer names are cut short, leaving room for the suffix below within the usual 255-byte limit
+// on filenames.
+const MAX_NAME_LENGTH: usize = 200;
+
+impl NetctlIdentifier {
+    // Profile names are filenames in the netctl directory, so an ESSID can't be used as-is: a "/"
+    // or ".." could point outside of it, and a leading "." would make a hidden file. Spaces become
+    // underscores as before, and anything else which had to be changed gets a suffix made from the
+    // ESSID itself, so that e.g. "a/b" and "a.b" don't end up sharing a profile, and so that the
+    // same ESSID always gets the same name.
+    pub(crate) fn from_essid(essid: &str) -> Self {
+        let name = sanitize_essid(essid);
+        if name.is_empty() || name != essid.replace(' ', "_") {
+            Self::from_essid_with_suffix(essid)
+        } else {
+            Self::new(name)
+        }
+    }
+
+    // Used when the usual name is already taken by a profile for another network, e.g. when
+    // "Cafe Wifi" and "Cafe_Wifi" would otherwise share "Cafe_Wifi".
+    pub(crate) fn from_essid_with_suffix(essid: &str) -> Self {
+        Self::new(format!(
+            "{}_{:08x}",
+            sanitize_essid(essid),
+            fnv1a_hash(essid.as_bytes())
+        ))
+    }
+}
+
+fn sanitize_essid(essid: &str) -> String {
+    let mut name = String::new();
+    for c in essid.chars() {
+        if name.len() + c.len_utf8() > MAX_NAME_LENGTH {
+            break;
+        }
+        match c {
+            '.' if name.is_empty() => name.push('_'),
+            '/' => name.push('_'),
+            c if c.is_whitespace() || c.is_control() => name.push('_'),
+            c => name.push(c),
+        }
+    }
+    name
+}
+
+// Used instead of std's hashers, which aren't guaranteed to give the same result between versions.
+fn fnv1a_hash(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5, |hash, &byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_essid_unchanged() {
+        assert_eq!["Citadel", NetctlIdentifier::from_essid("Citadel").as_ref()];
+        assert_eq!["FU_ARK", NetctlIdentifier::from_essid("FU ARK").as_ref()];
+        assert_eq!["caf\u{e9}.5G", NetctlIdentifier::from_essid("caf\u{e9}.5G").as_ref()];
+    }
+
+    #[test]
+    fn test_from_essid_sanitized() {
+        let names = ["../../etc/shadow", ".hidden", "..", "a/b", "a\nb", "", "a\u{1f4f6}b/"]
+            .iter()
+            .map(|essid| NetctlIdentifier::from_essid(essid).to_string())
+            .collect::<Vec<_>>();
+        for name in &names {
+            assert![!name.contains('/'), "{}", name];
+            assert![!name.starts_with('.'), "{}", name];
+            assert![!name.contains('\n'), "{}", name];
+            assert![!name.is_empty()];
+        }
+        assert![names[0].starts_with("_._.._etc_shadow_")];
+        assert![names[3].starts_with("a_b_")];
+        assert![names[6].starts_with("a\u{1f4f6}b__")];
+    }
+
+    #[test]
+    fn test_from_essid_suffix_is_deterministic() {
+        let a_slash_b = NetctlIdentifier::from_essid("a/b");
+        assert_eq![a_slash_b, NetctlIdentifier::from_essid("a/b")];
+        assert_ne![a_slash_b, NetctlIdentifier::from_essid("a\nb")];
+        assert_ne![a_slash_b, NetctlIdentifier::from_essid("a b")];
+    }
+
+    #[test]
+    fn test_from_essid_with_suffix() {
+        let name = NetctlIdentifier::from_essid_with_suffix("Cafe Wifi");
+        assert![name.as_ref().starts_with("Cafe_Wifi_")];
+        assert_eq![name.as_ref().len(), "Cafe_Wifi_".len() + 8];
+        assert_ne![name, NetctlIdentifier::from_essid_with_suffix("Cafe_Wifi")];
+        assert_eq![
+            NetctlIdentifier::from_essid("a/b"),
+            NetctlIdentifier::from_essid_with_suffix("a/b")
+        ];
+    }
+
+    #[test]
+    fn test_from_essid_long() {
+        let essid = "x".repeat(300);
+        let name = NetctlIdentifier::from_essid(&essid);
+        assert![name.as_ref().len() <= 255];
+        assert_ne![name, NetctlIdentifier::from_essid(&"x".repeat(299))];
+    }
+}
@@ -2,7 +2,9 @@ mod config_handler;
 mod identifiers;
 pub(crate) mod utils;
 
-const DEFAULT_NETCTL_CFG_DIR: &str = "/etc/netctl/";
+pub(crate) const DEFAULT_NETCTL_CFG_DIR: &str = "/etc/netctl/";
 
 pub(crate) use config_handler::NetctlConfigHandler;
+#[cfg(test)]
+pub(crate) use config_handler::NetctlRawConfigContents;
 pub(crate) use identifiers::NetctlIdentifier;
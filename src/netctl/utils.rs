@@ -24,3 +24,20 @@ where
     )
 }
 
+// Stopping a profile which isn't running does nothing, so this is safe to run before removing one.
+pub(crate) fn netctl_stop<O>(
+    options: &O,
+    netctl_identifier: &NetctlIdentifier,
+) -> Result<(), RuwiError>
+where
+    O: Global,
+{
+    SystemCommandRunner::new(options, "netctl", &["stop", netctl_identifier.as_ref()])
+        .run_command_pass(
+            RuwiErrorKind::FailedToStopNetctl,
+            &format!(
+                "Failed to stop netctl profile \"{}\"! Are you running as root?",
+                netctl_identifier.as_ref()
+            ),
+        )
+}
@@ -1,4 +1,4 @@
-use crate::enums::{NetworkingService, NetworkingServiceIdentifier};
+use crate::enums::{DisplayField, NetworkingService, NetworkingServiceIdentifier};
 use crate::interface_management::ip_interfaces::{LinuxIPInterface, WifiIPInterface};
 use crate::netctl::NetctlIdentifier;
 
@@ -18,6 +18,16 @@ pub trait Known {
 
 pub trait Selectable {
     fn get_display_string(&self) -> String;
+
+    // A single field for `--display-template`, or None if it doesn't apply to this item.
+    fn get_display_field(&self, _field: DisplayField, _use_icons: bool) -> Option<String> {
+        None
+    }
+
+    // Full details, shown in the preview pane of selectors which support one.
+    fn get_preview_string(&self) -> Option<String> {
+        None
+    }
 }
 
 // This exists so that AnnotatedRuwiNetwork does not need to have the
@@ -2,6 +2,7 @@ use typed_builder::TypedBuilder;
 
 use crate::options::bluetooth::BluetoothOptions;
 use crate::prelude::*;
+use crate::select::display_template::DisplayTemplate;
 
 #[derive(Debug, Clone, TypedBuilder)]
 pub struct BluetoothConnectOptions {
@@ -67,6 +68,15 @@ impl PreParseGlobal for BluetoothConnectOptions {
     fn get_selection_method(&self) -> &SelectionMethod {
         self.bluetooth.get_selection_method()
     }
+    fn get_selection_command(&self) -> &Option<String> {
+        self.bluetooth.get_selection_command()
+    }
+    fn get_display_template(&self) -> &Option<DisplayTemplate> {
+        self.bluetooth.get_display_template()
+    }
+    fn get_select_timeout(&self) -> &Option<SelectTimeout> {
+        self.bluetooth.get_select_timeout()
+    }
     fn is_test_or_dry_run(&self) -> bool {
         self.bluetooth.is_test_or_dry_run()
     }
@@ -1,5 +1,6 @@
 use crate::prelude::*;
 use crate::options::GlobalOptions;
+use crate::select::display_template::DisplayTemplate;
 use typed_builder::TypedBuilder;
 
 #[derive(Debug, Clone, TypedBuilder)]
@@ -36,6 +37,15 @@ impl PreParseGlobal for ClearOptions {
     fn get_selection_method(&self) -> &SelectionMethod {
         self.globals.get_selection_method()
     }
+    fn get_selection_command(&self) -> &Option<String> {
+        self.globals.get_selection_command()
+    }
+    fn get_display_template(&self) -> &Option<DisplayTemplate> {
+        self.globals.get_display_template()
+    }
+    fn get_select_timeout(&self) -> &Option<SelectTimeout> {
+        self.globals.get_select_timeout()
+    }
     fn is_test_or_dry_run(&self) -> bool {
         self.globals.is_test_or_dry_run()
     }
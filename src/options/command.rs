@@ -1,6 +1,9 @@
 use crate::errors::RuwiError;
 use crate::options::clear::ClearOptions;
 use crate::options::wifi::connect::WifiConnectOptions;
+use crate::options::wifi::forget::WifiForgetOptions;
+use crate::options::wifi::info::WifiInfoOptions;
+use crate::options::wifi::list::WifiListOptions;
 use crate::options::wifi::select::WifiSelectOptions;
 use crate::options::wired::connect::WiredConnectOptions;
 use crate::options::bluetooth::connect::BluetoothConnectOptions;
@@ -33,6 +36,9 @@ impl RuwiCommand {
         match self {
             Self::Wifi(RuwiWifiCommand::Connect(options)) => options.run(),
             Self::Wifi(RuwiWifiCommand::Select(options)) => options.run(),
+            Self::Wifi(RuwiWifiCommand::List(options)) => options.run(),
+            Self::Wifi(RuwiWifiCommand::Info(options)) => options.run(),
+            Self::Wifi(RuwiWifiCommand::Forget(options)) => options.run(),
             Self::Wired(RuwiWiredCommand::Connect(options)) => options.run(),
             Self::Bluetooth(RuwiBluetoothCommand::Connect(options)) => options.run(),
             // TODO: give clear its own options, and make it match this format
@@ -46,6 +52,9 @@ impl RuwiCommand {
 pub enum RuwiWifiCommand {
     Connect(WifiConnectOptions),
     Select(WifiSelectOptions),
+    List(WifiListOptions),
+    Info(WifiInfoOptions),
+    Forget(WifiForgetOptions),
 }
 
 impl Default for RuwiWifiCommand {
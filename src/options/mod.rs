@@ -11,10 +11,15 @@ pub mod traits;
 pub(crate) mod wifi;
 pub(crate) mod wired;
 
+use crate::cmdline_parser::SELECTION_METHOD_TOKEN;
 use crate::enums::SelectionMethod;
+use crate::select::display_template::DisplayTemplate;
+use crate::service_detection::{HasSystemCheckMapping, SystemCheckerReal};
+use crate::structs::SelectTimeout;
 
 use traits::PreParseGlobal;
 
+use std::sync::OnceLock;
 use typed_builder::TypedBuilder;
 pub static PROG_NAME: &str = "ruwi";
 
@@ -24,8 +29,18 @@ pub struct GlobalOptions {
     debug: bool,
     #[builder(default = true)]
     dry_run: bool,
+    // None means the best selection method is looked for on the system, the first time one is
+    // needed, so commands which never prompt don't pay for the system checks.
+    #[builder(default = Some(SelectionMethod::default()))]
+    selection_method: Option<SelectionMethod>,
     #[builder(default)]
-    selection_method: SelectionMethod,
+    detected_selection_method: OnceLock<SelectionMethod>,
+    #[builder(default = None)]
+    selection_command: Option<String>,
+    #[builder(default = None)]
+    display_template: Option<DisplayTemplate>,
+    #[builder(default = None)]
+    select_timeout: Option<SelectTimeout>,
     #[builder(default = false)]
     pretend_to_be_root: bool,
 }
@@ -41,7 +56,22 @@ impl PreParseGlobal for GlobalOptions {
         self.dry_run
     }
     fn get_selection_method(&self) -> &SelectionMethod {
-        &self.selection_method
+        match &self.selection_method {
+            Some(selection_method) => selection_method,
+            None => self.detected_selection_method.get_or_init(|| {
+                let checker = SystemCheckerReal::new(self);
+                SelectionMethod::choose_best_from_system(&checker, SELECTION_METHOD_TOKEN)
+            }),
+        }
+    }
+    fn get_selection_command(&self) -> &Option<String> {
+        &self.selection_command
+    }
+    fn get_display_template(&self) -> &Option<DisplayTemplate> {
+        &self.display_template
+    }
+    fn get_select_timeout(&self) -> &Option<SelectTimeout> {
+        &self.select_timeout
     }
 
     fn is_test_or_dry_run(&self) -> bool {
@@ -63,7 +93,11 @@ impl Default for GlobalOptions {
     fn default() -> Self {
         Self {
             debug: false,
-            selection_method: SelectionMethod::default(),
+            selection_method: Some(SelectionMethod::default()),
+            detected_selection_method: OnceLock::new(),
+            selection_command: None,
+            display_template: None,
+            select_timeout: None,
             #[cfg(not(test))]
             dry_run: false,
             #[cfg(test)]
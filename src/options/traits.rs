@@ -7,6 +7,7 @@ use crate::prelude::*;
 
 use crate::interface_management::ip_interfaces::WifiIPInterface;
 use crate::known_networks::WifiKnownNetworks;
+use crate::select::display_template::DisplayTemplate;
 
 use crate::structs::ScanResult;
 
@@ -15,6 +16,9 @@ pub trait PreParseGlobal {
     fn get_debug(&self) -> bool;
     fn get_dry_run(&self) -> bool;
     fn get_selection_method(&self) -> &SelectionMethod;
+    fn get_selection_command(&self) -> &Option<String>;
+    fn get_display_template(&self) -> &Option<DisplayTemplate>;
+    fn get_select_timeout(&self) -> &Option<SelectTimeout>;
     fn is_test_or_dry_run(&self) -> bool;
     fn pretend_to_be_root(&self) -> bool;
 }
@@ -29,6 +33,10 @@ pub trait Wifi {
     fn get_ignore_known(&self) -> bool;
     fn get_force_synchronous_scan(&self) -> bool;
     fn get_given_interface_name(&self) -> &Option<String>;
+    fn get_strict_parse(&self) -> bool;
+    fn get_detect_scan_type(&self) -> bool;
+    fn get_sort_policy(&self) -> &SortPolicy;
+    fn get_essid_priority(&self) -> &[String];
 }
 
 pub trait Wired {
@@ -39,11 +47,18 @@ pub trait AutoSelect {
     fn get_auto_mode(&self) -> &AutoMode;
 }
 
-pub trait WifiConnect {
-    fn get_force_ask_password(&self) -> bool;
-    fn get_given_essid(&self) -> &Option<String>;
-    fn get_given_encryption_key(&self) -> &Option<String>;
+// What's needed to look up known networks, for commands which don't connect to anything.
+pub trait WifiConnectionManager {
     fn get_connect_via(&self) -> &WifiConnectionType;
+    fn get_given_essid(&self) -> &Option<String>;
+}
+
+pub trait WifiConnect: WifiConnectionManager {
+    fn get_force_ask_password(&self) -> bool;
+    fn get_confirm_password(&self) -> bool;
+    fn get_password_prompt_method(&self) -> &PasswordPromptMethod;
+    fn get_hash_password(&self) -> bool;
+    fn get_given_encryption_key(&self) -> &Option<EncryptionKey>;
 }
 
 pub trait WiredConnect {
@@ -62,6 +77,6 @@ pub trait WifiDataGatherer {
         &self,
         interface: &WifiIPInterface,
         synchronous_rescan: &Option<SynchronousRescanType>,
-    ) -> Result<(WifiKnownNetworks, ScanResult), RuwiError>;
+    ) -> Result<(WifiKnownNetworks, Vec<ScanResult>), RuwiError>;
 }
 
@@ -1,7 +1,9 @@
 use typed_builder::TypedBuilder;
 
+use crate::essid_pattern::EssidPattern;
 use crate::options::wifi::WifiOptions;
 use crate::prelude::*;
+use crate::select::display_template::DisplayTemplate;
 
 #[derive(Debug, Clone, TypedBuilder)]
 pub struct WifiConnectOptions {
@@ -13,9 +15,19 @@ pub struct WifiConnectOptions {
     #[builder(default = None)]
     given_essid: Option<String>,
     #[builder(default = false)]
+    given_essid_is_hidden: bool,
+    #[builder(default = None)]
+    given_essid_pattern: Option<EssidPattern>,
+    #[builder(default = false)]
     force_ask_password: bool,
+    #[builder(default = false)]
+    confirm_password: bool,
+    #[builder(default)]
+    password_prompt_method: PasswordPromptMethod,
+    #[builder(default = true)]
+    hash_password: bool,
     #[builder(default = None)]
-    given_encryption_key: Option<String>,
+    given_encryption_key: Option<EncryptionKey>,
 }
 
 impl Default for WifiConnectOptions {
@@ -24,9 +36,14 @@ impl Default for WifiConnectOptions {
             wifi: WifiOptions::default(),
             connect_via: WifiConnectionType::default(),
             given_essid: None,
+            given_essid_is_hidden: false,
+            given_essid_pattern: None,
             given_encryption_key: None,
             auto_mode: AutoMode::default(),
             force_ask_password: false,
+            confirm_password: false,
+            password_prompt_method: PasswordPromptMethod::default(),
+            hash_password: true,
         }
     }
 }
@@ -50,6 +67,15 @@ impl PreParseGlobal for WifiConnectOptions {
     fn get_selection_method(&self) -> &SelectionMethod {
         self.wifi.get_selection_method()
     }
+    fn get_selection_command(&self) -> &Option<String> {
+        self.wifi.get_selection_command()
+    }
+    fn get_display_template(&self) -> &Option<DisplayTemplate> {
+        self.wifi.get_display_template()
+    }
+    fn get_select_timeout(&self) -> &Option<SelectTimeout> {
+        self.wifi.get_select_timeout()
+    }
     fn is_test_or_dry_run(&self) -> bool {
         self.wifi.is_test_or_dry_run()
     }
@@ -74,6 +100,18 @@ impl Wifi for WifiConnectOptions {
     fn get_given_interface_name(&self) -> &Option<String> {
         self.wifi.get_given_interface_name()
     }
+    fn get_strict_parse(&self) -> bool {
+        self.wifi.get_strict_parse()
+    }
+    fn get_detect_scan_type(&self) -> bool {
+        self.wifi.get_detect_scan_type()
+    }
+    fn get_sort_policy(&self) -> &SortPolicy {
+        self.wifi.get_sort_policy()
+    }
+    fn get_essid_priority(&self) -> &[String] {
+        self.wifi.get_essid_priority()
+    }
 }
 
 impl AutoSelect for WifiConnectOptions {
@@ -86,18 +124,38 @@ impl WifiConnect for WifiConnectOptions {
     fn get_force_ask_password(&self) -> bool {
         self.force_ask_password
     }
-    fn get_given_essid(&self) -> &Option<String> {
-        &self.given_essid
+    fn get_confirm_password(&self) -> bool {
+        self.confirm_password
+    }
+    fn get_password_prompt_method(&self) -> &PasswordPromptMethod {
+        &self.password_prompt_method
+    }
+    fn get_hash_password(&self) -> bool {
+        self.hash_password
     }
-    fn get_given_encryption_key(&self) -> &Option<String> {
+    fn get_given_encryption_key(&self) -> &Option<EncryptionKey> {
         &self.given_encryption_key
     }
+}
+
+impl WifiConnectionManager for WifiConnectOptions {
     fn get_connect_via(&self) -> &WifiConnectionType {
         &self.connect_via
     }
+    fn get_given_essid(&self) -> &Option<String> {
+        &self.given_essid
+    }
 }
 
 impl WifiConnectOptions {
+    pub fn get_given_essid_is_hidden(&self) -> bool {
+        self.given_essid_is_hidden
+    }
+
+    pub fn get_given_essid_pattern(&self) -> &Option<EssidPattern> {
+        &self.given_essid_pattern
+    }
+
     #[cfg(test)]
     pub fn from_scan_type(scan_type: WifiScanType) -> Self {
         Self {
@@ -0,0 +1,59 @@
+use crate::options::GlobalOptions;
+use crate::prelude::*;
+use crate::select::display_template::DisplayTemplate;
+use typed_builder::TypedBuilder;
+
+// Options for the hidden `wifi info` subcommand, which fzf calls to fill its preview pane.
+#[derive(Debug, Clone, Default, TypedBuilder)]
+pub struct WifiInfoOptions {
+    globals: GlobalOptions,
+    details_file: String,
+    selected_line: String,
+}
+
+impl WifiInfoOptions {
+    pub fn get_details_file(&self) -> &str {
+        &self.details_file
+    }
+    pub fn get_selected_line(&self) -> &str {
+        &self.selected_line
+    }
+}
+
+impl Global for WifiInfoOptions {
+    fn get_post_parse_context(&self) -> PostParseContext {
+        PostParseContext {
+            network_or_device: NetworkOrDevice::Network,
+        }
+    }
+}
+
+impl PreParseGlobal for WifiInfoOptions {
+    fn d(&self) -> bool {
+        self.get_debug()
+    }
+    fn get_debug(&self) -> bool {
+        self.globals.get_debug()
+    }
+    fn get_dry_run(&self) -> bool {
+        self.globals.get_dry_run()
+    }
+    fn get_selection_method(&self) -> &SelectionMethod {
+        self.globals.get_selection_method()
+    }
+    fn get_selection_command(&self) -> &Option<String> {
+        self.globals.get_selection_command()
+    }
+    fn get_display_template(&self) -> &Option<DisplayTemplate> {
+        self.globals.get_display_template()
+    }
+    fn get_select_timeout(&self) -> &Option<SelectTimeout> {
+        self.globals.get_select_timeout()
+    }
+    fn is_test_or_dry_run(&self) -> bool {
+        self.globals.is_test_or_dry_run()
+    }
+    fn pretend_to_be_root(&self) -> bool {
+        self.globals.pretend_to_be_root()
+    }
+}
@@ -0,0 +1,105 @@
+use typed_builder::TypedBuilder;
+
+use crate::options::wifi::WifiOptions;
+use crate::prelude::*;
+use crate::select::display_template::DisplayTemplate;
+
+#[derive(Debug, Clone, Default, TypedBuilder)]
+pub struct WifiListOptions {
+    wifi: WifiOptions,
+    #[builder(default)]
+    connect_via: WifiConnectionType,
+    #[builder(default)]
+    format: WifiListFormat,
+}
+
+impl WifiListOptions {
+    pub fn get_format(&self) -> &WifiListFormat {
+        &self.format
+    }
+}
+
+impl Global for WifiListOptions {
+    fn get_post_parse_context(&self) -> PostParseContext {
+        self.wifi.get_post_parse_context()
+    }
+}
+
+impl PreParseGlobal for WifiListOptions {
+    fn d(&self) -> bool {
+        self.get_debug()
+    }
+    fn get_debug(&self) -> bool {
+        self.wifi.get_debug()
+    }
+    fn get_dry_run(&self) -> bool {
+        self.wifi.get_dry_run()
+    }
+    fn get_selection_method(&self) -> &SelectionMethod {
+        self.wifi.get_selection_method()
+    }
+    fn get_selection_command(&self) -> &Option<String> {
+        self.wifi.get_selection_command()
+    }
+    fn get_display_template(&self) -> &Option<DisplayTemplate> {
+        self.wifi.get_display_template()
+    }
+    fn get_select_timeout(&self) -> &Option<SelectTimeout> {
+        self.wifi.get_select_timeout()
+    }
+    fn is_test_or_dry_run(&self) -> bool {
+        self.wifi.is_test_or_dry_run()
+    }
+    fn pretend_to_be_root(&self) -> bool {
+        self.wifi.pretend_to_be_root()
+    }
+}
+
+impl Wifi for WifiListOptions {
+    fn get_scan_type(&self) -> &WifiScanType {
+        self.wifi.get_scan_type()
+    }
+    fn get_scan_method(&self) -> &ScanMethod {
+        self.wifi.get_scan_method()
+    }
+    fn get_ignore_known(&self) -> bool {
+        self.wifi.get_ignore_known()
+    }
+    fn get_force_synchronous_scan(&self) -> bool {
+        self.wifi.get_force_synchronous_scan()
+    }
+    fn get_given_interface_name(&self) -> &Option<String> {
+        self.wifi.get_given_interface_name()
+    }
+    fn get_strict_parse(&self) -> bool {
+        self.wifi.get_strict_parse()
+    }
+    fn get_detect_scan_type(&self) -> bool {
+        self.wifi.get_detect_scan_type()
+    }
+    fn get_sort_policy(&self) -> &SortPolicy {
+        self.wifi.get_sort_policy()
+    }
+    fn get_essid_priority(&self) -> &[String] {
+        self.wifi.get_essid_priority()
+    }
+}
+
+// Listing never auto-selects anything, but the synchronous retry logic
+// uses the auto mode to decide whether to rescan.
+impl AutoSelect for WifiListOptions {
+    fn get_auto_mode(&self) -> &AutoMode {
+        &AutoMode::Ask
+    }
+}
+
+// Known networks are looked up through the connection manager, so listing
+// needs to know which one would be used to connect.
+impl WifiConnectionManager for WifiListOptions {
+    fn get_connect_via(&self) -> &WifiConnectionType {
+        &self.connect_via
+    }
+    fn get_given_essid(&self) -> &Option<String> {
+        &None
+    }
+}
@@ -1,8 +1,12 @@
 pub(crate) mod connect;
+pub(crate) mod forget;
+pub(crate) mod info;
+pub(crate) mod list;
 pub(crate) mod select;
 
 use crate::prelude::*;
 use crate::options::GlobalOptions;
+use crate::select::display_template::DisplayTemplate;
 use typed_builder::TypedBuilder;
 
 #[derive(Debug, Clone, TypedBuilder)]
@@ -18,6 +22,14 @@ pub struct WifiOptions {
     ignore_known: bool,
     #[builder(default = false)]
     force_synchronous_scan: bool,
+    #[builder(default = false)]
+    strict_parse: bool,
+    #[builder(default = false)]
+    detect_scan_type: bool,
+    #[builder(default)]
+    sort_policy: SortPolicy,
+    #[builder(default)]
+    essid_priority: Vec<String>,
 }
 
 impl Default for WifiOptions {
@@ -29,6 +41,10 @@ impl Default for WifiOptions {
             given_interface_name: None,
             ignore_known: false,
             force_synchronous_scan: false,
+            strict_parse: false,
+            detect_scan_type: false,
+            sort_policy: SortPolicy::default(),
+            essid_priority: vec![],
         }
     }
 }
@@ -59,6 +75,18 @@ impl Wifi for WifiOptions {
     fn get_given_interface_name(&self) -> &Option<String> {
         &self.given_interface_name
     }
+    fn get_strict_parse(&self) -> bool {
+        self.strict_parse
+    }
+    fn get_detect_scan_type(&self) -> bool {
+        self.detect_scan_type
+    }
+    fn get_sort_policy(&self) -> &SortPolicy {
+        &self.sort_policy
+    }
+    fn get_essid_priority(&self) -> &[String] {
+        &self.essid_priority
+    }
 }
 
 impl Global for WifiOptions {
@@ -83,6 +111,15 @@ impl PreParseGlobal for WifiOptions {
     fn get_selection_method(&self) -> &SelectionMethod {
         self.globals.get_selection_method()
     }
+    fn get_selection_command(&self) -> &Option<String> {
+        self.globals.get_selection_command()
+    }
+    fn get_display_template(&self) -> &Option<DisplayTemplate> {
+        self.globals.get_display_template()
+    }
+    fn get_select_timeout(&self) -> &Option<SelectTimeout> {
+        self.globals.get_select_timeout()
+    }
     fn is_test_or_dry_run(&self) -> bool {
         self.globals.is_test_or_dry_run()
     }
@@ -2,6 +2,7 @@ use typed_builder::TypedBuilder;
 
 use crate::prelude::*;
 use crate::options::wifi::WifiOptions;
+use crate::select::display_template::DisplayTemplate;
 
 #[derive(Debug, Clone, TypedBuilder)]
 pub struct WifiSelectOptions {
@@ -9,6 +10,8 @@ pub struct WifiSelectOptions {
     wifi: WifiOptions,
     #[builder(default)]
     auto_mode: AutoMode,
+    #[builder(default)]
+    connect_via: WifiConnectionType,
 }
 
 impl Default for WifiSelectOptions {
@@ -16,6 +19,7 @@ impl Default for WifiSelectOptions {
         Self {
             wifi: WifiOptions::default(),
             auto_mode: AutoMode::default(),
+            connect_via: WifiConnectionType::default(),
         }
     }
 }
@@ -45,6 +49,15 @@ impl PreParseGlobal for WifiSelectOptions {
     fn get_selection_method(&self) -> &SelectionMethod {
         self.wifi.get_selection_method()
     }
+    fn get_selection_command(&self) -> &Option<String> {
+        self.wifi.get_selection_command()
+    }
+    fn get_display_template(&self) -> &Option<DisplayTemplate> {
+        self.wifi.get_display_template()
+    }
+    fn get_select_timeout(&self) -> &Option<SelectTimeout> {
+        self.wifi.get_select_timeout()
+    }
     fn is_test_or_dry_run(&self) -> bool {
         self.wifi.is_test_or_dry_run()
     }
@@ -69,4 +82,26 @@ impl Wifi for WifiSelectOptions {
     fn get_given_interface_name(&self) -> &Option<String> {
         self.wifi.get_given_interface_name()
     }
+    fn get_strict_parse(&self) -> bool {
+        self.wifi.get_strict_parse()
+    }
+    fn get_detect_scan_type(&self) -> bool {
+        self.wifi.get_detect_scan_type()
+    }
+    fn get_sort_policy(&self) -> &SortPolicy {
+        self.wifi.get_sort_policy()
+    }
+    fn get_essid_priority(&self) -> &[String] {
+        self.wifi.get_essid_priority()
+    }
+}
+
+// Nothing is connected to, but the connection manager is used to find known networks.
+impl WifiConnectionManager for WifiSelectOptions {
+    fn get_connect_via(&self) -> &WifiConnectionType {
+        &self.connect_via
+    }
+    fn get_given_essid(&self) -> &Option<String> {
+        &None
+    }
 }
@@ -2,6 +2,7 @@ use typed_builder::TypedBuilder;
 
 use crate::prelude::*;
 use crate::options::wired::WiredOptions;
+use crate::select::display_template::DisplayTemplate;
 
 #[derive(Debug, Clone, TypedBuilder)]
 pub struct WiredConnectOptions {
@@ -41,6 +42,15 @@ impl PreParseGlobal for WiredConnectOptions {
     fn get_selection_method(&self) -> &SelectionMethod {
         self.wired.get_selection_method()
     }
+    fn get_selection_command(&self) -> &Option<String> {
+        self.wired.get_selection_command()
+    }
+    fn get_display_template(&self) -> &Option<DisplayTemplate> {
+        self.wired.get_display_template()
+    }
+    fn get_select_timeout(&self) -> &Option<SelectTimeout> {
+        self.wired.get_select_timeout()
+    }
     fn is_test_or_dry_run(&self) -> bool {
         self.wired.is_test_or_dry_run()
     }
@@ -0,0 +1,91 @@
+use super::nmcli::{split_nmcli_escaped_line, NMCLI_SCAN_FIELDS};
+use crate::prelude::*;
+use crate::utils::strip_ansi_escape_codes;
+
+const WPA_CLI_HEADER: &str = "bssid / frequency / signal level / flags / ssid";
+
+// Guess which program produced the given scan output. The checks are ordered from the most
+// to the least distinctive format, since nmcli's terse output has no header to look for.
+pub(crate) fn detect_scan_type(output: &str) -> Option<WifiScanType> {
+    let output = strip_ansi_escape_codes(output);
+    let lines = output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+
+    if output.trim_start().starts_with('{') {
+        Some(WifiScanType::RuwiJSON)
+    } else if lines.iter().any(|line| is_iwctl_line(line)) {
+        Some(WifiScanType::Iwctl)
+    } else if lines.iter().any(|line| *line == WPA_CLI_HEADER) {
+        Some(WifiScanType::WpaCli)
+    } else if lines.iter().any(|line| is_iwlist_line(line)) {
+        Some(WifiScanType::IWList)
+    } else if lines.iter().any(|line| is_iw_line(line)) {
+        Some(WifiScanType::IW)
+    } else if !lines.is_empty() && lines.iter().all(|line| is_nmcli_line(line)) {
+        Some(WifiScanType::Nmcli)
+    } else {
+        None
+    }
+}
+
+pub(crate) fn err_failed_to_detect_scan_type(source: &str) -> RuwiError {
+    rerr!(
+        RuwiErrorKind::FailedToDetectScanType,
+        format!(
+            "Could not determine which program produced the scan results from {}. Pass the scan type explicitly with `-s`.",
+            source
+        )
+    )
+}
+
+fn is_iwctl_line(line: &str) -> bool {
+    line == "Available networks" || line == "No networks available" || line.starts_with("Network name ")
+}
+
+fn is_iwlist_line(line: &str) -> bool {
+    line.ends_with("Scan completed :")
+        || line.ends_with("No scan results")
+        || (line.starts_with("Cell ") && line.contains(" - Address: "))
+}
+
+fn is_iw_line(line: &str) -> bool {
+    line.starts_with("BSS ") && line.contains("(on ")
+}
+
+fn is_nmcli_line(line: &str) -> bool {
+    split_nmcli_escaped_line(line).len() == NMCLI_SCAN_FIELDS.split(',').count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_samples() {
+        let samples = [
+            (include_str!("samples/iw_two_different_networks.txt"), WifiScanType::IW),
+            (include_str!("samples/iw_many_networks.txt"), WifiScanType::IW),
+            (include_str!("samples/iwlist_two_different_networks.txt"), WifiScanType::IWList),
+            (include_str!("samples/iwlist_no_scan_results.txt"), WifiScanType::IWList),
+            (include_str!("samples/iwctl_two_different_networks.txt"), WifiScanType::Iwctl),
+            (include_str!("samples/iwctl_no_networks.txt"), WifiScanType::Iwctl),
+            (include_str!("samples/nmcli_two_different_networks.txt"), WifiScanType::Nmcli),
+            (include_str!("samples/nmcli_many_networks.txt"), WifiScanType::Nmcli),
+            (include_str!("samples/wpa_cli_two_different_networks.txt"), WifiScanType::WpaCli),
+            (include_str!("samples/wpa_cli_no_networks.txt"), WifiScanType::WpaCli),
+            (include_str!("samples/ruwi_json_two_networks.json"), WifiScanType::RuwiJSON),
+        ];
+        for (output, expected) in &samples {
+            assert_eq![detect_scan_type(output), Some(expected.clone())];
+        }
+    }
+
+    #[test]
+    fn test_detect_unknown() {
+        assert_eq![detect_scan_type(""), None];
+        assert_eq![detect_scan_type(include_str!("samples/broken_input_two_words.txt")), None];
+    }
+}
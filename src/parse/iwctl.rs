@@ -0,0 +1,106 @@
+use crate::prelude::*;
+use crate::utils::strip_ansi_escape_codes;
+
+const IWCTL_HEADER_TOKEN: &str = "Network name";
+const IWCTL_NO_NETWORKS_TOKEN: &str = "No networks available";
+const IWCTL_CONNECTED_MARKER: char = '>';
+const IWCTL_OPEN_SECURITY_TOKEN: &str = "open";
+
+// Output of `iwctl station <iface> get-networks rssi-dbms` is a table of network name,
+// security type (open, psk, 8021x, ...), and signal, with the currently connected network
+// marked with a '>':
+//
+//       Network name                      Security            Signal
+// --------------------------------------------------------------------------------
+//   >   Valparaiso_Guest_House 2          psk                  -4600
+//       xfinitywifi                       open                 -7100
+pub(crate) fn parse_iwctl_scan<O>(
+    _options: &O,
+    output: &str,
+    scan_type: ScanType,
+) -> Result<ParseResult, RuwiError>
+where
+    O: Global,
+{
+    let output = strip_ansi_escape_codes(output);
+    if output.lines().any(|line| line.trim() == IWCTL_NO_NETWORKS_TOKEN) {
+        return Err(err_iwctl_no_networks_seen());
+    } else if !output.contains(IWCTL_HEADER_TOKEN) {
+        return Err(err_iwctl_malformed_output());
+    }
+
+    let lines = output
+        .lines()
+        .skip_while(|line| !line.contains(IWCTL_HEADER_TOKEN))
+        .skip(1)
+        .map(str::trim)
+        .filter(|line| !is_iwctl_separator_line(line));
+
+    let mut seen_networks = vec![];
+    let mut line_parse_errors = vec![];
+    for line in lines {
+        let res = parse_iwctl_line_into_network(line);
+        match res {
+            Ok(nw) => seen_networks.push(nw),
+            Err(err) => line_parse_errors.push((line.to_string(), err)),
+        };
+    }
+
+    if seen_networks.is_empty() && line_parse_errors.is_empty() {
+        Err(err_iwctl_no_networks_seen())
+    } else {
+        Ok(ParseResult {
+            scan_type,
+            seen_networks,
+            line_parse_errors,
+        })
+    }
+}
+
+fn is_iwctl_separator_line(line: &str) -> bool {
+    line.is_empty() || line.chars().all(|c| c == '-')
+}
+
+fn parse_iwctl_line_into_network(line: &str) -> Result<WirelessNetwork, IndividualParseError> {
+    let line = line.trim_start_matches(IWCTL_CONNECTED_MARKER).trim_start();
+
+    let (rest, signal_txt) = line
+        .rsplit_once(char::is_whitespace)
+        .ok_or(IndividualParseError::MissingIwctlField)?;
+    let (essid, security) = rest
+        .trim_end()
+        .rsplit_once(char::is_whitespace)
+        .ok_or(IndividualParseError::MissingIwctlField)?;
+    let essid = essid.trim_end();
+    if essid.is_empty() {
+        return Err(IndividualParseError::MissingIwctlField);
+    }
+
+    // `rssi-dbms` gives signal strength in hundredths of a dBm.
+    let signal_strength = signal_txt
+        .parse::<i32>()
+        .map(|x| x / 100)
+        .map(SignalStrength::from_dbm)
+        .or(Err(IndividualParseError::FailedToParseSignalLevel))?;
+
+    let nw = WirelessNetwork::builder()
+        .essid(essid.to_string())
+        .is_encrypted(security != IWCTL_OPEN_SECURITY_TOKEN)
+        .signal_strength(Some(signal_strength))
+        .build();
+    Ok(nw)
+}
+
+pub(super) fn err_iwctl_malformed_output() -> RuwiError {
+    rerr!(
+        RuwiErrorKind::MalformedIwctlOutput,
+        "Malformed output returned by `iwctl station <interface> get-networks`. Try running it manually, or rerunning ruwi with -d to see the parse error."
+    )
+}
+
+pub(super) fn err_iwctl_no_networks_seen() -> RuwiError {
+    rerr!(
+        RuwiErrorKind::NoNetworksSeenWithIwctl,
+        "No networks seen by `iwctl station <interface> get-networks`. Are you near wireless networks? Try running `iwctl station <interface> scan`."
+    )
+}
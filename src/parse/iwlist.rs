@@ -0,0 +1,214 @@
+use crate::prelude::*;
+
+const IWLIST_CELL_PREFIX: &str = "Cell ";
+const IWLIST_ADDRESS_TOKEN: &str = "Address: ";
+const IWLIST_CHANNEL_PREFIX: &str = "Channel:";
+const IWLIST_ENCRYPTION_PREFIX: &str = "Encryption key:";
+const IWLIST_ESSID_PREFIX: &str = "ESSID:\"";
+const IWLIST_QUALITY_PREFIX: &str = "Quality=";
+const IWLIST_SIGNAL_LEVEL_TOKEN: &str = "Signal level=";
+
+pub(crate) fn parse_iwlist_scan<O>(
+    _options: &O,
+    interface_name: &str,
+    output: &str,
+    scan_type: ScanType,
+) -> Result<ParseResult, RuwiError>
+where
+    O: Global,
+{
+    let cell_chunks = break_iwlist_output_into_chunks_per_cell(interface_name, output)?;
+    let mut seen_networks = vec![];
+    let mut line_parse_errors = vec![];
+    for chunk in cell_chunks {
+        let res = parse_iwlist_chunk_into_network(&chunk);
+        match res {
+            Ok(nw) => seen_networks.push(nw),
+            Err(err) => line_parse_errors.push((chunk.join("\n"), err)),
+        };
+    }
+    Ok(ParseResult {
+        scan_type,
+        seen_networks,
+        line_parse_errors,
+    })
+}
+
+// The first line of `iwlist <iface> scanning` is a header, e.g. "wlp3s0    Scan completed :",
+// followed by one indented block per cell (BSS), each starting with "Cell NN - Address: ...".
+fn break_iwlist_output_into_chunks_per_cell<'a>(
+    interface_name: &str,
+    output: &'a str,
+) -> Result<Vec<Vec<&'a str>>, RuwiError> {
+    let mut lines = output.trim().lines().map(str::trim);
+
+    let header = lines
+        .next()
+        .ok_or_else(|| err_iwlist_no_networks_seen(interface_name))?;
+    if header.ends_with("No scan results") {
+        return Err(err_iwlist_no_networks_seen(interface_name));
+    } else if !header.ends_with("Scan completed :") {
+        eprintln!("[ERR]: Culprit line: \"{}\"", header);
+        return Err(err_iwlist_malformed_output(interface_name));
+    }
+
+    let mut cell_chunks: Vec<Vec<&str>> = vec![];
+    for line in lines {
+        if is_first_line_of_iwlist_cell(line) {
+            cell_chunks.push(vec![line]);
+        } else if let Some(chunk) = cell_chunks.last_mut() {
+            chunk.push(line);
+        } else {
+            eprintln!("[ERR]: Culprit line: \"{}\"", line);
+            return Err(err_iwlist_malformed_output(interface_name));
+        }
+    }
+
+    if cell_chunks.is_empty() {
+        Err(err_iwlist_no_networks_seen(interface_name))
+    } else {
+        Ok(cell_chunks)
+    }
+}
+
+fn parse_iwlist_chunk_into_network(chunk: &[&str]) -> Result<WirelessNetwork, IndividualParseError> {
+    let bssid = chunk
+        .first()
+        .ok_or(IndividualParseError::ZeroLengthIWListChunk)?
+        .split_once(IWLIST_ADDRESS_TOKEN)
+        .map(|(_, addr)| addr.trim().to_lowercase());
+
+    let essid = chunk
+        .iter()
+        .find(|line| line.starts_with(IWLIST_ESSID_PREFIX))
+        .ok_or(IndividualParseError::MissingIWListESSIDField)?
+        .trim_start_matches(IWLIST_ESSID_PREFIX)
+        .strip_suffix('"')
+        .and_then(unescape_iwlist_essid)
+        .ok_or(IndividualParseError::FailedToUnescapeSSIDField)?;
+
+    let is_encrypted = chunk
+        .iter()
+        .find(|line| line.starts_with(IWLIST_ENCRYPTION_PREFIX))
+        .ok_or(IndividualParseError::MissingIWListEncryptionKeyField)?
+        .trim_start_matches(IWLIST_ENCRYPTION_PREFIX)
+        == "on";
+
+    let signal_strength = chunk
+        .iter()
+        .find(|line| line.starts_with(IWLIST_QUALITY_PREFIX))
+        .and_then(|line| parse_iwlist_signal_strength(line));
+
+    let channel = chunk
+        .iter()
+        .find(|line| line.starts_with(IWLIST_CHANNEL_PREFIX))
+        .and_then(|line| {
+            line.trim_start_matches(IWLIST_CHANNEL_PREFIX)
+                .parse::<u32>()
+                .ok()
+        });
+
+    let nw = WirelessNetwork::builder()
+        .essid(essid)
+        .is_encrypted(is_encrypted)
+        .bssid(bssid)
+        .signal_strength(signal_strength)
+        .channel(channel)
+        .build();
+    Ok(nw)
+}
+
+// Most drivers report e.g. "Quality=45/70  Signal level=-65 dBm", in which case the dBm value
+// is used. Some only report relative values ("Signal level=60/100"), in which case the quality
+// is used as a percentage, like nmcli does.
+fn parse_iwlist_signal_strength(line: &str) -> Option<SignalStrength> {
+    let mut fields = line.split_whitespace();
+    let quality = fields.next()?.trim_start_matches(IWLIST_QUALITY_PREFIX);
+    let signal_level = line
+        .split_once(IWLIST_SIGNAL_LEVEL_TOKEN)
+        .map(|(_, level)| level.trim());
+
+    if let Some(dbm) = signal_level.and_then(|x| x.strip_suffix("dBm")) {
+        if let Ok(dbm) = dbm.trim().parse::<i32>() {
+            return Some(SignalStrength::from_dbm(dbm));
+        }
+    }
+
+    let (numerator, denominator) = quality.split_once('/')?;
+    let numerator = numerator.parse::<u32>().ok()?;
+    let denominator = denominator.parse::<u32>().ok()?;
+    if denominator == 0 {
+        None
+    } else {
+        Some(SignalStrength::from_quality(numerator * 100 / denominator))
+    }
+}
+
+// iwlist prints non-printable bytes (and double quotes) in ESSIDs as "\xNN".
+fn unescape_iwlist_essid(escaped: &str) -> Option<String> {
+    let mut bytes = vec![];
+    let mut rest = escaped.as_bytes();
+    while let Some((&first, tail)) = rest.split_first() {
+        if first == b'\\' && tail.first() == Some(&b'x') && tail.len() >= 3 {
+            let hex = std::str::from_utf8(&tail[1..3]).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[3..];
+        } else {
+            bytes.push(first);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn is_first_line_of_iwlist_cell(line: &str) -> bool {
+    line.starts_with(IWLIST_CELL_PREFIX) && line.contains(IWLIST_ADDRESS_TOKEN)
+}
+
+pub(super) fn err_iwlist_malformed_output(interface_name: &str) -> RuwiError {
+    rerr!(
+        RuwiErrorKind::MalformedIWListOutput,
+        format!(
+            "Malformed output returned by `sudo iwlist {} scanning`. Try running it manually, or rerunning ruwi with -d to see the parse error.",
+            interface_name,
+        )
+    )
+}
+
+pub(super) fn err_iwlist_no_networks_seen(interface_name: &str) -> RuwiError {
+    rerr!(
+        RuwiErrorKind::NoNetworksSeenWithIWListScan,
+        format!(
+            "No networks seen by `sudo iwlist {} scanning`. Are you near wireless networks?",
+            interface_name,
+        )
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unescape_iwlist_essid() {
+        assert_eq![
+            unescape_iwlist_essid(r#"Caf\xC3\xA9 \x22Bon\x22"#),
+            Some("Café \"Bon\"".to_string())
+        ];
+        assert_eq![unescape_iwlist_essid(r"back\slash"), Some(r"back\slash".to_string())];
+        assert_eq![unescape_iwlist_essid(r"\xFF"), None];
+    }
+
+    #[test]
+    fn test_parse_iwlist_signal_strength() {
+        assert_eq![
+            parse_iwlist_signal_strength("Quality=45/70  Signal level=-65 dBm"),
+            Some(SignalStrength::from_dbm(-65))
+        ];
+        assert_eq![
+            parse_iwlist_signal_strength("Quality=60/100  Signal level=60/100"),
+            Some(SignalStrength::from_quality(60))
+        ];
+        assert_eq![parse_iwlist_signal_strength("Quality=lol"), None];
+    }
+}
@@ -1,17 +1,53 @@
-mod nmcli;
+pub(crate) mod detect_scan_type;
+
+mod iwctl;
+use iwctl::parse_iwctl_scan;
+
+mod iwlist;
+use iwlist::parse_iwlist_scan;
+
+pub(crate) mod nmcli;
 use nmcli::parse_nmcli_scan;
 
+pub(crate) mod ruwi_json;
+use ruwi_json::parse_ruwi_json_scan;
+
 use crate::prelude::*;
 
 use unescape::unescape;
 
+// Results from several scans (e.g. multiple files given with `-F`) are merged into one
+// result set, which is reported as being of the first scan's type.
+pub(crate) fn parse_results<O>(
+    options: &O,
+    interface_name_for_errors: &str,
+    scan_results: &[ScanResult],
+) -> Result<ParseResult, RuwiError>
+where
+    O: Global + Wifi,
+{
+    let mut merged: Option<ParseResult> = None;
+    for scan_result in scan_results {
+        let parse_result = parse_result(options, interface_name_for_errors, scan_result)?;
+        merged = Some(match merged {
+            Some(mut merged) => {
+                merged.seen_networks.extend(parse_result.seen_networks);
+                merged.line_parse_errors.extend(parse_result.line_parse_errors);
+                merged
+            }
+            None => parse_result,
+        });
+    }
+    Ok(merged.unwrap_or_default())
+}
+
 pub(crate) fn parse_result<O>(
     options: &O,
     interface_name_for_errors: &str,
     scan_result: &ScanResult,
 ) -> Result<ParseResult, RuwiError>
 where
-    O: Global,
+    O: Global + Wifi,
 {
     let st = scan_result.scan_type.clone();
     let res = match &st {
@@ -27,13 +63,70 @@ where
             &scan_result.scan_output,
             st,
         ),
-        ScanType::Wifi(WifiScanType::RuwiJSON) => Err(nie("JSON support is coming soon!")),
+        ScanType::Wifi(WifiScanType::IWList) => parse_iwlist_scan(
+            options,
+            interface_name_for_errors,
+            &scan_result.scan_output,
+            st,
+        ),
+        ScanType::Wifi(WifiScanType::Iwctl) => {
+            parse_iwctl_scan(options, &scan_result.scan_output, st)
+        }
+        ScanType::Wifi(WifiScanType::RuwiJSON) => {
+            parse_ruwi_json_scan(options, &scan_result.scan_output, st)
+        }
     };
 
     if options.d() {
         dbg![&res];
+        if let Ok(parse_result) = &res {
+            print_line_parse_errors(&parse_result.line_parse_errors);
+        }
+    }
+
+    match res {
+        Ok(parse_result) if options.get_strict_parse() => {
+            fail_on_line_parse_errors(&parse_result)?;
+            Ok(parse_result)
+        }
+        _ => res,
     }
-    res
+}
+
+fn print_line_parse_errors(line_parse_errors: &[(String, IndividualParseError)]) {
+    if line_parse_errors.is_empty() {
+        return;
+    }
+    eprintln!(
+        "[NOTE]: Failed to parse {} entries in the scan output:",
+        line_parse_errors.len()
+    );
+    for (line, err) in line_parse_errors {
+        eprintln!("* {:?}: {}", err, line);
+    }
+}
+
+fn fail_on_line_parse_errors(parse_result: &ParseResult) -> Result<(), RuwiError> {
+    let line_parse_errors = &parse_result.line_parse_errors;
+    if line_parse_errors.is_empty() {
+        return Ok(());
+    }
+    let ScanType::Wifi(scan_type) = &parse_result.scan_type;
+    let mut err = rerr!(
+        RuwiErrorKind::StrictParseFailedOnScanLines,
+        format!(
+            "Failed to parse {} entries in the {} scan output, and --strict-parse was given.",
+            line_parse_errors.len(),
+            scan_type,
+        )
+    );
+    err.extra_data = Some(
+        line_parse_errors
+            .iter()
+            .map(|(line, err)| (format!("{:?}", err), line.to_string()))
+            .collect(),
+    );
+    Err(err)
 }
 
 fn parse_iw_scan<O>(
@@ -139,17 +232,105 @@ fn parse_iw_chunk_into_network(chunk: &[&str]) -> Result<WirelessNetwork, Indivi
                 .parse::<i32>()
                 .ok()
         })
-        .map(|x| x + 90);
+        .map(SignalStrength::from_dbm);
+
+    // Older versions of iw print "freq: 2412", newer ones "freq: 2412.0".
+    let frequency = chunk
+        .iter()
+        .filter_map(|line| line.strip_prefix("freq: "))
+        .find_map(|freq| freq.split('.').next()?.parse::<u32>().ok());
+
+    let channel = chunk
+        .iter()
+        .find_map(|line| {
+            line.strip_prefix("DS Parameter set: channel ")
+                .or_else(|| line.strip_prefix("* primary channel: "))
+        })
+        .and_then(|x| x.trim().parse::<u32>().ok())
+        .or_else(|| frequency.and_then(get_channel_from_frequency));
+
+    let channel_utilisation = chunk
+        .iter()
+        .find_map(|line| line.strip_prefix("* channel utilisation: "))
+        .map(|x| x.trim().to_string());
+
+    let security = get_iw_security(chunk, is_encrypted);
 
     let nw = WirelessNetwork::builder()
         .essid(essid)
         .is_encrypted(is_encrypted)
         .bssid(bssid)
         .signal_strength(signal_strength)
+        .channel_utilisation(channel_utilisation)
+        .channel(channel)
+        .frequency(frequency)
+        .security(Some(security))
         .build();
     Ok(nw)
 }
 
+// The RSN (WPA2/WPA3) and WPA sections each list the authentication suites the network
+// accepts, e.g. "* Authentication suites: PSK SAE". Networks which offer both PSK and SAE
+// (WPA3 transition mode) are treated as WPA2-PSK, since that is what every client supports.
+fn get_iw_security(chunk: &[&str], has_privacy: bool) -> WifiSecurity {
+    let mut rsn_suites = vec![];
+    let mut wpa_suites = vec![];
+    let mut in_rsn_section = false;
+    let mut in_wpa_section = false;
+    for line in chunk {
+        let line = if let Some(rest) = line.strip_prefix("RSN:") {
+            in_rsn_section = true;
+            in_wpa_section = false;
+            rest.trim()
+        } else if let Some(rest) = line.strip_prefix("WPA:") {
+            in_rsn_section = false;
+            in_wpa_section = true;
+            rest.trim()
+        } else if !line.starts_with('*') {
+            in_rsn_section = false;
+            in_wpa_section = false;
+            continue;
+        } else {
+            line
+        };
+
+        if let Some(suites) = line.strip_prefix("* Authentication suites:") {
+            let suites = suites
+                .split_whitespace()
+                .flat_map(|suite| suite.split('/'));
+            if in_rsn_section {
+                rsn_suites.extend(suites);
+            } else if in_wpa_section {
+                wpa_suites.extend(suites);
+            }
+        }
+    }
+
+    if rsn_suites.contains(&"802.1X") || wpa_suites.contains(&"802.1X") {
+        WifiSecurity::Enterprise
+    } else if rsn_suites.contains(&"PSK") {
+        WifiSecurity::Wpa2Psk
+    } else if rsn_suites.contains(&"SAE") {
+        WifiSecurity::Wpa3Sae
+    } else if !wpa_suites.is_empty() {
+        WifiSecurity::WpaPsk
+    } else if has_privacy {
+        WifiSecurity::Wep
+    } else {
+        WifiSecurity::Open
+    }
+}
+
+fn get_channel_from_frequency(freq_mhz: u32) -> Option<u32> {
+    match WifiBand::from_frequency(freq_mhz)? {
+        WifiBand::TwoPointFour if freq_mhz == 2484 => Some(14),
+        WifiBand::TwoPointFour => freq_mhz.checked_sub(2407).map(|x| x / 5),
+        WifiBand::Five => freq_mhz.checked_sub(5000).map(|x| x / 5),
+        WifiBand::Six if freq_mhz == 5935 => Some(2),
+        WifiBand::Six => freq_mhz.checked_sub(5950).map(|x| x / 5),
+    }
+}
+
 fn is_first_line_of_iw_network(line: &str) -> bool {
     line.starts_with("BSS ") && line.contains("(on ")
 }
@@ -175,7 +356,7 @@ fn err_iw_no_networks_seen(interface_name: &str) -> RuwiError {
 }
 
 fn parse_wpa_cli_scan<O>(
-    _options: &O,
+    options: &O,
     output: &str,
     scan_type: ScanType,
 ) -> Result<ParseResult, RuwiError>
@@ -198,8 +379,9 @@ where
     }
 
     if networks.is_empty() {
-        // TODO(wishlist): if individual parse errors happened, print them?
-        //                 maybe only in debug mode?
+        if options.d() {
+            print_line_parse_errors(&line_parse_errors);
+        }
         Err(err_wpa_cli_no_networks_seen())
     } else {
         Ok(ParseResult {
@@ -240,7 +422,7 @@ fn parse_wpa_line_into_network(line: &str) -> Result<WirelessNetwork, Individual
     let is_encrypted = flags.contains("WPA");
     let signal_strength = signal_level
         .parse::<i32>()
-        .map(|x| x + 90)
+        .map(SignalStrength::from_dbm)
         .or(Err(IndividualParseError::FailedToParseSignalLevel))?;
 
     let nw = WirelessNetwork::builder()
@@ -260,6 +442,8 @@ fn parse_wpa_line_into_network(line: &str) -> Result<WirelessNetwork, Individual
 mod tests {
     use super::*;
     use crate::options::wifi::connect::WifiConnectOptions;
+    use crate::options::wifi::WifiOptions;
+    use crate::options::GlobalOptions;
 
     fn compare_parsed_result_to_expected_result(
         options: &WifiConnectOptions,
@@ -301,7 +485,10 @@ mod tests {
                 .essid("Pee Pee Poo Poo Man".to_string())
                 .is_encrypted(true)
                 .bssid(Some("32:ac:a3:7b:ab:0b".to_string()))
-                .signal_strength(Some(52))
+                .signal_strength(Some(SignalStrength::from_dbm(-38)))
+                .channel(Some(1))
+                .frequency(Some(2412))
+                .security(Some(WifiSecurity::Wpa2Psk))
                 .build()],
             line_parse_errors: vec![],
         });
@@ -324,13 +511,92 @@ mod tests {
                     .essid("Valparaiso_Guest_House 1".to_string())
                     .is_encrypted(true)
                     .bssid(Some("f4:28:53:fe:a5:d0".to_string()))
-                    .signal_strength(Some(25))
+                    .signal_strength(Some(SignalStrength::from_dbm(-65)))
+                    .channel(Some(6))
+                    .frequency(Some(2437))
+                    .security(Some(WifiSecurity::Wpa2Psk))
                     .build(),
                 WirelessNetwork::builder()
                     .essid("Valparaiso_Guest_House 2".to_string())
                     .is_encrypted(true)
                     .bssid(Some("68:72:51:68:73:da".to_string()))
-                    .signal_strength(Some(44))
+                    .signal_strength(Some(SignalStrength::from_dbm(-46)))
+                    .channel(Some(10))
+                    .frequency(Some(2457))
+                    .security(Some(WifiSecurity::Wpa2Psk))
+                    .build(),
+            ],
+            line_parse_errors: vec![],
+        });
+        compare_parsed_result_to_expected_result(&options, &scan_result, &expected_parse_result);
+    }
+
+    #[test]
+    fn test_iw_six_networks_mixed_security() {
+        let wifi_scan_type = WifiScanType::IW;
+        let st = ScanType::Wifi(wifi_scan_type.clone());
+        let options = WifiConnectOptions::from_scan_type(wifi_scan_type);
+        let scan_result = ScanResult {
+            scan_type: st.clone(),
+            scan_output: include_str!("samples/iw_six_networks_mixed_security.txt").to_string(),
+        };
+        let expected_parse_result = Ok(ParseResult {
+            scan_type: st,
+            seen_networks: vec![
+                WirelessNetwork::builder()
+                    .essid("Citadel".to_string())
+                    .is_encrypted(true)
+                    .bssid(Some("10:da:43:9c:1e:60".to_string()))
+                    .signal_strength(Some(SignalStrength::from_dbm(-58)))
+                    .channel_utilisation(Some("28/255".to_string()))
+                    .channel(Some(36))
+                    .frequency(Some(5180))
+                    .security(Some(WifiSecurity::Wpa3Sae))
+                    .build(),
+                WirelessNetwork::builder()
+                    .essid("Citadel 6E".to_string())
+                    .is_encrypted(true)
+                    .bssid(Some("10:da:43:9c:1e:61".to_string()))
+                    .signal_strength(Some(SignalStrength::from_dbm(-71)))
+                    .channel(Some(5))
+                    .frequency(Some(5975))
+                    .security(Some(WifiSecurity::Wpa2Psk))
+                    .build(),
+                WirelessNetwork::builder()
+                    .essid("OldRouter".to_string())
+                    .is_encrypted(true)
+                    .bssid(Some("10:da:43:9c:1e:62".to_string()))
+                    .signal_strength(Some(SignalStrength::from_dbm(-80)))
+                    .channel(Some(6))
+                    .frequency(Some(2437))
+                    .security(Some(WifiSecurity::WpaPsk))
+                    .build(),
+                WirelessNetwork::builder()
+                    .essid("Grandmas WEP".to_string())
+                    .is_encrypted(true)
+                    .bssid(Some("10:da:43:9c:1e:63".to_string()))
+                    .signal_strength(Some(SignalStrength::from_dbm(-85)))
+                    .channel(Some(11))
+                    .frequency(Some(2462))
+                    .security(Some(WifiSecurity::Wep))
+                    .build(),
+                WirelessNetwork::builder()
+                    .essid("Campus".to_string())
+                    .is_encrypted(true)
+                    .bssid(Some("10:da:43:9c:1e:64".to_string()))
+                    .signal_strength(Some(SignalStrength::from_dbm(-62)))
+                    .channel(Some(1))
+                    .frequency(Some(2412))
+                    .security(Some(WifiSecurity::Enterprise))
+                    .build(),
+                WirelessNetwork::builder()
+                    .essid("xfinitywifi".to_string())
+                    .is_encrypted(false)
+                    .bssid(Some("10:da:43:9c:1e:65".to_string()))
+                    .signal_strength(Some(SignalStrength::from_dbm(-66)))
+                    .channel(Some(1))
+                    .frequency(Some(2412))
+                    .security(Some(WifiSecurity::Open))
                     .build(),
             ],
             line_parse_errors: vec![],
@@ -338,6 +604,16 @@ mod tests {
         compare_parsed_result_to_expected_result(&options, &scan_result, &expected_parse_result);
     }
 
+    #[test]
+    fn test_get_channel_from_frequency() {
+        assert_eq![get_channel_from_frequency(2412), Some(1)];
+        assert_eq![get_channel_from_frequency(2484), Some(14)];
+        assert_eq![get_channel_from_frequency(5180), Some(36)];
+        assert_eq![get_channel_from_frequency(5935), Some(2)];
+        assert_eq![get_channel_from_frequency(6115), Some(33)];
+        assert_eq![get_channel_from_frequency(900), None];
+    }
+
     #[test]
     fn test_wpa_cli_no_networks() {
         let wifi_scan_type = WifiScanType::WpaCli;
@@ -367,13 +643,13 @@ mod tests {
                     .essid("Valparaiso_Guest_House 1".to_string())
                     .is_encrypted(true)
                     .bssid(Some("f4:28:53:fe:a5:d0".to_string()))
-                    .signal_strength(Some(24))
+                    .signal_strength(Some(SignalStrength::from_dbm(-66)))
                     .build(),
                 WirelessNetwork::builder()
                     .essid("Valparaiso_Guest_House 2".to_string())
                     .is_encrypted(true)
                     .bssid(Some("68:72:51:68:73:da".to_string()))
-                    .signal_strength(Some(43))
+                    .signal_strength(Some(SignalStrength::from_dbm(-47)))
                     .build(),
             ],
             line_parse_errors: vec![],
@@ -400,43 +676,43 @@ mod tests {
                     .essid("Nima Lodge".to_string())
                     .is_encrypted(true)
                     .bssid(Some("78:8a:20:e3:9d:62".to_string()))
-                    .signal_strength(Some(49))
+                    .signal_strength(Some(SignalStrength::from_dbm(-41)))
                     .build(),
                 WirelessNetwork::builder()
                     .essid("DIRECT-AF-HP DeskJet 3830 series".to_string())
                     .is_encrypted(true)
                     .bssid(Some("fc:3f:db:a1:5e:b0".to_string()))
-                    .signal_strength(Some(22))
+                    .signal_strength(Some(SignalStrength::from_dbm(-68)))
                     .build(),
                 WirelessNetwork::builder()
                     .essid("Nima Lodge".to_string())
                     .is_encrypted(true)
                     .bssid(Some("fc:ec:da:69:e0:3e".to_string()))
-                    .signal_strength(Some(5))
+                    .signal_strength(Some(SignalStrength::from_dbm(-85)))
                     .build(),
                 WirelessNetwork::builder()
                     .essid("".to_string())
                     .is_encrypted(true)
                     .bssid(Some("fe:ec:da:69:e0:3e".to_string()))
-                    .signal_strength(Some(5))
+                    .signal_strength(Some(SignalStrength::from_dbm(-85)))
                     .build(),
                 WirelessNetwork::builder()
                     .essid("WISPerNET-George-Sentraal1".to_string())
                     .is_encrypted(false)
                     .bssid(Some("ba:69:f4:1f:2d:15".to_string()))
-                    .signal_strength(Some(1))
+                    .signal_strength(Some(SignalStrength::from_dbm(-89)))
                     .build(),
                 WirelessNetwork::builder()
                     .essid("WISPerNET-Bosplaas-SW-802.11".to_string())
                     .is_encrypted(false)
                     .bssid(Some("b8:69:f4:1f:2d:15".to_string()))
-                    .signal_strength(Some(1))
+                    .signal_strength(Some(SignalStrength::from_dbm(-89)))
                     .build(),
                 WirelessNetwork::builder()
                     .essid("".to_string())
                     .is_encrypted(true)
                     .bssid(Some("7a:8a:20:e3:9d:62".to_string()))
-                    .signal_strength(Some(51))
+                    .signal_strength(Some(SignalStrength::from_dbm(-39)))
                     .build(),
             ],
             line_parse_errors: vec![],
@@ -459,7 +735,7 @@ mod tests {
                 .essid("Valparaiso_Guest_House 1".to_string())
                 .is_encrypted(true)
                 .bssid(Some("f4:28:53:fe:a5:d0".to_string()))
-                .signal_strength(Some(24))
+                .signal_strength(Some(SignalStrength::from_dbm(-66)))
                 .build()],
             line_parse_errors: vec![
                 (
@@ -493,7 +769,7 @@ mod tests {
                 .essid("Valparaiso_Guest_House 1".to_string())
                 .is_encrypted(true)
                 .bssid(Some("f4:28:53:fe:a5:d0".to_string()))
-                .signal_strength(Some(24))
+                .signal_strength(Some(SignalStrength::from_dbm(-66)))
                 .build()],
             line_parse_errors: vec![(
                 "68:72:51:68:73:da\t2457\t-xx\t[WPA2-PSK-CCMP][ESS]\tValparaiso_Guest_House 2"
@@ -531,71 +807,610 @@ mod tests {
                 WirelessNetwork::builder()
                     .essid("alltheinternets".to_string())
                     .is_encrypted(true)
-                    .signal_strength(Some(95))
+                    .bssid(Some("34:12:98:b5:e1:01".to_string()))
+                    .signal_strength(Some(SignalStrength::from_quality(95)))
+                    .channel(Some(149))
+                    .frequency(Some(5745))
+                    .rate(Some(540))
+                    .security(Some(WifiSecurity::Wpa2Psk))
                     .build(),
                 WirelessNetwork::builder()
                     .essid("Patrician Pad".to_string())
                     .is_encrypted(true)
-                    .signal_strength(Some(95))
+                    .bssid(Some("70:3a:cb:12:4f:a2".to_string()))
+                    .signal_strength(Some(SignalStrength::from_quality(95)))
+                    .channel(Some(36))
+                    .frequency(Some(5180))
+                    .rate(Some(270))
+                    .security(Some(WifiSecurity::Wpa2Psk))
                     .build(),
                 WirelessNetwork::builder()
                     .essid("casa".to_string())
                     .is_encrypted(true)
-                    .signal_strength(Some(94))
+                    .bssid(Some("c0:56:27:3e:9d:10".to_string()))
+                    .signal_strength(Some(SignalStrength::from_quality(94)))
+                    .channel(Some(1))
+                    .frequency(Some(2412))
+                    .rate(Some(130))
+                    .security(Some(WifiSecurity::Wpa2Psk))
                     .build(),
                 WirelessNetwork::builder()
                     .essid("Patrician Pad".to_string())
                     .is_encrypted(true)
-                    .signal_strength(Some(94))
+                    .bssid(Some("70:3a:cb:12:4f:a1".to_string()))
+                    .signal_strength(Some(SignalStrength::from_quality(94)))
+                    .channel(Some(6))
+                    .frequency(Some(2437))
+                    .rate(Some(130))
+                    .security(Some(WifiSecurity::Wpa2Psk))
                     .build(),
                 WirelessNetwork::builder()
                     .essid("xfinitywifi".to_string())
                     .is_encrypted(false)
-                    .signal_strength(Some(90))
+                    .bssid(Some("10:da:43:9c:1e:5b".to_string()))
+                    .signal_strength(Some(SignalStrength::from_quality(90)))
+                    .channel(Some(36))
+                    .frequency(Some(5180))
+                    .rate(Some(54))
+                    .security(Some(WifiSecurity::Open))
                     .build(),
                 WirelessNetwork::builder()
                     .essid("MeshResearch".to_string())
                     .is_encrypted(true)
-                    .signal_strength(Some(52))
+                    .bssid(Some("b8:27:eb:0a:71:3c".to_string()))
+                    .signal_strength(Some(SignalStrength::from_quality(52)))
+                    .channel(Some(11))
+                    .frequency(Some(2462))
+                    .rate(Some(65))
+                    .security(Some(WifiSecurity::Wpa2Psk))
                     .build(),
                 WirelessNetwork::builder()
                     .essid("".to_string())
                     .is_encrypted(true)
-                    .signal_strength(Some(35))
+                    .bssid(Some("0e:da:43:9c:1e:5a".to_string()))
+                    .signal_strength(Some(SignalStrength::from_quality(35)))
+                    .channel(Some(11))
+                    .frequency(Some(2462))
+                    .rate(Some(54))
+                    .security(Some(WifiSecurity::Wpa2Psk))
                     .build(),
                 WirelessNetwork::builder()
                     .essid("Lots:of:colons:lol:".to_string())
                     .is_encrypted(false)
-                    .signal_strength(Some(34))
+                    .bssid(Some("9a:31:5f:00:12:7e".to_string()))
+                    .signal_strength(Some(SignalStrength::from_quality(34)))
+                    .channel(Some(1))
+                    .frequency(Some(2412))
+                    .rate(Some(54))
+                    .security(Some(WifiSecurity::Open))
                     .build(),
                 WirelessNetwork::builder()
                     .essid("".to_string())
                     .is_encrypted(true)
-                    .signal_strength(Some(32))
+                    .bssid(Some("0e:da:43:9c:1e:5c".to_string()))
+                    .signal_strength(Some(SignalStrength::from_quality(32)))
+                    .channel(Some(6))
+                    .frequency(Some(2437))
+                    .rate(Some(54))
+                    .security(Some(WifiSecurity::Wpa2Psk))
                     .build(),
                 WirelessNetwork::builder()
                     .essid("xfinitywifi".to_string())
                     .is_encrypted(false)
-                    .signal_strength(Some(32))
+                    .bssid(Some("10:da:43:9c:1e:5d".to_string()))
+                    .signal_strength(Some(SignalStrength::from_quality(32)))
+                    .channel(Some(1))
+                    .frequency(Some(2412))
+                    .rate(Some(54))
+                    .security(Some(WifiSecurity::Open))
                     .build(),
                 WirelessNetwork::builder()
                     .essid("Okonomiyaki".to_string())
                     .is_encrypted(true)
-                    .signal_strength(Some(30))
+                    .bssid(Some("a4:2b:b0:c9:77:e2".to_string()))
+                    .signal_strength(Some(SignalStrength::from_quality(30)))
+                    .channel(Some(44))
+                    .frequency(Some(5220))
+                    .rate(Some(405))
+                    .security(Some(WifiSecurity::Wpa3Sae))
                     .build(),
                 WirelessNetwork::builder()
                     .essid("XFINITY".to_string())
                     .is_encrypted(true)
-                    .signal_strength(Some(17))
+                    .bssid(Some("10:da:43:9c:1e:5e".to_string()))
+                    .signal_strength(Some(SignalStrength::from_quality(17)))
+                    .channel(Some(36))
+                    .frequency(Some(5180))
+                    .rate(Some(54))
+                    .security(Some(WifiSecurity::Enterprise))
                     .build(),
                 WirelessNetwork::builder()
                     .essid("".to_string())
                     .is_encrypted(true)
-                    .signal_strength(Some(15))
+                    .bssid(Some("0e:da:43:9c:1e:5f".to_string()))
+                    .signal_strength(Some(SignalStrength::from_quality(15)))
+                    .channel(Some(1))
+                    .frequency(Some(2412))
+                    .rate(Some(54))
+                    .security(Some(WifiSecurity::Enterprise))
                     .build(),
             ],
             line_parse_errors: vec![],
         });
         compare_parsed_result_to_expected_result(&options, &scan_result, &expected_parse_result);
     }
+
+    // The same two networks as in iw_two_different_networks.txt, as seen by nmcli.
+    #[test]
+    fn test_nmcli_two_different_networks() {
+        let st = ScanType::Wifi(WifiScanType::Nmcli);
+        let options = WifiConnectOptions::default();
+        let scan_result = ScanResult {
+            scan_type: st.clone(),
+            scan_output: include_str!("samples/nmcli_two_different_networks.txt").to_string(),
+        };
+        let expected_parse_result = Ok(ParseResult {
+            scan_type: st,
+            seen_networks: vec![
+                WirelessNetwork::builder()
+                    .essid("Valparaiso_Guest_House 1".to_string())
+                    .is_encrypted(true)
+                    .bssid(Some("f4:28:53:fe:a5:d0".to_string()))
+                    .signal_strength(Some(SignalStrength::from_quality(35)))
+                    .channel(Some(6))
+                    .frequency(Some(2437))
+                    .rate(Some(54))
+                    .security(Some(WifiSecurity::Wpa2Psk))
+                    .build(),
+                WirelessNetwork::builder()
+                    .essid("Valparaiso_Guest_House 2".to_string())
+                    .is_encrypted(true)
+                    .bssid(Some("68:72:51:68:73:da".to_string()))
+                    .signal_strength(Some(SignalStrength::from_quality(54)))
+                    .channel(Some(10))
+                    .frequency(Some(2457))
+                    .rate(Some(54))
+                    .security(Some(WifiSecurity::Wpa2Psk))
+                    .build(),
+            ],
+            line_parse_errors: vec![],
+        });
+        compare_parsed_result_to_expected_result(&options, &scan_result, &expected_parse_result);
+    }
+
+    #[test]
+    fn test_nmcli_line_missing_fields() {
+        let st = ScanType::Wifi(WifiScanType::Nmcli);
+        let options = WifiConnectOptions::default();
+        let scan_result = ScanResult {
+            scan_type: st.clone(),
+            scan_output: "WPA2:95:alltheinternets".to_string(),
+        };
+        let expected_parse_result = Ok(ParseResult {
+            scan_type: st,
+            seen_networks: vec![],
+            line_parse_errors: vec![(
+                "WPA2:95:alltheinternets".to_string(),
+                IndividualParseError::MissingNmcliSeparator,
+            )],
+        });
+        compare_parsed_result_to_expected_result(&options, &scan_result, &expected_parse_result);
+    }
+
+    #[test]
+    fn test_strict_parse_fails_on_line_parse_errors() {
+        let wifi_opts = WifiOptions::builder()
+            .globals(GlobalOptions::default())
+            .scan_type(WifiScanType::Nmcli)
+            .strict_parse(true)
+            .build();
+        let options = WifiConnectOptions::builder().wifi(wifi_opts).build();
+        let scan_result = ScanResult {
+            scan_type: ScanType::Wifi(WifiScanType::Nmcli),
+            scan_output: "WPA2:95:alltheinternets".to_string(),
+        };
+        let err = parse_result(&options, "fake_interface_name", &scan_result).unwrap_err();
+        assert_eq![err.kind, RuwiErrorKind::StrictParseFailedOnScanLines];
+        assert_eq![
+            err.extra_data,
+            Some(vec![(
+                "MissingNmcliSeparator".to_string(),
+                "WPA2:95:alltheinternets".to_string()
+            )])
+        ];
+
+        let scan_result = ScanResult {
+            scan_type: ScanType::Wifi(WifiScanType::Nmcli),
+            scan_output: include_str!("samples/nmcli_two_different_networks.txt").to_string(),
+        };
+        assert![parse_result(&options, "fake_interface_name", &scan_result).is_ok()];
+    }
+
+    #[test]
+    fn test_parse_results_merges_scans() {
+        let options = WifiConnectOptions::default();
+        let scan_results = vec![
+            ScanResult {
+                scan_type: ScanType::Wifi(WifiScanType::IW),
+                scan_output: include_str!("samples/iw_two_different_networks.txt").to_string(),
+            },
+            ScanResult {
+                scan_type: ScanType::Wifi(WifiScanType::Nmcli),
+                scan_output: "WPA2:95:alltheinternets".to_string(),
+            },
+            ScanResult {
+                scan_type: ScanType::Wifi(WifiScanType::RuwiJSON),
+                scan_output: include_str!("samples/ruwi_json_two_networks.json").to_string(),
+            },
+        ];
+        let res = parse_results(&options, "wlp3s0", &scan_results).unwrap();
+        assert_eq![res.scan_type, ScanType::Wifi(WifiScanType::IW)];
+        assert_eq![res.seen_networks.len(), 4];
+        assert_eq![res.line_parse_errors.len(), 1];
+
+        assert_eq![parse_results(&options, "wlp3s0", &[]).unwrap(), ParseResult::default()];
+    }
+
+    #[test]
+    fn test_iwlist_two_different_networks() {
+        let wifi_scan_type = WifiScanType::IWList;
+        let st = ScanType::Wifi(wifi_scan_type.clone());
+        let options = WifiConnectOptions::from_scan_type(wifi_scan_type);
+        let scan_result = ScanResult {
+            scan_type: st.clone(),
+            scan_output: include_str!("samples/iwlist_two_different_networks.txt").to_string(),
+        };
+        let expected_parse_result = Ok(ParseResult {
+            scan_type: st,
+            seen_networks: vec![
+                WirelessNetwork::builder()
+                    .essid("Valparaiso_Guest_House 1".to_string())
+                    .is_encrypted(true)
+                    .bssid(Some("f4:28:53:fe:a5:d0".to_string()))
+                    .signal_strength(Some(SignalStrength::from_dbm(-65)))
+                    .channel(Some(6))
+                    .build(),
+                WirelessNetwork::builder()
+                    .essid("Valparaiso_Guest_House 2".to_string())
+                    .is_encrypted(true)
+                    .bssid(Some("68:72:51:68:73:da".to_string()))
+                    .signal_strength(Some(SignalStrength::from_dbm(-46)))
+                    .channel(Some(10))
+                    .build(),
+            ],
+            line_parse_errors: vec![],
+        });
+        compare_parsed_result_to_expected_result(&options, &scan_result, &expected_parse_result);
+    }
+
+    #[test]
+    fn test_iwlist_four_networks_one_missing_essid() {
+        let wifi_scan_type = WifiScanType::IWList;
+        let st = ScanType::Wifi(wifi_scan_type.clone());
+        let options = WifiConnectOptions::from_scan_type(wifi_scan_type);
+        let scan_result = ScanResult {
+            scan_type: st.clone(),
+            scan_output: include_str!("samples/iwlist_four_networks_one_missing_essid.txt")
+                .to_string(),
+        };
+        let expected_parse_result = Ok(ParseResult {
+            scan_type: st,
+            seen_networks: vec![
+                WirelessNetwork::builder()
+                    .essid("xfinitywifi".to_string())
+                    .is_encrypted(false)
+                    .bssid(Some("10:da:43:9c:1e:5b".to_string()))
+                    .signal_strength(Some(SignalStrength::from_dbm(-31)))
+                    .channel(Some(36))
+                    .build(),
+                WirelessNetwork::builder()
+                    .essid("Café \"Très Bon\"".to_string())
+                    .is_encrypted(true)
+                    .bssid(Some("10:da:43:9c:1e:5d".to_string()))
+                    .signal_strength(Some(SignalStrength::from_dbm(-80)))
+                    .channel(Some(11))
+                    .build(),
+                WirelessNetwork::builder()
+                    .essid("".to_string())
+                    .is_encrypted(true)
+                    .bssid(Some("10:da:43:9c:1e:5e".to_string()))
+                    .signal_strength(Some(SignalStrength::from_dbm(-70)))
+                    .channel(Some(6))
+                    .build(),
+            ],
+            line_parse_errors: vec![(
+                vec![
+                    "Cell 02 - Address: 10:DA:43:9C:1E:5C",
+                    "Channel:1",
+                    "Frequency:2.412 GHz (Channel 1)",
+                    "Quality=24/70  Signal level=-86 dBm",
+                    "Encryption key:on",
+                    "Bit Rates:1 Mb/s; 2 Mb/s; 5.5 Mb/s; 11 Mb/s",
+                    "Mode:Master",
+                ]
+                .join("\n"),
+                IndividualParseError::MissingIWListESSIDField,
+            )],
+        });
+        compare_parsed_result_to_expected_result(&options, &scan_result, &expected_parse_result);
+    }
+
+    #[test]
+    fn test_iwlist_no_scan_results() {
+        let wifi_scan_type = WifiScanType::IWList;
+        let st = ScanType::Wifi(wifi_scan_type.clone());
+        let options = WifiConnectOptions::from_scan_type(wifi_scan_type);
+        let scan_result = ScanResult {
+            scan_type: st,
+            scan_output: include_str!("samples/iwlist_no_scan_results.txt").to_string(),
+        };
+        let expected_parse_result = Err(iwlist::err_iwlist_no_networks_seen("fake_interface_name"));
+        compare_parsed_result_to_expected_result(&options, &scan_result, &expected_parse_result);
+    }
+
+    #[test]
+    fn test_iwlist_broken_input_two_words() {
+        let wifi_scan_type = WifiScanType::IWList;
+        let st = ScanType::Wifi(wifi_scan_type.clone());
+        let options = WifiConnectOptions::from_scan_type(wifi_scan_type);
+        let scan_result = ScanResult {
+            scan_type: st,
+            scan_output: include_str!("samples/broken_input_two_words.txt").to_string(),
+        };
+        let expected_parse_result = Err(iwlist::err_iwlist_malformed_output("fake_interface_name"));
+        compare_parsed_result_to_expected_result(&options, &scan_result, &expected_parse_result);
+    }
+
+    #[test]
+    fn test_iwctl_two_different_networks() {
+        let wifi_scan_type = WifiScanType::Iwctl;
+        let st = ScanType::Wifi(wifi_scan_type.clone());
+        let options = WifiConnectOptions::from_scan_type(wifi_scan_type);
+        let scan_result = ScanResult {
+            scan_type: st.clone(),
+            scan_output: include_str!("samples/iwctl_two_different_networks.txt").to_string(),
+        };
+        let expected_parse_result = Ok(ParseResult {
+            scan_type: st,
+            seen_networks: vec![
+                WirelessNetwork::builder()
+                    .essid("Valparaiso_Guest_House 1".to_string())
+                    .is_encrypted(true)
+                    .signal_strength(Some(SignalStrength::from_dbm(-65)))
+                    .build(),
+                WirelessNetwork::builder()
+                    .essid("Valparaiso_Guest_House 2".to_string())
+                    .is_encrypted(true)
+                    .signal_strength(Some(SignalStrength::from_dbm(-46)))
+                    .build(),
+            ],
+            line_parse_errors: vec![],
+        });
+        compare_parsed_result_to_expected_result(&options, &scan_result, &expected_parse_result);
+    }
+
+    #[test]
+    fn test_iwctl_four_networks_one_broken() {
+        let wifi_scan_type = WifiScanType::Iwctl;
+        let st = ScanType::Wifi(wifi_scan_type.clone());
+        let options = WifiConnectOptions::from_scan_type(wifi_scan_type);
+        let scan_result = ScanResult {
+            scan_type: st.clone(),
+            scan_output: include_str!("samples/iwctl_four_networks_one_broken.txt").to_string(),
+        };
+        let expected_parse_result = Ok(ParseResult {
+            scan_type: st,
+            seen_networks: vec![
+                WirelessNetwork::builder()
+                    .essid("Valparaiso_Guest_House 2".to_string())
+                    .is_encrypted(true)
+                    .signal_strength(Some(SignalStrength::from_dbm(-46)))
+                    .build(),
+                WirelessNetwork::builder()
+                    .essid("Valparaiso_Guest_House 1".to_string())
+                    .is_encrypted(true)
+                    .signal_strength(Some(SignalStrength::from_dbm(-65)))
+                    .build(),
+                WirelessNetwork::builder()
+                    .essid("xfinitywifi".to_string())
+                    .is_encrypted(false)
+                    .signal_strength(Some(SignalStrength::from_dbm(-71)))
+                    .build(),
+                WirelessNetwork::builder()
+                    .essid("Corp Net".to_string())
+                    .is_encrypted(true)
+                    .signal_strength(Some(SignalStrength::from_dbm(-83)))
+                    .build(),
+            ],
+            line_parse_errors: vec![(
+                "Broken Line".to_string(),
+                IndividualParseError::MissingIwctlField,
+            )],
+        });
+        compare_parsed_result_to_expected_result(&options, &scan_result, &expected_parse_result);
+    }
+
+    #[test]
+    fn test_iwctl_strong_signals() {
+        let wifi_scan_type = WifiScanType::Iwctl;
+        let st = ScanType::Wifi(wifi_scan_type.clone());
+        let options = WifiConnectOptions::from_scan_type(wifi_scan_type);
+        let scan_result = ScanResult {
+            scan_type: st.clone(),
+            scan_output: include_str!("samples/iwctl_strong_signals.txt").to_string(),
+        };
+        let expected_parse_result = Ok(ParseResult {
+            scan_type: st,
+            seen_networks: vec![
+                WirelessNetwork::builder()
+                    .essid("Upstairs".to_string())
+                    .is_encrypted(true)
+                    .signal_strength(Some(SignalStrength::from_dbm(-9)))
+                    .build(),
+                WirelessNetwork::builder()
+                    .essid("Downstairs".to_string())
+                    .is_encrypted(true)
+                    .signal_strength(Some(SignalStrength::from_dbm(-30)))
+                    .build(),
+            ],
+            line_parse_errors: vec![],
+        });
+        compare_parsed_result_to_expected_result(&options, &scan_result, &expected_parse_result);
+    }
+
+    #[test]
+    fn test_iwctl_no_networks() {
+        let wifi_scan_type = WifiScanType::Iwctl;
+        let st = ScanType::Wifi(wifi_scan_type.clone());
+        let options = WifiConnectOptions::from_scan_type(wifi_scan_type);
+        let scan_result = ScanResult {
+            scan_type: st,
+            scan_output: include_str!("samples/iwctl_no_networks.txt").to_string(),
+        };
+        let expected_parse_result = Err(iwctl::err_iwctl_no_networks_seen());
+        compare_parsed_result_to_expected_result(&options, &scan_result, &expected_parse_result);
+    }
+
+    #[test]
+    fn test_iwctl_broken_input_two_words() {
+        let wifi_scan_type = WifiScanType::Iwctl;
+        let st = ScanType::Wifi(wifi_scan_type.clone());
+        let options = WifiConnectOptions::from_scan_type(wifi_scan_type);
+        let scan_result = ScanResult {
+            scan_type: st,
+            scan_output: include_str!("samples/broken_input_two_words.txt").to_string(),
+        };
+        let expected_parse_result = Err(iwctl::err_iwctl_malformed_output());
+        compare_parsed_result_to_expected_result(&options, &scan_result, &expected_parse_result);
+    }
+
+    #[test]
+    fn test_ruwi_json_two_networks() {
+        let st = ScanType::Wifi(WifiScanType::RuwiJSON);
+        let options = WifiConnectOptions::from_scan_type(WifiScanType::RuwiJSON);
+        let scan_result = ScanResult {
+            scan_type: st.clone(),
+            scan_output: include_str!("samples/ruwi_json_two_networks.json").to_string(),
+        };
+        let expected_parse_result = Ok(ParseResult {
+            scan_type: st,
+            seen_networks: vec![
+                WirelessNetwork::builder()
+                    .essid("Valparaiso_Guest_House 1".to_string())
+                    .is_encrypted(true)
+                    .bssid(Some("f4:28:53:fe:a5:d0".to_string()))
+                    .signal_strength(Some(SignalStrength::from_dbm(-66)))
+                    .channel(Some(6))
+                    .security(Some(WifiSecurity::Wpa2Psk))
+                    .build(),
+                WirelessNetwork::builder()
+                    .essid("xfinitywifi".to_string())
+                    .is_encrypted(false)
+                    .security(Some(WifiSecurity::Open))
+                    .build(),
+            ],
+            line_parse_errors: vec![],
+        });
+        compare_parsed_result_to_expected_result(&options, &scan_result, &expected_parse_result);
+    }
+
+    #[test]
+    fn test_ruwi_json_security_types() {
+        let st = ScanType::Wifi(WifiScanType::RuwiJSON);
+        let options = WifiConnectOptions::from_scan_type(WifiScanType::RuwiJSON);
+        let scan_result = ScanResult {
+            scan_type: st.clone(),
+            scan_output: include_str!("samples/ruwi_json_security_types.json").to_string(),
+        };
+        let expected_parse_result = Ok(ParseResult {
+            scan_type: st,
+            seen_networks: vec![
+                WirelessNetwork::builder()
+                    .essid("Citadel".to_string())
+                    .is_encrypted(true)
+                    .signal_strength(Some(SignalStrength::from_dbm(-52)))
+                    .security(Some(WifiSecurity::Wpa3Sae))
+                    .build(),
+                WirelessNetwork::builder()
+                    .essid("Old Router".to_string())
+                    .is_encrypted(true)
+                    .security(Some(WifiSecurity::Wep))
+                    .build(),
+                WirelessNetwork::builder()
+                    .essid("Corp".to_string())
+                    .is_encrypted(true)
+                    .security(Some(WifiSecurity::Enterprise))
+                    .build(),
+                WirelessNetwork::builder()
+                    .essid("Legacy".to_string())
+                    .is_encrypted(true)
+                    .security(Some(WifiSecurity::WpaPsk))
+                    .build(),
+                WirelessNetwork::builder()
+                    .essid("Library".to_string())
+                    .is_encrypted(false)
+                    .security(Some(WifiSecurity::Open))
+                    .build(),
+            ],
+            line_parse_errors: vec![],
+        });
+        compare_parsed_result_to_expected_result(&options, &scan_result, &expected_parse_result);
+    }
+
+    #[test]
+    fn test_ruwi_json_three_networks_two_malformed() {
+        let st = ScanType::Wifi(WifiScanType::RuwiJSON);
+        let options = WifiConnectOptions::from_scan_type(WifiScanType::RuwiJSON);
+        let scan_result = ScanResult {
+            scan_type: st.clone(),
+            scan_output: include_str!("samples/ruwi_json_three_networks_two_malformed.json")
+                .to_string(),
+        };
+        let expected_parse_result = Ok(ParseResult {
+            scan_type: st,
+            seen_networks: vec![WirelessNetwork::builder()
+                .essid("Valparaiso_Guest_House 2".to_string())
+                .is_encrypted(true)
+                .bssid(Some("68:72:51:68:73:da".to_string()))
+                .signal_strength(Some(SignalStrength::from_dbm(-33)))
+                .channel(Some(10))
+                .security(Some(WifiSecurity::WpaPsk))
+                .build()],
+            line_parse_errors: vec![
+                (
+                    r#"{"bssid":"68:72:51:68:73:db","signal":-40}"#.to_string(),
+                    IndividualParseError::MalformedRuwiJSONNetwork,
+                ),
+                (
+                    r#"{"encryption":"rot13","essid":"Pizzeria"}"#.to_string(),
+                    IndividualParseError::MalformedRuwiJSONNetwork,
+                ),
+            ],
+        });
+        compare_parsed_result_to_expected_result(&options, &scan_result, &expected_parse_result);
+    }
+
+    #[test]
+    fn test_ruwi_json_unsupported_version() {
+        let st = ScanType::Wifi(WifiScanType::RuwiJSON);
+        let options = WifiConnectOptions::from_scan_type(WifiScanType::RuwiJSON);
+        let scan_result = ScanResult {
+            scan_type: st,
+            scan_output: include_str!("samples/ruwi_json_unsupported_version.json").to_string(),
+        };
+        let expected_parse_result = Err(ruwi_json::err_unsupported_ruwi_json_version(9000));
+        compare_parsed_result_to_expected_result(&options, &scan_result, &expected_parse_result);
+    }
+
+    #[test]
+    fn test_ruwi_json_not_json() {
+        let st = ScanType::Wifi(WifiScanType::RuwiJSON);
+        let options = WifiConnectOptions::from_scan_type(WifiScanType::RuwiJSON);
+        let scan_result = ScanResult {
+            scan_type: st,
+            scan_output: include_str!("samples/broken_input_two_words.txt").to_string(),
+        };
+        let res = parse_result(&options, "fake_interface_name", &scan_result);
+        assert_eq![res.err().unwrap().kind, RuwiErrorKind::MalformedRuwiJSON];
+    }
 }
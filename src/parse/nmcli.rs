@@ -1,5 +1,10 @@
 use crate::prelude::*;
 
+// The fields requested from `nmcli -g <fields> device wifi list`, in order. SSID is kept last
+// so that it's obvious where a line was cut off.
+pub(crate) const NMCLI_SCAN_FIELDS: &str = "SECURITY,SIGNAL,BSSID,CHAN,FREQ,RATE,SSID";
+const NMCLI_NUM_SCAN_FIELDS: usize = 7;
+
 pub(crate) fn parse_nmcli_scan<O>(
     _options: &O,
     output: &str,
@@ -25,25 +30,105 @@ where
 }
 
 fn get_network_from_nmcli_line(line: &str) -> Result<WirelessNetwork, IndividualParseError> {
-    let mut tokens = line.splitn(3, ':');
-
-    let enc_txt = tokens
-        .next()
-        .ok_or(IndividualParseError::MissingNmcliSeparator)?;
-    let signal_strength_txt = tokens
-        .next()
-        .ok_or(IndividualParseError::MissingNmcliSeparator)?;
-    let essid_txt = tokens
-        .next()
-        .ok_or(IndividualParseError::MissingNmcliSeparator)?;
-
-    let is_encrypted = !enc_txt.is_empty();
-    let signal_strength = signal_strength_txt.parse::<i32>().ok();
+    let fields = split_nmcli_escaped_line(line);
+    if fields.len() != NMCLI_NUM_SCAN_FIELDS {
+        return Err(IndividualParseError::MissingNmcliSeparator);
+    }
+
+    let (enc_txt, signal_strength_txt, bssid_txt, chan_txt, freq_txt, rate_txt, essid_txt) = (
+        &fields[0], &fields[1], &fields[2], &fields[3], &fields[4], &fields[5], &fields[6],
+    );
+
+    let is_encrypted = !enc_txt.is_empty() && enc_txt != "--";
+    let security = get_security_from_nmcli_text(enc_txt);
+    let signal_strength = signal_strength_txt
+        .parse::<u32>()
+        .ok()
+        .map(SignalStrength::from_quality);
+    let bssid = Some(bssid_txt.to_lowercase()).filter(|x| !x.is_empty());
+    let channel = chan_txt.parse::<u32>().ok();
+    let frequency = parse_nmcli_number_with_unit(freq_txt);
+    let rate = parse_nmcli_number_with_unit(rate_txt);
     let essid = essid_txt.to_string();
 
     Ok(WirelessNetwork::builder()
         .essid(essid)
         .is_encrypted(is_encrypted)
+        .bssid(bssid)
         .signal_strength(signal_strength)
-    .build())
+        .channel(channel)
+        .frequency(frequency)
+        .rate(rate)
+        .security(security)
+        .build())
+}
+
+// With escaping enabled (the default for -g), nmcli escapes colons in field values as "\:"
+// and backslashes as "\\", so BSSIDs and SSIDs containing colons can be split unambiguously.
+pub(super) fn split_nmcli_escaped_line(line: &str) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => field.extend(chars.next()),
+            ':' => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+// e.g. "2437 MHz" or "130 Mbit/s"
+fn parse_nmcli_number_with_unit(text: &str) -> Option<u32> {
+    text.split_whitespace().next()?.parse::<u32>().ok()
+}
+
+// nmcli describes security as a list of flags, e.g. "WPA1 WPA2", "WPA2 802.1X", or
+// "WPA2 WPA3" for WPA3 transition mode. These are resolved the same way as for iw.
+fn get_security_from_nmcli_text(text: &str) -> Option<WifiSecurity> {
+    let flags = text.split_whitespace().collect::<Vec<_>>();
+    if flags.is_empty() || flags == ["--"] {
+        Some(WifiSecurity::Open)
+    } else if flags.contains(&"802.1X") {
+        Some(WifiSecurity::Enterprise)
+    } else if flags.contains(&"WPA2") {
+        Some(WifiSecurity::Wpa2Psk)
+    } else if flags.contains(&"WPA3") {
+        Some(WifiSecurity::Wpa3Sae)
+    } else if flags.contains(&"WPA1") {
+        Some(WifiSecurity::WpaPsk)
+    } else if flags.contains(&"WEP") {
+        Some(WifiSecurity::Wep)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_nmcli_escaped_line() {
+        assert_eq![
+            split_nmcli_escaped_line(r"WPA2:44:68\:72\:51\:68\:73\:DA:10:2457 MHz:54 Mbit/s:back\\slash\:colon"),
+            vec!["WPA2", "44", "68:72:51:68:73:DA", "10", "2457 MHz", "54 Mbit/s", r"back\slash:colon"]
+        ];
+        assert_eq![split_nmcli_escaped_line(""), vec![""]];
+    }
+
+    #[test]
+    fn test_get_security_from_nmcli_text() {
+        assert_eq![get_security_from_nmcli_text(""), Some(WifiSecurity::Open)];
+        assert_eq![get_security_from_nmcli_text("--"), Some(WifiSecurity::Open)];
+        assert_eq![get_security_from_nmcli_text("WEP"), Some(WifiSecurity::Wep)];
+        assert_eq![get_security_from_nmcli_text("WPA1"), Some(WifiSecurity::WpaPsk)];
+        assert_eq![get_security_from_nmcli_text("WPA1 WPA2"), Some(WifiSecurity::Wpa2Psk)];
+        assert_eq![get_security_from_nmcli_text("WPA2 WPA3"), Some(WifiSecurity::Wpa2Psk)];
+        assert_eq![get_security_from_nmcli_text("WPA3"), Some(WifiSecurity::Wpa3Sae)];
+        assert_eq![get_security_from_nmcli_text("WPA1 WPA2 802.1X"), Some(WifiSecurity::Enterprise)];
+        assert_eq![get_security_from_nmcli_text("OWE"), None];
+    }
 }
@@ -0,0 +1,143 @@
+//! `RuwiJSON` is ruwi's own scan result format, intended for feeding scan results from other tools
+//! (or from another machine) into ruwi with `-F <file>` or `-I`, using `-s ruwi_json`.
+//!
+//! The top level is an object with a schema version and a list of networks:
+//!
+//! ```json
+//! {
+//!     "version": 1,
+//!     "networks": [
+//!         {
+//!             "essid": "Valparaiso_Guest_House 1",
+//!             "bssid": "f4:28:53:fe:a5:d0",
+//!             "signal": -66,
+//!             "encryption": "wpa2",
+//!             "channel": 6
+//!         },
+//!         {
+//!             "essid": "xfinitywifi"
+//!         }
+//!     ]
+//! }
+//! ```
+//!
+//! Version 1 network fields:
+//! * `essid` (string, required)
+//! * `bssid` (string, optional)
+//! * `signal` (integer, optional): signal level in dBm, as reported by `iw`/`wpa_cli`
+//! * `encryption` (string, optional, default "none"): one of "none", "wep", "wpa", "wpa2", "wpa3",
+//!   "802.1x". "wpa" and "wpa2" are taken to be WPA-PSK and WPA2-PSK, and "wpa3" WPA3-SAE.
+//! * `channel` (integer, optional)
+//!
+//! Unknown fields are rejected. A network entry which fails to deserialize is reported as a
+//! parse error for that entry and skipped, while the rest of the networks are still used.
+
+use crate::prelude::*;
+
+use serde::{Deserialize, Deserializer};
+use serde_derive::Deserialize;
+
+pub(crate) const RUWI_JSON_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Deserialize)]
+struct RuwiJSONScanResults {
+    version: u32,
+    networks: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuwiJSONNetwork {
+    essid: String,
+    #[serde(default)]
+    bssid: Option<String>,
+    #[serde(default)]
+    signal: Option<i32>,
+    #[serde(default = "get_default_encryption", deserialize_with = "deserialize_encryption")]
+    encryption: WifiSecurity,
+    #[serde(default)]
+    channel: Option<u32>,
+}
+
+impl From<RuwiJSONNetwork> for WirelessNetwork {
+    fn from(nw: RuwiJSONNetwork) -> Self {
+        WirelessNetwork::builder()
+            .essid(nw.essid)
+            .is_encrypted(nw.encryption != WifiSecurity::Open)
+            .bssid(nw.bssid)
+            .signal_strength(nw.signal.map(SignalStrength::from_dbm))
+            .channel(nw.channel)
+            .security(Some(nw.encryption))
+            .build()
+    }
+}
+
+pub(crate) fn parse_ruwi_json_scan<O>(
+    _options: &O,
+    output: &str,
+    scan_type: ScanType,
+) -> Result<ParseResult, RuwiError>
+where
+    O: Global,
+{
+    let scan_results: RuwiJSONScanResults = serde_json::from_str(output).map_err(|e| {
+        rerr!(
+            RuwiErrorKind::MalformedRuwiJSON,
+            format!("Failed to parse RuwiJSON scan results: {}", e)
+        )
+    })?;
+
+    if scan_results.version != RUWI_JSON_SCHEMA_VERSION {
+        return Err(err_unsupported_ruwi_json_version(scan_results.version));
+    }
+
+    let mut seen_networks = vec![];
+    let mut line_parse_errors = vec![];
+    for entry in scan_results.networks {
+        let entry_text = entry.to_string();
+        match serde_json::from_value::<RuwiJSONNetwork>(entry) {
+            Ok(nw) => seen_networks.push(WirelessNetwork::from(nw)),
+            Err(_) => line_parse_errors
+                .push((entry_text, IndividualParseError::MalformedRuwiJSONNetwork)),
+        };
+    }
+
+    Ok(ParseResult {
+        scan_type,
+        seen_networks,
+        line_parse_errors,
+    })
+}
+
+pub(super) fn err_unsupported_ruwi_json_version(version: u32) -> RuwiError {
+    rerr!(
+        RuwiErrorKind::UnsupportedRuwiJSONVersion,
+        format!(
+            "Unsupported RuwiJSON schema version {}. This version of ruwi understands version {}.",
+            version, RUWI_JSON_SCHEMA_VERSION,
+        )
+    )
+}
+
+fn get_default_encryption() -> WifiSecurity {
+    WifiSecurity::Open
+}
+
+fn deserialize_encryption<'de, D>(deserializer: D) -> Result<WifiSecurity, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let encryption = String::deserialize(deserializer)?.to_lowercase();
+    match encryption.as_ref() {
+        "none" => Ok(WifiSecurity::Open),
+        "wep" => Ok(WifiSecurity::Wep),
+        "wpa" => Ok(WifiSecurity::WpaPsk),
+        "wpa2" => Ok(WifiSecurity::Wpa2Psk),
+        "wpa3" => Ok(WifiSecurity::Wpa3Sae),
+        "802.1x" => Ok(WifiSecurity::Enterprise),
+        _ => Err(serde::de::Error::custom(format!(
+            "unknown encryption type: \"{}\"",
+            encryption
+        ))),
+    }
+}
@@ -2,12 +2,17 @@ use super::utils::{get_prompt_command, spawn_and_await_prompt_command};
 
 use crate::prelude::*;
 
+use std::io;
+use std::time::Duration;
+
 pub(crate) struct PromptCommandRunner<'a, O: PreParseGlobal> {
     opts: &'a O,
     cmd_name: &'a str,
     args: &'a [&'a str],
     // TODO: change to &str
     elements: &'a [String],
+    timeout: Option<Duration>,
+    sensitive_output: bool,
     //#[cfg(test)]
     //expected_output: Result<String, RuwiError>,
 }
@@ -24,6 +29,22 @@ impl<'a, O: PreParseGlobal> PromptCommandRunner<'a, O> {
             cmd_name,
             args,
             elements,
+            timeout: None,
+            sensitive_output: false,
+        }
+    }
+
+    // If the command is still running after `timeout`, it's killed, and a SelectionTimedOut
+    // error is returned.
+    pub(crate) fn with_timeout(self, timeout: Option<Duration>) -> Self {
+        Self { timeout, ..self }
+    }
+
+    // For password prompts: the output is never printed, even with debugging turned on.
+    pub(crate) fn with_sensitive_output(self) -> Self {
+        Self {
+            sensitive_output: true,
+            ..self
         }
     }
 
@@ -33,10 +54,15 @@ impl<'a, O: PreParseGlobal> PromptCommandRunner<'a, O> {
         }
 
         let mut cmd = get_prompt_command(self.opts, self.cmd_name, self.args)?;
-        let prompt_res = spawn_and_await_prompt_command(self.opts, &mut cmd, self.elements);
+        let prompt_res =
+            spawn_and_await_prompt_command(self.opts, &mut cmd, self.elements, self.timeout);
 
         if self.opts.d() {
-            dbg!(&prompt_res);
+            if self.sensitive_output {
+                dbg!(&prompt_res.as_ref().map(|output| output.status));
+            } else {
+                dbg!(&prompt_res);
+            }
         }
 
         match prompt_res {
@@ -52,6 +78,10 @@ impl<'a, O: PreParseGlobal> PromptCommandRunner<'a, O> {
                     ))
                 }
             }
+            Err(err) if err.kind() == io::ErrorKind::TimedOut => Err(rerr!(
+                RuwiErrorKind::SelectionTimedOut,
+                format!("`{}` was closed after {}", self.cmd_name, err)
+            )),
             Err(err) => Err(rerr!(
                 RuwiErrorKind::PromptCommandSpawnFailed,
                 format!("{}", err)
@@ -3,6 +3,7 @@ use crate::prelude::*;
 use std::io;
 use std::process::Output;
 use std::process::{Command, Stdio};
+use std::time::Duration;
 
 #[cfg(not(test))]
 use std::fs::Metadata;
@@ -17,6 +18,26 @@ use nix::unistd::getuid;
 use std::io::Write;
 #[cfg(not(test))]
 use std::os::unix::fs::PermissionsExt;
+#[cfg(not(test))]
+use std::io::Read;
+#[cfg(not(test))]
+use std::process::Child;
+#[cfg(not(test))]
+use std::thread;
+#[cfg(not(test))]
+use std::time::Instant;
+
+#[cfg(not(test))]
+use nix::sys::signal::{kill, Signal};
+#[cfg(not(test))]
+use nix::unistd::Pid;
+#[cfg(not(test))]
+use std::convert::TryFrom;
+
+#[cfg(not(test))]
+const TIMEOUT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+#[cfg(not(test))]
+const TERMINATE_GRACE_PERIOD: Duration = Duration::from_secs(1);
 
 // TODO: combine codepaths for prompt and piped, since the two paths can mask functions being unused
 
@@ -179,13 +200,14 @@ pub(super) fn spawn_and_await_prompt_command<O>(
     opts: &O,
     cmd: &mut Command,
     elements: &[String],
+    timeout: Option<Duration>,
 ) -> io::Result<Output>
 where
     O: PreParseGlobal,
 {
     #[cfg(test)]
     {
-        dbg!(&cmd, &elements);
+        dbg!(&cmd, &elements, &timeout);
         let _ = opts.d();
         panic!("Prevented prompt command usage in test!");
     }
@@ -207,7 +229,10 @@ where
 
         stdin.write_all(elements.join("\n").as_bytes())?;
 
-        let output = child.wait_with_output()?;
+        let output = match timeout {
+            Some(timeout) => wait_with_output_or_kill(child, timeout)?,
+            None => child.wait_with_output()?,
+        };
 
         if opts.d() {
             dbg!(&output);
@@ -217,6 +242,61 @@ where
     }
 }
 
+// stdout is read on another thread so that a full pipe can't keep the child from exiting. On
+// timeout, the child is asked to exit first, so that e.g. fzf can restore the terminal.
+#[cfg(not(test))]
+fn wait_with_output_or_kill(mut child: Child, timeout: Duration) -> io::Result<Output> {
+    drop(child.stdin.take());
+    let mut stdout = child.stdout.take();
+    let read_stdout = thread::spawn(move || {
+        let mut buf = vec![];
+        if let Some(stdout) = stdout.as_mut() {
+            stdout.read_to_end(&mut buf)?;
+        }
+        Ok::<_, io::Error>(buf)
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            terminate_child(&mut child)?;
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("no selection was made within {} seconds.", timeout.as_secs()),
+            ));
+        }
+        thread::sleep(TIMEOUT_POLL_INTERVAL);
+    };
+
+    let stdout = read_stdout
+        .join()
+        .map_err(|_| io::Error::new(io::ErrorKind::Other, "Failed to read from stdout."))??;
+    Ok(Output {
+        status,
+        stdout,
+        stderr: vec![],
+    })
+}
+
+#[cfg(not(test))]
+fn terminate_child(child: &mut Child) -> io::Result<()> {
+    let pid = Pid::from_raw(i32::try_from(child.id()).unwrap_or(i32::MAX));
+    if kill(pid, Signal::SIGTERM).is_ok() {
+        let grace_deadline = Instant::now() + TERMINATE_GRACE_PERIOD;
+        while Instant::now() < grace_deadline {
+            if child.try_wait()?.is_some() {
+                return Ok(());
+            }
+            thread::sleep(TIMEOUT_POLL_INTERVAL);
+        }
+    }
+    child.kill()?;
+    child.wait().map(|_| ())
+}
+
 pub(super) fn verify_command_safety<O>(
     opts: &O,
     cmd_path: &FullCommandPath,
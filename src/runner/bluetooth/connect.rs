@@ -5,11 +5,10 @@ use crate::bluetooth::{TODOBluetoothCtlController, BluetoothService};
 use crate::bluetooth::utils::get_first_matching_device;
 use crate::bluetooth::BluetoothDevice;
 use crate::options::bluetooth::connect::BluetoothConnectOptions;
-use crate::synchronous_retry_logic::{
-    manual_refresh_requested, should_auto_retry_with_synchronous_scan,
-};
+use crate::synchronous_retry_logic::should_auto_retry_with_synchronous_scan;
 use crate::utils::loop_check;
 
+use crate::select::selection_outcome::SelectionOutcome;
 use crate::select::Selector;
 
 const LOOP_MAX: u16 = 1000;
@@ -131,14 +130,12 @@ where
             }
         };
 
-        let select_result = devs.select_network(opts);
-
-        if manual_refresh_requested(&select_result) {
-            synchronous_retry = Some(SynchronousRescanType::ManuallyRequested);
-            continue;
+        // Refresh is the only additional option offered when selecting a device.
+        if let SelectionOutcome::Selected(dev, _) = devs.select_network(opts)? {
+            return Ok(dev);
         }
-
-        return select_result;
+        eprintln!("[NOTE]: Refresh requested, running synchronous scan.");
+        synchronous_retry = Some(SynchronousRescanType::ManuallyRequested);
     }
 }
 
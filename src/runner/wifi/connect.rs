@@ -1,10 +1,11 @@
 use super::utils::{
-    gather_wifi_network_data, get_network_from_given_essid, scan_and_select_network,
+    gather_wifi_network_data, get_network_from_given_essid, scan_and_match_network,
+    scan_and_select_network,
 };
 
 use crate::configure_network::possibly_configure_network;
 use crate::connect::wifi_connect::connect_to_network;
-use crate::encryption_key::possibly_get_encryption_key;
+use crate::encryption_key::get_encryption_key_for_key_action;
 use crate::interface_management::ip_interfaces::{LinuxIPInterface, WifiIPInterface};
 use crate::known_networks::WifiKnownNetworks;
 use crate::options::wifi::connect::WifiConnectOptions;
@@ -13,17 +14,28 @@ use crate::runner::Runner;
 
 impl Runner for WifiConnectOptions {
     fn run(&self) -> Result<(), RuwiError> {
-        let interface = WifiIPInterface::from_name_or_first(self, self.get_given_interface_name())?;
+        let mut interface = WifiIPInterface::from_name_or_first(self, self.get_given_interface_name())?;
 
-        let selected_network = if let Some(essid) = self.get_given_essid() {
-            get_network_from_given_essid(self, &essid)
+        let (selected_network, key_action) = if let Some(essid) = self.get_given_essid() {
+            get_network_from_given_essid(self, &essid, self.get_given_essid_is_hidden())
+                .map(|nw| (nw, None))
+        } else if let Some(pattern) = self.get_given_essid_pattern() {
+            scan_and_match_network(self, &interface, pattern).map(|nw| (nw, None))
         } else {
-            scan_and_select_network(self, &interface)
+            scan_and_select_network(self, &mut interface)
         }?;
 
-        let maybe_key = possibly_get_encryption_key(self, &selected_network)?;
-        possibly_configure_network(self, &interface, &selected_network, &maybe_key)?;
-        connect_to_network(self, &interface, &selected_network, &maybe_key)?;
+        let maybe_key =
+            get_encryption_key_for_key_action(self, &selected_network, key_action.as_ref())?;
+        let configured_netctl_identifier =
+            possibly_configure_network(self, &interface, &selected_network, &maybe_key)?;
+        connect_to_network(
+            self,
+            &interface,
+            &selected_network,
+            &maybe_key,
+            configured_netctl_identifier.as_ref(),
+        )?;
         Ok(())
     }
 }
@@ -33,7 +45,7 @@ impl WifiDataGatherer for WifiConnectOptions {
         &self,
         interface: &WifiIPInterface,
         synchronous_rescan: &Option<SynchronousRescanType>,
-    ) -> Result<(WifiKnownNetworks, ScanResult), RuwiError> {
+    ) -> Result<(WifiKnownNetworks, Vec<ScanResult>), RuwiError> {
         gather_wifi_network_data(self, interface, synchronous_rescan)
     }
 }
@@ -0,0 +1,149 @@
+use crate::forget_network::{
+    confirm_and_forget_network, forget_network, select_known_network_to_forget,
+};
+use crate::known_networks::WifiKnownNetworks;
+use crate::netctl::NetctlIdentifier;
+use crate::options::wifi::forget::WifiForgetOptions;
+use crate::prelude::*;
+use crate::runner::Runner;
+use crate::utils::loop_check;
+
+const LOOP_MAX: u16 = 1000;
+
+impl Runner for WifiForgetOptions {
+    fn run(&self) -> Result<(), RuwiError> {
+        let network = match self.get_given_essid() {
+            Some(essid) => get_given_known_network(self, essid)?,
+            None => select_known_network(self)?,
+        };
+        if self.get_skip_confirmation() {
+            forget_network(self, &network)
+        } else {
+            confirm_and_forget_network(self, &network)
+        }
+    }
+}
+
+fn get_given_known_network(
+    options: &WifiForgetOptions,
+    essid: &str,
+) -> Result<AnnotatedWirelessNetwork, RuwiError> {
+    // Known networks aren't looked up in dry-run mode, so show what would be done if the network
+    // was known under the name ruwi would have given it.
+    if options.get_dry_run() {
+        return Ok(get_assumed_known_network(options.get_connect_via(), essid));
+    }
+
+    WifiKnownNetworks::find_known_networks_from_system(options)?
+        .get_known_networks()
+        .into_iter()
+        .find(|nw| nw.get_public_name() == essid)
+        .ok_or_else(|| {
+            rerr!(
+                RuwiErrorKind::GivenNetworkIsNotKnown,
+                format!(
+                    "\"{}\" is not a known network for {}, so there is nothing to forget.",
+                    essid,
+                    options.get_connect_via()
+                )
+            )
+        })
+}
+
+fn get_assumed_known_network(
+    connect_via: &WifiConnectionType,
+    essid: &str,
+) -> AnnotatedWirelessNetwork {
+    let service_identifier = match connect_via {
+        WifiConnectionType::Netctl => Some(NetworkingServiceIdentifier::Netctl(
+            NetctlIdentifier::from_essid(essid).to_string(),
+        )),
+        WifiConnectionType::Nmcli => Some(NetworkingServiceIdentifier::NetworkManager),
+        WifiConnectionType::Iwd => Some(NetworkingServiceIdentifier::Iwd),
+        WifiConnectionType::None | WifiConnectionType::Print => None,
+    };
+    AnnotatedWirelessNetwork::builder()
+        .essid(essid)
+        .service_identifier(service_identifier)
+        .build()
+}
+
+// Refreshing looks up the known networks again, and asks again.
+fn select_known_network(
+    options: &WifiForgetOptions,
+) -> Result<AnnotatedWirelessNetwork, RuwiError> {
+    let mut loop_protection = 0;
+    loop {
+        loop_check(&mut loop_protection, LOOP_MAX)?;
+        let known_networks =
+            WifiKnownNetworks::find_known_networks_from_system(options)?.get_known_networks();
+        if known_networks.is_empty() {
+            return Err(get_no_known_networks_error(options));
+        }
+        if let Some(network) = select_known_network_to_forget(options, &known_networks)? {
+            return Ok(network);
+        }
+    }
+}
+
+fn get_no_known_networks_error(options: &WifiForgetOptions) -> RuwiError {
+    let hint = if options.get_dry_run() {
+        " Known networks aren't looked up in dry-run mode, give the network's name with `-e` instead."
+    } else {
+        ""
+    };
+    rerr!(
+        RuwiErrorKind::NoKnownNetworksFound,
+        format!(
+            "No known networks found for {}.{}",
+            options.get_connect_via(),
+            hint
+        )
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::wifi::WifiOptions;
+    use crate::options::GlobalOptions;
+
+    fn get_dry_run_options(connect_via: WifiConnectionType) -> WifiForgetOptions {
+        let globals = GlobalOptions::builder().dry_run(true).build();
+        WifiForgetOptions::builder()
+            .wifi(WifiOptions::builder().globals(globals).build())
+            .connect_via(connect_via)
+            .build()
+    }
+
+    #[test]
+    fn test_assumed_known_network_in_dry_run() -> Result<(), RuwiError> {
+        let options = get_dry_run_options(WifiConnectionType::Netctl);
+        let network = get_given_known_network(&options, "Home Network")?;
+        assert_eq![network.get_public_name(), "Home Network"];
+        assert_eq![
+            network.get_service_identifier(),
+            Some(&NetworkingServiceIdentifier::Netctl("Home_Network".to_string()))
+        ];
+
+        let options = get_dry_run_options(WifiConnectionType::Nmcli);
+        let network = get_given_known_network(&options, "Home Network")?;
+        assert_eq![
+            network.get_service_identifier(),
+            Some(&NetworkingServiceIdentifier::NetworkManager)
+        ];
+
+        let options = get_dry_run_options(WifiConnectionType::Print);
+        let network = get_given_known_network(&options, "Home Network")?;
+        assert![!network.is_known()];
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_known_networks_in_dry_run() {
+        let options = get_dry_run_options(WifiConnectionType::Netctl);
+        let err = select_known_network(&options).unwrap_err();
+        assert_eq![err.kind, RuwiErrorKind::NoKnownNetworksFound];
+        assert![err.desc.contains("-e")];
+    }
+}
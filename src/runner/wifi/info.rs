@@ -0,0 +1,13 @@
+use crate::options::wifi::info::WifiInfoOptions;
+use crate::prelude::*;
+use crate::runner::Runner;
+use crate::select::get_preview_for_selected_line;
+
+impl Runner for WifiInfoOptions {
+    fn run(&self) -> Result<(), RuwiError> {
+        let preview =
+            get_preview_for_selected_line(self.get_details_file(), self.get_selected_line())?;
+        println!("{}", preview);
+        Ok(())
+    }
+}
@@ -0,0 +1,315 @@
+use super::utils::{gather_wifi_network_data, scan_and_sort_networks};
+
+use crate::interface_management::ip_interfaces::{LinuxIPInterface, WifiIPInterface};
+use crate::known_networks::WifiKnownNetworks;
+use crate::options::wifi::list::WifiListOptions;
+use crate::prelude::*;
+use crate::runner::Runner;
+use crate::select::Selector;
+use crate::sort_networks::is_hidden_network_name;
+
+use serde_derive::Serialize;
+use unicode_width::UnicodeWidthStr;
+
+const NO_VALUE_TOKEN: &str = "-";
+const HIDDEN_ESSID_TOKEN: &str = "(hidden)";
+const HEADERS: [&str; 7] = [
+    "ESSID",
+    "BSSID",
+    "SIGNAL",
+    "QUALITY",
+    "ENCRYPTION",
+    "KNOWN",
+    "SERVICE",
+];
+
+impl Runner for WifiListOptions {
+    fn run(&self) -> Result<(), RuwiError> {
+        let interface = WifiIPInterface::from_name_or_first(self, self.get_given_interface_name())?;
+        let (sorted_networks, line_parse_errors) = scan_and_sort_networks(self, &interface)?;
+        // Hidden networks can't be told apart by name, so they're listed after the others.
+        let networks = sorted_networks
+            .get_networks()
+            .iter()
+            .chain(sorted_networks.get_hidden_networks())
+            .cloned()
+            .collect::<Vec<_>>();
+        let output = format_networks(self.get_format(), &networks, &line_parse_errors)?;
+        println!("{}", output);
+        Ok(())
+    }
+}
+
+impl WifiDataGatherer for WifiListOptions {
+    fn get_wifi_data(
+        &self,
+        interface: &WifiIPInterface,
+        synchronous_rescan: &Option<SynchronousRescanType>,
+    ) -> Result<(WifiKnownNetworks, Vec<ScanResult>), RuwiError> {
+        gather_wifi_network_data(self, interface, synchronous_rescan)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ListedNetworks<'a> {
+    networks: Vec<ListedNetwork<'a>>,
+    parse_errors: Vec<ListedParseError<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct ListedParseError<'a> {
+    error: IndividualParseError,
+    line: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct ListedNetwork<'a> {
+    essid: &'a str,
+    hidden: bool,
+    bssid: Option<&'a str>,
+    signal: Option<i32>,
+    quality: Option<u8>,
+    encrypted: bool,
+    known: bool,
+    service: Option<&'static str>,
+    service_identifier: Option<&'a str>,
+}
+
+impl<'a> From<&'a AnnotatedWirelessNetwork> for ListedNetwork<'a> {
+    fn from(nw: &'a AnnotatedWirelessNetwork) -> Self {
+        let (service, service_identifier) = match nw.get_service_identifier() {
+            Some(NetworkingServiceIdentifier::Netctl(ident)) => (Some("netctl"), Some(ident.as_ref())),
+            Some(NetworkingServiceIdentifier::NetworkManager) => (Some("network_manager"), None),
+            Some(NetworkingServiceIdentifier::Iwd) => (Some("iwd"), None),
+            None => (None, None),
+        };
+        // The names of hidden networks are empty or made up of NUL bytes, neither of which
+        // is worth printing.
+        let hidden = is_hidden_network_name(nw.get_public_name());
+        Self {
+            essid: if hidden { "" } else { nw.get_public_name() },
+            hidden,
+            bssid: nw._get_bssid().map(String::as_ref),
+            signal: nw.get_signal_strength().map(SignalStrength::get_dbm),
+            quality: nw.get_signal_strength().map(SignalStrength::get_quality),
+            encrypted: nw.is_encrypted(),
+            known: nw.is_known(),
+            service,
+            service_identifier,
+        }
+    }
+}
+
+impl<'a> ListedNetwork<'a> {
+    fn get_fields(&self) -> [String; 7] {
+        let service = match (self.service, self.service_identifier) {
+            (Some(service), Some(ident)) => format!("{} ({})", service, ident),
+            (Some(service), None) => service.to_string(),
+            (None, _) => NO_VALUE_TOKEN.to_string(),
+        };
+        [
+            if self.hidden { HIDDEN_ESSID_TOKEN } else { self.essid }.to_string(),
+            self.bssid.unwrap_or(NO_VALUE_TOKEN).to_string(),
+            self.signal
+                .map_or_else(|| NO_VALUE_TOKEN.to_string(), |x| x.to_string()),
+            self.quality
+                .map_or_else(|| NO_VALUE_TOKEN.to_string(), |x| format!("{}%", x)),
+            if self.encrypted { "encrypted" } else { "open" }.to_string(),
+            if self.known { "yes" } else { "no" }.to_string(),
+            service,
+        ]
+    }
+}
+
+// Lines which failed to parse are only included in JSON output, since the table and TSV
+// formats are meant to be read one network per line. They can be seen with `-d` instead.
+fn format_networks(
+    format: &WifiListFormat,
+    networks: &[AnnotatedWirelessNetwork],
+    line_parse_errors: &[(String, IndividualParseError)],
+) -> Result<String, RuwiError> {
+    let listed_networks = networks.iter().map(ListedNetwork::from).collect::<Vec<_>>();
+    match format {
+        WifiListFormat::Human => Ok(format_as_table(&listed_networks)),
+        WifiListFormat::Tsv => Ok(format_as_tsv(&listed_networks)),
+        WifiListFormat::Json => format_as_json(listed_networks, line_parse_errors),
+    }
+}
+
+fn format_as_table(listed_networks: &[ListedNetwork]) -> String {
+    let rows = listed_networks
+        .iter()
+        .map(ListedNetwork::get_fields)
+        .collect::<Vec<_>>();
+
+    let mut widths = HEADERS.iter().map(|x| x.len()).collect::<Vec<_>>();
+    for row in &rows {
+        for (width, field) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(field.width());
+        }
+    }
+
+    let header = HEADERS.iter().map(|x| (*x).to_string()).collect::<Vec<_>>();
+    std::iter::once(header)
+        .chain(rows.into_iter().map(|row| row.to_vec()))
+        .map(|row| {
+            row.iter()
+                .zip(widths.iter())
+                .map(|(field, width)| {
+                    let padding = width.saturating_sub(field.width());
+                    format!("{}{}", field, " ".repeat(padding))
+                })
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn format_as_tsv(listed_networks: &[ListedNetwork]) -> String {
+    std::iter::once(HEADERS.join("\t"))
+        .chain(
+            listed_networks
+                .iter()
+                .map(|nw| nw.get_fields().join("\t")),
+        )
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn format_as_json(
+    listed_networks: Vec<ListedNetwork>,
+    line_parse_errors: &[(String, IndividualParseError)],
+) -> Result<String, RuwiError> {
+    let parse_errors = line_parse_errors
+        .iter()
+        .map(|(line, error)| ListedParseError {
+            error: *error,
+            line,
+        })
+        .collect();
+    serde_json::to_string(&ListedNetworks {
+        networks: listed_networks,
+        parse_errors,
+    })
+    .map_err(|e| {
+        rerr!(
+            RuwiErrorKind::FailedToSerializeNetworkList,
+            format!("Failed to serialize network list to JSON: {}", e)
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_networks() -> Vec<AnnotatedWirelessNetwork> {
+        vec![
+            AnnotatedWirelessNetwork::builder()
+                .essid("Valparaiso_Guest_House 2")
+                .bssid(Some("68:72:51:68:73:da".to_string()))
+                .signal_strength(Some(SignalStrength::from_dbm(-33)))
+                .is_encrypted(true)
+                .service_identifier(NetworkingServiceIdentifier::netctl_nw("valpo"))
+                .build(),
+            AnnotatedWirelessNetwork::builder()
+                .essid("xfinitywifi")
+                .service_identifier(None)
+                .build(),
+        ]
+    }
+
+    #[test]
+    fn test_format_hidden() {
+        let mut networks = get_networks();
+        networks.push(
+            AnnotatedWirelessNetwork::builder()
+                .essid("\0\0\0")
+                .signal_strength(Some(SignalStrength::from_dbm(-71)))
+                .is_encrypted(true)
+                .build(),
+        );
+        let output = format_networks(&WifiListFormat::Tsv, &networks, &[]).unwrap();
+        assert_eq![
+            output.lines().last().unwrap(),
+            "(hidden)\t-\t-71\t58%\tencrypted\tno\t-"
+        ];
+
+        let output = format_networks(&WifiListFormat::Json, &networks, &[]).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq![parsed["networks"][2]["essid"], ""];
+        assert_eq![parsed["networks"][2]["hidden"], true];
+        assert_eq![parsed["networks"][0]["hidden"], false];
+    }
+
+    #[test]
+    fn test_format_human() {
+        let output = format_networks(&WifiListFormat::Human, &get_networks(), &[]).unwrap();
+        let expected = [
+            "ESSID                     BSSID              SIGNAL  QUALITY  ENCRYPTION  KNOWN  SERVICE",
+            "Valparaiso_Guest_House 2  68:72:51:68:73:da  -33     100%     encrypted   yes    netctl (valpo)",
+            "xfinitywifi               -                  -       -        open        no     -",
+        ]
+        .join("\n");
+        assert_eq![output, expected];
+    }
+
+    #[test]
+    fn test_format_tsv() {
+        let output = format_networks(&WifiListFormat::Tsv, &get_networks(), &[]).unwrap();
+        let expected = [
+            "ESSID\tBSSID\tSIGNAL\tQUALITY\tENCRYPTION\tKNOWN\tSERVICE",
+            "Valparaiso_Guest_House 2\t68:72:51:68:73:da\t-33\t100%\tencrypted\tyes\tnetctl (valpo)",
+            "xfinitywifi\t-\t-\t-\topen\tno\t-",
+        ]
+        .join("\n");
+        assert_eq![output, expected];
+    }
+
+    #[test]
+    fn test_format_json() {
+        let line_parse_errors = vec![(
+            "WPA2:95:alltheinternets".to_string(),
+            IndividualParseError::MissingNmcliSeparator,
+        )];
+        let output =
+            format_networks(&WifiListFormat::Json, &get_networks(), &line_parse_errors).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let expected = serde_json::json!({
+            "networks": [
+                {
+                    "essid": "Valparaiso_Guest_House 2",
+                    "hidden": false,
+                    "bssid": "68:72:51:68:73:da",
+                    "signal": -33,
+                    "quality": 100,
+                    "encrypted": true,
+                    "known": true,
+                    "service": "netctl",
+                    "service_identifier": "valpo",
+                },
+                {
+                    "essid": "xfinitywifi",
+                    "hidden": false,
+                    "bssid": null,
+                    "signal": null,
+                    "quality": null,
+                    "encrypted": false,
+                    "known": false,
+                    "service": null,
+                    "service_identifier": null,
+                },
+            ],
+            "parse_errors": [
+                {
+                    "error": "MissingNmcliSeparator",
+                    "line": "WPA2:95:alltheinternets",
+                },
+            ]
+        });
+        assert_eq![parsed, expected];
+    }
+}
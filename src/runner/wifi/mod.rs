@@ -1,3 +1,6 @@
 mod connect;
+mod forget;
+mod info;
+mod list;
 mod select;
 mod utils;
@@ -1,16 +1,15 @@
-use super::utils::scan_and_select_network;
+use super::utils::{gather_wifi_network_data, scan_and_select_network};
 
 use crate::interface_management::ip_interfaces::{LinuxIPInterface, WifiIPInterface};
 use crate::known_networks::WifiKnownNetworks;
 use crate::options::wifi::select::WifiSelectOptions;
 use crate::prelude::*;
 use crate::runner::Runner;
-use crate::wifi_scan::wifi_scan;
 
 impl Runner for WifiSelectOptions {
     fn run(&self) -> Result<(), RuwiError> {
-        let interface = WifiIPInterface::from_name_or_first(self, self.get_given_interface_name())?;
-        let selected_network = scan_and_select_network(self, &interface)?;
+        let mut interface = WifiIPInterface::from_name_or_first(self, self.get_given_interface_name())?;
+        let (selected_network, _key_action) = scan_and_select_network(self, &mut interface)?;
         println!("{}", selected_network.get_public_name());
         Ok(())
     }
@@ -21,8 +20,7 @@ impl WifiDataGatherer for WifiSelectOptions {
         &self,
         interface: &WifiIPInterface,
         synchronous_rescan: &Option<SynchronousRescanType>,
-    ) -> Result<(WifiKnownNetworks, ScanResult), RuwiError> {
-        let scan_result = wifi_scan(self, interface, synchronous_rescan)?;
-        Ok((WifiKnownNetworks::default(), scan_result))
+    ) -> Result<(WifiKnownNetworks, Vec<ScanResult>), RuwiError> {
+        gather_wifi_network_data(self, interface, synchronous_rescan)
     }
 }
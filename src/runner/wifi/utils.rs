@@ -1,51 +1,253 @@
 use std::thread;
 
 use crate::annotate_networks::annotate_networks;
+use crate::essid_pattern::EssidPattern;
+use crate::forget_network::{confirm_and_forget_network, select_and_forget_known_network};
 use crate::interface_management::ip_interfaces::{LinuxIPInterface, WifiIPInterface};
 use crate::known_networks::WifiKnownNetworks;
-use crate::parse::parse_result;
+use crate::parse::parse_results;
 use crate::prelude::*;
+use crate::select::key_actions::SelectionKeyAction;
+use crate::select::prompt_for_hidden_essid::{prompt_for_essid, prompt_for_hidden_essid};
+use crate::select::selection_outcome::SelectionOutcome;
 use crate::select::Selector;
-use crate::sort_networks::SortedFilteredNetworks;
-use crate::synchronous_retry_logic::{
-    manual_refresh_requested, should_auto_retry_with_synchronous_scan,
-};
+use crate::sort_networks::{NetworkSortOrder, SortedFilteredNetworks};
+use crate::synchronous_retry_logic::should_auto_retry_with_synchronous_scan;
 use crate::utils::{await_thread, loop_check};
 use crate::wifi_scan::wifi_scan;
 
 const LOOP_MAX: u16 = 1000;
 
+// Networks forgotten with a key action are removed, and then the user is asked to select again.
+// If the user switches interfaces, `interface` is updated so that the caller connects with it.
 pub(crate) fn scan_and_select_network<O>(
+    options: &O,
+    interface: &mut WifiIPInterface,
+) -> Result<(AnnotatedWirelessNetwork, Option<SelectionKeyAction>), RuwiError>
+where
+    O: Send + Sync + Global + Wifi + AutoSelect + WifiDataGatherer,
+{
+    let mut synchronous_retry = None;
+    let mut loop_protection = 0;
+    loop {
+        loop_check(&mut loop_protection, LOOP_MAX)?;
+        let (sorted_networks, _) =
+            scan_and_sort_networks_impl(options, interface, &mut synchronous_retry)?;
+
+        match sorted_networks.select_network(options)? {
+            SelectionOutcome::Selected(network, Some(SelectionKeyAction::ForgetNetwork)) => {
+                confirm_and_forget_network(options, &network)?;
+            }
+            SelectionOutcome::Selected(network, key_action) => return Ok((network, key_action)),
+            SelectionOutcome::Refresh => {
+                eprintln!("[NOTE]: Refresh requested, running synchronous scan.");
+                synchronous_retry = Some(SynchronousRescanType::ManuallyRequested);
+            }
+            SelectionOutcome::EnterEssidManually => {
+                if let Some(network) = get_network_from_manual_entry(options, &sorted_networks)? {
+                    return Ok((network, None));
+                }
+            }
+            SelectionOutcome::ConnectToHiddenNetwork => {
+                eprintln!("[NOTE]: Hidden network requested, will prompt for its name.");
+                return get_hidden_network_from_prompt(options, &sorted_networks)
+                    .map(|nw| (nw, None));
+            }
+            SelectionOutcome::ForgetKnownNetwork => {
+                select_and_forget_known_network(options, sorted_networks.get_networks())?;
+            }
+            SelectionOutcome::SwitchInterface => {
+                *interface = select_other_interface(options, interface)?;
+                synchronous_retry = None;
+            }
+        }
+    }
+}
+
+// Cached scan results can be stale, so if nothing matches, a synchronous scan is tried once
+// before giving up.
+pub(crate) fn scan_and_match_network<O>(
     options: &O,
     interface: &WifiIPInterface,
+    pattern: &EssidPattern,
 ) -> Result<AnnotatedWirelessNetwork, RuwiError>
 where
-    O: Send + Sync + Global + AutoSelect + WifiDataGatherer,
+    O: Global + Wifi + AutoSelect + WifiDataGatherer,
 {
     let mut synchronous_retry = None;
+    loop {
+        let (sorted_networks, _) =
+            scan_and_sort_networks_impl(options, interface, &mut synchronous_retry)?;
+        let matching_networks = sorted_networks.filter_by_essid_pattern(pattern);
+
+        if let Some(network) = matching_networks.get_networks().first() {
+            eprintln!(
+                "[NOTE]: Found {} network(s) matching \"{}\", using the best: {}",
+                matching_networks.get_networks().len(),
+                pattern.as_str(),
+                network.get_public_name()
+            );
+            return Ok(network.clone());
+        }
+
+        if synchronous_retry.is_none() && !options.get_force_synchronous_scan() {
+            eprintln!(
+                "[NOTE]: No networks matching \"{}\" seen, running synchronous scan.",
+                pattern.as_str()
+            );
+            synchronous_retry = Some(SynchronousRescanType::Automatic);
+            continue;
+        }
+
+        return Err(pattern.get_no_match_error(sorted_networks.get_networks()));
+    }
+}
+
+fn get_hidden_network_from_prompt<O>(
+    options: &O,
+    sorted_networks: &SortedFilteredNetworks<AnnotatedWirelessNetwork>,
+) -> Result<AnnotatedWirelessNetwork, RuwiError>
+where
+    O: Global,
+{
+    let essid = prompt_for_hidden_essid(options)?;
+    let is_encrypted = sorted_networks
+        .get_hidden_networks()
+        .iter()
+        .any(|nw| nw.is_encrypted());
+    Ok(AnnotatedWirelessNetwork::builder()
+        .essid(essid)
+        .is_encrypted(is_encrypted)
+        .is_hidden(true)
+        .build())
+}
+
+// A name which was seen in the scan uses everything we know about that network. Anything else
+// can't be seen, so it's treated as an encrypted hidden network. An empty name goes back to
+// selection.
+fn get_network_from_manual_entry<O>(
+    options: &O,
+    sorted_networks: &SortedFilteredNetworks<AnnotatedWirelessNetwork>,
+) -> Result<Option<AnnotatedWirelessNetwork>, RuwiError>
+where
+    O: Global,
+{
+    let essid = prompt_for_essid(options)?;
+    if essid.is_empty() {
+        eprintln!("[NOTE]: No network name given, returning to selection.");
+        return Ok(None);
+    }
+
+    let seen_network = sorted_networks
+        .get_networks()
+        .iter()
+        .find(|nw| nw.get_public_name() == essid)
+        .cloned();
+    Ok(Some(seen_network.unwrap_or_else(|| {
+        eprintln!(
+            "[NOTE]: \"{}\" was not seen in the scan results, will treat it as a hidden network.",
+            essid
+        );
+        AnnotatedWirelessNetwork::builder()
+            .essid(essid)
+            .is_encrypted(true)
+            .is_hidden(true)
+            .build()
+    })))
+}
+
+fn select_other_interface<O>(
+    options: &O,
+    current_interface: &WifiIPInterface,
+) -> Result<WifiIPInterface, RuwiError>
+where
+    O: Global,
+{
+    // Refreshing looks for interfaces again, e.g. after plugging in a USB adapter.
     let mut loop_protection = 0;
     loop {
         loop_check(&mut loop_protection, LOOP_MAX)?;
-        let (known_network_names, scan_result) =
-            options.get_wifi_data(interface, &synchronous_retry)?;
-        let parse_results = parse_result(options, interface.get_ifname(), &scan_result)?;
+        let interfaces = if options.is_test_or_dry_run() {
+            vec![current_interface.clone()]
+        } else {
+            WifiIPInterface::get_all(options)?
+        };
+
+        if interfaces.len() < 2 {
+            eprintln!(
+                "[NOTE]: No other wifi interfaces found, staying on \"{}\".",
+                current_interface.get_ifname()
+            );
+            return Ok(current_interface.clone());
+        }
+
+        match interfaces.prompt_user_for_selection(options)? {
+            SelectionOutcome::Selected(interface, _) => {
+                eprintln!("[NOTE]: Switched to interface \"{}\".", interface.get_ifname());
+                return Ok(interface);
+            }
+            SelectionOutcome::Refresh => {
+                eprintln!("[NOTE]: Refresh requested, looking for wifi interfaces again.");
+            }
+            _ => return Ok(current_interface.clone()),
+        }
+    }
+}
+
+impl Selector for Vec<WifiIPInterface> {
+    type Item = WifiIPInterface;
+
+    fn get_networks(&self) -> &[WifiIPInterface] {
+        self
+    }
+
+    fn get_selection_target<O>(&self, _options: &O) -> String
+    where
+        O: Global,
+    {
+        "wifi interface".to_string()
+    }
+}
+
+pub(super) type LineParseErrors = Vec<(String, IndividualParseError)>;
+
+pub(super) fn scan_and_sort_networks<O>(
+    options: &O,
+    interface: &WifiIPInterface,
+) -> Result<(SortedFilteredNetworks<AnnotatedWirelessNetwork>, LineParseErrors), RuwiError>
+where
+    O: Global + Wifi + AutoSelect + WifiDataGatherer,
+{
+    scan_and_sort_networks_impl(options, interface, &mut None)
+}
+
+fn scan_and_sort_networks_impl<O>(
+    options: &O,
+    interface: &WifiIPInterface,
+    synchronous_retry: &mut Option<SynchronousRescanType>,
+) -> Result<(SortedFilteredNetworks<AnnotatedWirelessNetwork>, LineParseErrors), RuwiError>
+where
+    O: Global + Wifi + AutoSelect + WifiDataGatherer,
+{
+    let mut loop_protection = 0;
+    loop {
+        loop_check(&mut loop_protection, LOOP_MAX)?;
+        let (known_network_names, scan_results) =
+            options.get_wifi_data(interface, synchronous_retry)?;
+        let parse_results = parse_results(options, interface.get_ifname(), &scan_results)?;
 
         let annotated_networks =
             annotate_networks(options, &parse_results.seen_networks, &known_network_names);
-        if should_auto_retry_with_synchronous_scan(options, &annotated_networks, &synchronous_retry)
+        if should_auto_retry_with_synchronous_scan(options, &annotated_networks, synchronous_retry)
         {
-            synchronous_retry = Some(SynchronousRescanType::Automatic);
+            *synchronous_retry = Some(SynchronousRescanType::Automatic);
             continue;
         }
 
-        let sorted_networks = SortedFilteredNetworks::new(annotated_networks);
-
-        let selected_network_result = sorted_networks.select_network(options);
-        if manual_refresh_requested(&selected_network_result) {
-            synchronous_retry = Some(SynchronousRescanType::ManuallyRequested);
-            continue;
-        }
-        return selected_network_result;
+        return Ok((
+            SortedFilteredNetworks::new(annotated_networks, &NetworkSortOrder::from_options(options)),
+            parse_results.line_parse_errors,
+        ));
     }
 }
 
@@ -53,9 +255,9 @@ pub(super) fn gather_wifi_network_data<O>(
     options: &O,
     interface: &WifiIPInterface,
     synchronous_rescan: &Option<SynchronousRescanType>,
-) -> Result<(WifiKnownNetworks, ScanResult), RuwiError>
+) -> Result<(WifiKnownNetworks, Vec<ScanResult>), RuwiError>
 where
-    O: 'static + Global + Wifi + WifiConnect + Send + Sync + Clone,
+    O: 'static + Global + Wifi + WifiConnectionManager + Send + Sync + Clone,
 {
     let options: &'static O = Box::leak(Box::new(options.clone()));
     let synchronous_rescan = synchronous_rescan.clone();
@@ -67,14 +269,15 @@ where
         thread::spawn(move || wifi_scan(options, &interface, &synchronous_rescan));
 
     let known_network_names = await_thread(get_nw_names)??;
-    let scan_result = await_thread(get_scan_results)??;
+    let scan_results = await_thread(get_scan_results)??;
 
-    Ok((known_network_names, scan_result))
+    Ok((known_network_names, scan_results))
 }
 
 pub(super) fn get_network_from_given_essid<O>(
     options: &O,
     essid: &str,
+    is_hidden: bool,
 ) -> Result<AnnotatedWirelessNetwork, RuwiError>
 where
     O: Global + Wifi + WifiConnect,
@@ -88,5 +291,6 @@ where
         .essid(essid)
         .service_identifier(existing_network_identifier)
         .is_encrypted(is_encrypted)
+        .is_hidden(is_hidden)
         .build())
 }
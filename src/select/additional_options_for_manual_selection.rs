@@ -1,26 +1,46 @@
-use crate::strum_utils::possible_string_vals;
-use std::fmt::Debug;
-
 use strum_macros::{AsStaticStr, Display, EnumIter, EnumString};
 
+// Options shown below the list of networks/devices in the manual selector. Choosing one of
+// these is returned to the caller as a `SelectionOutcome`, and each runner decides what to do.
 #[derive(Debug, Clone, PartialEq, Eq, EnumString, EnumIter, Display, AsStaticStr)]
 #[strum(serialize_all = "snake_case")]
-pub(super) enum SelectionOption {
+pub(crate) enum SelectionOption {
     Refresh,
+    #[strum(serialize = "enter network name manually...")]
+    EnterEssidManually,
+    #[strum(serialize = "connect to hidden network...")]
+    ConnectToHiddenNetwork,
+    #[strum(serialize = "forget a known network...")]
+    ForgetKnownNetwork,
+    #[strum(serialize = "switch interface...")]
+    SwitchInterface,
 }
 
-pub(super) fn get_possible_selection_options_as_strings() -> Vec<String> {
-    possible_string_vals::<SelectionOption, _>()
-        .iter()
-        .map(|&x| x.into())
-        .collect()
+pub(super) fn get_selection_options_as_strings(options: &[SelectionOption]) -> Vec<String> {
+    options.iter().map(ToString::to_string).collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::str::FromStr;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_selection_options_roundtrip() {
+        for option in SelectionOption::iter() {
+            assert_eq![SelectionOption::from_str(&option.to_string()), Ok(option)];
+        }
+    }
+
     #[test]
-    fn test_refresh_is_present() {
-        assert![get_possible_selection_options_as_strings().contains(&"refresh".to_string())];
+    fn test_selection_options_as_strings() {
+        assert_eq![
+            get_selection_options_as_strings(&[
+                SelectionOption::Refresh,
+                SelectionOption::ConnectToHiddenNetwork
+            ]),
+            vec!["refresh", "connect to hidden network..."]
+        ];
     }
 }
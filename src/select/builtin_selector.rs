@@ -0,0 +1,459 @@
+// A minimal fuzzy-filtering selector which runs directly on the terminal, for systems where
+// neither fzf nor a display server (for dmenu) is available.
+use super::additional_options_for_manual_selection::SelectionOption;
+use crate::prelude::*;
+
+use nix::sys::termios::{cfmakeraw, tcgetattr, tcsetattr, SetArg, Termios};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::os::unix::io::AsRawFd;
+use zeroize::Zeroizing;
+
+const TTY_PATH: &str = "/dev/tty";
+const MAX_VISIBLE_ITEMS: usize = 15;
+
+const ENTER_ALTERNATE_SCREEN: &str = "\x1b[?1049h";
+const LEAVE_ALTERNATE_SCREEN: &str = "\x1b[?1049l";
+const CLEAR_SCREEN: &str = "\x1b[H\x1b[2J";
+const CLEAR_LINE: &str = "\r\x1b[2K";
+const INVERT_COLORS: &str = "\x1b[7m";
+const RESET_COLORS: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Key {
+    Up,
+    Down,
+    Enter,
+    Backspace,
+    Escape,
+    CtrlC,
+    CtrlR,
+    CtrlU,
+    Char(char),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SelectorAction {
+    Continue,
+    Select(String),
+    Refresh,
+    Bail,
+}
+
+pub(crate) fn run_builtin_selector<O>(
+    _options: &O,
+    prompt: &str,
+    elements: &[String],
+) -> Result<String, RuwiError>
+where
+    O: Global,
+{
+    let mut terminal = RawTerminal::open().map_err(err_builtin_selector_failed)?;
+    terminal
+        .write_str(ENTER_ALTERNATE_SCREEN)
+        .map_err(err_builtin_selector_failed)?;
+
+    let res = run_selector_loop(&mut terminal, prompt, elements);
+
+    terminal
+        .write_str(LEAVE_ALTERNATE_SCREEN)
+        .map_err(err_builtin_selector_failed)?;
+
+    match res.map_err(err_builtin_selector_failed)? {
+        SelectorAction::Select(line) => Ok(line),
+        SelectorAction::Refresh => Ok(SelectionOption::Refresh.to_string()),
+        SelectorAction::Bail | SelectorAction::Continue => Err(err_bailout_requested()),
+    }
+}
+
+pub(crate) fn run_builtin_password_prompt<O>(
+    _options: &O,
+    prompt: &str,
+) -> Result<EncryptionKey, RuwiError>
+where
+    O: Global,
+{
+    let mut terminal = RawTerminal::open().map_err(err_builtin_selector_failed)?;
+    let mut password = Zeroizing::new(String::new());
+    loop {
+        terminal
+            .write_str(&format!("{}{}{}", CLEAR_LINE, prompt, "*".repeat(password.chars().count())))
+            .map_err(err_builtin_selector_failed)?;
+        for key in terminal.read_keys().map_err(err_builtin_selector_failed)? {
+            match key {
+                Key::Enter => {
+                    terminal.write_str("\r\n").map_err(err_builtin_selector_failed)?;
+                    return Ok(EncryptionKey::from(password.as_str()));
+                }
+                Key::Escape | Key::CtrlC => {
+                    terminal.write_str("\r\n").map_err(err_builtin_selector_failed)?;
+                    return Err(err_bailout_requested());
+                }
+                Key::Backspace => {
+                    password.pop();
+                }
+                Key::CtrlU => password.clear(),
+                Key::Char(c) => password.push(c),
+                Key::Up | Key::Down | Key::CtrlR => {}
+            }
+        }
+    }
+}
+
+fn run_selector_loop(
+    terminal: &mut RawTerminal,
+    prompt: &str,
+    elements: &[String],
+) -> io::Result<SelectorAction> {
+    let mut state = SelectorState::new(elements);
+    loop {
+        terminal.write_str(&state.render(prompt))?;
+        for key in terminal.read_keys()? {
+            let action = state.handle_key(key);
+            if action != SelectorAction::Continue {
+                return Ok(action);
+            }
+        }
+    }
+}
+
+struct SelectorState<'a> {
+    elements: &'a [String],
+    query: String,
+    matches: Vec<&'a String>,
+    cursor: usize,
+}
+
+impl<'a> SelectorState<'a> {
+    fn new(elements: &'a [String]) -> Self {
+        let mut state = Self {
+            elements,
+            query: String::new(),
+            matches: vec![],
+            cursor: 0,
+        };
+        state.update_matches();
+        state
+    }
+
+    fn update_matches(&mut self) {
+        let query = &self.query;
+        self.matches = self
+            .elements
+            .iter()
+            .filter(|element| fuzzy_match(query, element))
+            .collect();
+        self.cursor = 0;
+    }
+
+    fn handle_key(&mut self, key: Key) -> SelectorAction {
+        match key {
+            Key::Up => self.cursor = self.cursor.saturating_sub(1),
+            Key::Down => {
+                if self.cursor + 1 < self.matches.len() {
+                    self.cursor += 1;
+                }
+            }
+            Key::Enter => {
+                if let Some(selected) = self.matches.get(self.cursor) {
+                    return SelectorAction::Select((*selected).clone());
+                }
+            }
+            Key::Backspace => {
+                self.query.pop();
+                self.update_matches();
+            }
+            Key::CtrlU => {
+                self.query.clear();
+                self.update_matches();
+            }
+            Key::Char(c) => {
+                self.query.push(c);
+                self.update_matches();
+            }
+            Key::CtrlR => return SelectorAction::Refresh,
+            Key::Escape | Key::CtrlC => return SelectorAction::Bail,
+        }
+        SelectorAction::Continue
+    }
+
+    // Only a window of the matches around the cursor is shown, so that long lists
+    // don't scroll the prompt off of the screen.
+    fn render(&self, prompt: &str) -> String {
+        let window_start = (self.cursor + 1).saturating_sub(MAX_VISIBLE_ITEMS);
+        let lines = self
+            .matches
+            .iter()
+            .enumerate()
+            .skip(window_start)
+            .take(MAX_VISIBLE_ITEMS)
+            .map(|(i, element)| {
+                if i == self.cursor {
+                    format!("{}> {}{}", INVERT_COLORS, element, RESET_COLORS)
+                } else {
+                    format!("  {}", element)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // The terminal cursor is moved back to the end of the query after drawing.
+        let prompt_line = format!("{}{}", prompt, self.query);
+        format!(
+            "{}{}\r\n  {}/{}\r\n{}\x1b[1;{}H",
+            CLEAR_SCREEN,
+            prompt_line,
+            self.matches.len(),
+            self.elements.len(),
+            lines.join("\r\n"),
+            prompt_line.chars().count() + 1,
+        )
+    }
+}
+
+// Case-insensitive subsequence matching, as fzf does by default.
+fn fuzzy_match(query: &str, candidate: &str) -> bool {
+    let candidate = candidate.to_lowercase();
+    let mut candidate_chars = candidate.chars();
+    query
+        .to_lowercase()
+        .chars()
+        .all(|q| candidate_chars.any(|c| c == q))
+}
+
+fn parse_keys(input: &[u8]) -> Vec<Key> {
+    let chars = String::from_utf8_lossy(input).chars().collect::<Vec<_>>();
+    let mut keys = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let key = match &chars[i..] {
+            ['\x1b', '[' | 'O', rest @ ..] => {
+                let (key, len) = parse_escape_sequence(rest);
+                i += 2 + len;
+                keys.extend(key);
+                continue;
+            }
+            ['\x1b', ..] => Key::Escape,
+            ['\r' | '\n', ..] => Key::Enter,
+            ['\x7f' | '\x08', ..] => Key::Backspace,
+            ['\x03', ..] => Key::CtrlC,
+            ['\x0e', ..] => Key::Down,
+            ['\x10', ..] => Key::Up,
+            ['\x12', ..] => Key::CtrlR,
+            ['\x15', ..] => Key::CtrlU,
+            [c, ..] if !c.is_control() => Key::Char(*c),
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        keys.push(key);
+        i += 1;
+    }
+    keys
+}
+
+// The rest of a CSI ("ESC [") or SS3 ("ESC O") sequence: any parameter and intermediate bytes,
+// then a final byte. Returns the key for the arrows, and how many characters the sequence used,
+// so that other keys (e.g. Left, Home, Delete) can be skipped over as a whole.
+fn parse_escape_sequence(rest: &[char]) -> (Option<Key>, usize) {
+    let params_len = rest
+        .iter()
+        .take_while(|c| ('\x20'..='\x3f').contains(*c))
+        .count();
+    match rest.get(params_len) {
+        Some(final_char) if ('\x40'..='\x7e').contains(final_char) => {
+            let key = match (params_len, final_char) {
+                (0, 'A') => Some(Key::Up),
+                (0, 'B') => Some(Key::Down),
+                _ => None,
+            };
+            (key, params_len + 1)
+        }
+        // Cut short, or not a valid sequence. Only the parameters are skipped.
+        _ => (None, params_len),
+    }
+}
+
+// Puts the controlling terminal into raw mode for as long as this is alive. The terminal is
+// used directly instead of stdin/stdout, so that ruwi's own input and output can be redirected.
+struct RawTerminal {
+    tty: File,
+    original_termios: Termios,
+}
+
+impl RawTerminal {
+    fn open() -> io::Result<Self> {
+        let tty = OpenOptions::new().read(true).write(true).open(TTY_PATH)?;
+        let original_termios = tcgetattr(tty.as_raw_fd()).map_err(nix_to_io_err)?;
+        let mut raw_termios = original_termios.clone();
+        cfmakeraw(&mut raw_termios);
+        tcsetattr(tty.as_raw_fd(), SetArg::TCSANOW, &raw_termios).map_err(nix_to_io_err)?;
+        Ok(Self {
+            tty,
+            original_termios,
+        })
+    }
+
+    fn write_str(&mut self, text: &str) -> io::Result<()> {
+        self.tty.write_all(text.as_bytes())?;
+        self.tty.flush()
+    }
+
+    fn read_keys(&mut self) -> io::Result<Vec<Key>> {
+        let mut buf = [0; 64];
+        let bytes_read = self.tty.read(&mut buf)?;
+        if bytes_read == 0 {
+            return Ok(vec![Key::CtrlC]);
+        }
+        Ok(parse_keys(&buf[..bytes_read]))
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = tcsetattr(self.tty.as_raw_fd(), SetArg::TCSANOW, &self.original_termios);
+    }
+}
+
+fn nix_to_io_err(e: nix::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, e.to_string())
+}
+
+fn err_builtin_selector_failed(e: io::Error) -> RuwiError {
+    rerr!(
+        RuwiErrorKind::BuiltinSelectorFailed,
+        format!("Failed to run the builtin selector on {}: {}", TTY_PATH, e)
+    )
+}
+
+fn err_bailout_requested() -> RuwiError {
+    rerr!(
+        RuwiErrorKind::PromptCommandBailoutRequested,
+        "Selection cancelled."
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_elements() -> Vec<String> {
+        vec![
+            "0) [70%] Valparaiso_Guest_House 1".to_string(),
+            "1) [50%] xfinitywifi".to_string(),
+            "2) [20%] Patrician Pad".to_string(),
+            "refresh".to_string(),
+        ]
+    }
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert![fuzzy_match("", "anything")];
+        assert![fuzzy_match("xfin", "1) xfinitywifi")];
+        assert![fuzzy_match("PATPAD", "2) Patrician Pad")];
+        assert![fuzzy_match("vgh", "Valparaiso_Guest_House 1")];
+        assert![!fuzzy_match("hgv", "Valparaiso_Guest_House 1")];
+        assert![!fuzzy_match("xfinityx", "xfinitywifi")];
+    }
+
+    #[test]
+    fn test_parse_keys() {
+        assert_eq![parse_keys(b"ab"), vec![Key::Char('a'), Key::Char('b')]];
+        assert_eq![parse_keys(b"\x1b[A\x1b[B"), vec![Key::Up, Key::Down]];
+        assert_eq![parse_keys(b"\x1bOA"), vec![Key::Up]];
+        assert_eq![parse_keys(b"\x1b"), vec![Key::Escape]];
+        assert_eq![parse_keys("é".as_bytes()), vec![Key::Char('é')]];
+        assert_eq![
+            parse_keys(b"\r\x7f\x03\x12\x15\x0e\x10"),
+            vec![
+                Key::Enter,
+                Key::Backspace,
+                Key::CtrlC,
+                Key::CtrlR,
+                Key::CtrlU,
+                Key::Down,
+                Key::Up
+            ]
+        ];
+    }
+
+    #[test]
+    fn test_parse_keys_skips_other_escape_sequences() {
+        assert_eq![parse_keys(b"\x1b[C"), vec![]];
+        assert_eq![parse_keys(b"\x1b[3~"), vec![]];
+        assert_eq![
+            parse_keys(b"a\x1b[D\x1bOH\x1b[1;5Cb\x1b[3~\x1b[B"),
+            vec![Key::Char('a'), Key::Char('b'), Key::Down]
+        ];
+        assert_eq![parse_keys(b"\x1b[1;5A"), vec![]];
+        assert_eq![parse_keys(b"\x1b["), vec![]];
+    }
+
+    #[test]
+    fn test_navigation_and_selection() {
+        let elements = get_elements();
+        let mut state = SelectorState::new(&elements);
+        assert_eq![state.handle_key(Key::Up), SelectorAction::Continue];
+        assert_eq![state.handle_key(Key::Down), SelectorAction::Continue];
+        assert_eq![state.handle_key(Key::Down), SelectorAction::Continue];
+        assert_eq![
+            state.handle_key(Key::Enter),
+            SelectorAction::Select(elements[2].clone())
+        ];
+
+        for _ in 0..10 {
+            state.handle_key(Key::Down);
+        }
+        assert_eq![
+            state.handle_key(Key::Enter),
+            SelectorAction::Select(elements[3].clone())
+        ];
+    }
+
+    #[test]
+    fn test_incremental_filtering() {
+        let elements = get_elements();
+        let mut state = SelectorState::new(&elements);
+        state.handle_key(Key::Down);
+        for c in "wifi".chars() {
+            state.handle_key(Key::Char(c));
+        }
+        assert_eq![state.matches, vec![&elements[1]]];
+        assert_eq![
+            state.handle_key(Key::Enter),
+            SelectorAction::Select(elements[1].clone())
+        ];
+
+        state.handle_key(Key::Char('z'));
+        assert![state.matches.is_empty()];
+        assert_eq![state.handle_key(Key::Enter), SelectorAction::Continue];
+
+        state.handle_key(Key::Backspace);
+        assert_eq![state.matches, vec![&elements[1]]];
+
+        state.handle_key(Key::CtrlU);
+        assert_eq![state.matches.len(), elements.len()];
+    }
+
+    #[test]
+    fn test_refresh_and_bail() {
+        let elements = get_elements();
+        let mut state = SelectorState::new(&elements);
+        assert_eq![state.handle_key(Key::CtrlR), SelectorAction::Refresh];
+        assert_eq![state.handle_key(Key::Escape), SelectorAction::Bail];
+        assert_eq![state.handle_key(Key::CtrlC), SelectorAction::Bail];
+    }
+
+    #[test]
+    fn test_render_shows_query_and_highlights_cursor() {
+        let elements = get_elements();
+        let mut state = SelectorState::new(&elements);
+        state.handle_key(Key::Char('p'));
+        state.handle_key(Key::Char('a'));
+        let rendered = state.render("Select a network: ");
+        assert![rendered.contains("Select a network: pa\r\n")];
+        assert![rendered.contains(&format!("{}> {}", INVERT_COLORS, elements[0]))];
+        assert![rendered.contains(&format!("  {}", elements[2]))];
+        assert![!rendered.contains("xfinitywifi")];
+    }
+}
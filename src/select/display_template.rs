@@ -0,0 +1,273 @@
+use crate::prelude::*;
+
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use unicode_width::UnicodeWidthStr;
+
+// A user-given format for the lines shown in the selector, e.g. "{signal_bars} {essid} {known}".
+// Fields are written in braces, and "{{" and "}}" are literal braces. Fields which don't apply
+// to an item (e.g. `{band}` for a Bluetooth device) are left empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayTemplate {
+    segments: Vec<TemplateSegment>,
+    use_icons: bool,
+    align_columns: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TemplateSegment {
+    Text(String),
+    Field(DisplayField),
+}
+
+impl DisplayTemplate {
+    pub(crate) fn parse(template: &str) -> Result<Self, RuwiError> {
+        let mut segments = vec![];
+        let mut text = String::new();
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(err_unmatched_brace(template, "{")),
+                        }
+                    }
+                    let field = DisplayField::from_str(&name)
+                        .map_err(|_| err_unknown_field(template, &name))?;
+                    if !text.is_empty() {
+                        segments.push(TemplateSegment::Text(std::mem::take(&mut text)));
+                    }
+                    segments.push(TemplateSegment::Field(field));
+                }
+                '}' => return Err(err_unmatched_brace(template, "}")),
+                _ => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            segments.push(TemplateSegment::Text(text));
+        }
+
+        Ok(Self {
+            segments,
+            use_icons: false,
+            align_columns: false,
+        })
+    }
+
+    pub(crate) fn with_icons(self, use_icons: bool) -> Self {
+        Self { use_icons, ..self }
+    }
+
+    pub(crate) fn with_aligned_columns(self, align_columns: bool) -> Self {
+        Self {
+            align_columns,
+            ..self
+        }
+    }
+
+    // With aligned columns, each field is padded to the width of its widest value, so that the
+    // fields line up when the lines are shown one above the other. Widths are measured in
+    // terminal columns, since emoji and CJK characters take up two.
+    pub(crate) fn render_all<T>(&self, items: &[T]) -> Vec<String>
+    where
+        T: Selectable,
+    {
+        let rendered_fields = items
+            .iter()
+            .map(|item| self.get_field_values(item))
+            .collect::<Vec<_>>();
+
+        let widths = if self.align_columns {
+            self.get_column_widths(&rendered_fields)
+        } else {
+            vec![0; self.segments.len()]
+        };
+
+        rendered_fields
+            .iter()
+            .map(|values| render_line(values, &widths))
+            .collect()
+    }
+
+    fn get_field_values<T>(&self, item: &T) -> Vec<String>
+    where
+        T: Selectable,
+    {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                TemplateSegment::Text(text) => text.clone(),
+                TemplateSegment::Field(field) => item
+                    .get_display_field(*field, self.use_icons)
+                    .unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    fn get_column_widths(&self, rendered_fields: &[Vec<String>]) -> Vec<usize> {
+        (0..self.segments.len())
+            .map(|i| {
+                rendered_fields
+                    .iter()
+                    .map(|values| values[i].width())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+
+}
+
+// Each line has to stay on one line, or it would be shown as more than one choice.
+fn render_line(values: &[String], widths: &[usize]) -> String {
+    let mut line = String::new();
+    for (value, width) in values.iter().zip(widths) {
+        line.push_str(value);
+        let padding = width.saturating_sub(value.width());
+        line.extend(std::iter::repeat(' ').take(padding));
+    }
+    line.replace(&['\n', '\r'][..], " ").trim_end().to_string()
+}
+
+fn err_unknown_field(template: &str, name: &str) -> RuwiError {
+    let known_fields = DisplayField::iter()
+        .map(|field| format!("{{{}}}", field))
+        .collect::<Vec<_>>();
+    rerr!(
+        RuwiErrorKind::InvalidDisplayTemplate,
+        format!(
+            "Unknown field \"{{{}}}\" in display template \"{}\". Known fields are: {}",
+            name,
+            template,
+            known_fields.join(", ")
+        )
+    )
+}
+
+fn err_unmatched_brace(template: &str, brace: &str) -> RuwiError {
+    rerr!(
+        RuwiErrorKind::InvalidDisplayTemplate,
+        format!(
+            "Unmatched \"{}\" in display template \"{}\". Use \"{{{{\" or \"}}}}\" for literal braces.",
+            brace, template
+        )
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bluetooth::BluetoothDevice;
+
+    fn get_networks() -> Vec<AnnotatedWirelessNetwork> {
+        vec![
+            AnnotatedWirelessNetwork::builder()
+                .essid("Citadel")
+                .bssid(Some("f4:28:53:fe:a5:d0".to_string()))
+                .is_encrypted(true)
+                .signal_strength(Some(SignalStrength::from_dbm(-74)))
+                .frequency(Some(5180))
+                .security(Some(WifiSecurity::Wpa2Psk))
+                .service_identifier(NetworkingServiceIdentifier::netctl_nw("ruwi_citadel"))
+                .build(),
+            AnnotatedWirelessNetwork::builder()
+                .essid("xfinitywifi")
+                .signal_strength(Some(SignalStrength::from_dbm(-45)))
+                .frequency(Some(2412))
+                .build(),
+            AnnotatedWirelessNetwork::from_essid_only("NoSignal"),
+        ]
+    }
+
+    #[test]
+    fn test_render_fields() -> Result<(), RuwiError> {
+        let template = DisplayTemplate::parse(
+            "{signal_bars} {dbm}dBm {quality}% {essid} {band} {security} {known} {bssid}",
+        )?;
+        assert_eq![
+            template.render_all(&get_networks()),
+            vec![
+                "***- -74dBm 52% Citadel 5GHz WPA2-PSK known f4:28:53:fe:a5:d0",
+                "**** -45dBm 100% xfinitywifi 2.4GHz open",
+                " dBm % NoSignal  open",
+            ]
+        ];
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_icons_and_aligned_columns() -> Result<(), RuwiError> {
+        let template = DisplayTemplate::parse("{signal_bars} {essid} | {security}{known}")?
+            .with_icons(true)
+            .with_aligned_columns(true);
+        assert_eq![
+            template.render_all(&get_networks()),
+            vec![
+                "▂▄▆_ Citadel     | 🔒★",
+                "▂▄▆█ xfinitywifi | 🔓",
+                "     NoSignal    | 🔓",
+            ]
+        ];
+        Ok(())
+    }
+
+    #[test]
+    fn test_aligned_columns_with_wide_characters() -> Result<(), RuwiError> {
+        let template = DisplayTemplate::parse("{essid} | {dbm}")?.with_aligned_columns(true);
+        let networks = vec![
+            AnnotatedWirelessNetwork::builder()
+                .essid("🍕 Pizza")
+                .signal_strength(Some(SignalStrength::from_dbm(-50)))
+                .build(),
+            AnnotatedWirelessNetwork::builder()
+                .essid("Pizza Shop")
+                .signal_strength(Some(SignalStrength::from_dbm(-60)))
+                .build(),
+        ];
+        assert_eq![
+            template.render_all(&networks),
+            vec!["🍕 Pizza   | -50", "Pizza Shop | -60"]
+        ];
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_other_selectables() -> Result<(), RuwiError> {
+        let template = DisplayTemplate::parse("{name} [{bssid}] {band}")?;
+        let devices = vec![BluetoothDevice::builder()
+            .name("Headphones")
+            .addr("AB:13")
+            .build()];
+        assert_eq![template.render_all(&devices), vec!["Headphones [AB:13]"]];
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_escapes_and_errors() -> Result<(), RuwiError> {
+        let template = DisplayTemplate::parse("{{{essid}}}")?;
+        assert_eq![template.render_all(&get_networks())[0], "{Citadel}"];
+
+        let err = DisplayTemplate::parse("{essid} {strength}").err().unwrap();
+        assert_eq![err.kind, RuwiErrorKind::InvalidDisplayTemplate];
+        assert![err.desc.contains("{signal_bars}")];
+
+        let err = DisplayTemplate::parse("{essid").err().unwrap();
+        assert_eq![err.kind, RuwiErrorKind::InvalidDisplayTemplate];
+
+        let err = DisplayTemplate::parse("essid}").err().unwrap();
+        assert_eq![err.kind, RuwiErrorKind::InvalidDisplayTemplate];
+        Ok(())
+    }
+}
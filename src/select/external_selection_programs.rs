@@ -1,10 +1,15 @@
+use super::fzf_preview::PreviewDetailsFile;
+use super::key_actions::{get_key_actions_for_fzf_expect, get_key_actions_header};
+
 use crate::prelude::*;
-use crate::run_commands::PromptCommandRunner;
+use crate::run_commands::{PromptCommandRunner, SystemCommandRunner};
 
 use std::env;
 use std::io;
 use std::io::BufRead;
 use std::io::Write;
+use std::time::Duration;
+use zeroize::Zeroizing;
 
 pub(crate) fn run_dmenu<O>(
     options: &O,
@@ -14,13 +19,204 @@ pub(crate) fn run_dmenu<O>(
 where
     O: Global,
 {
-    PromptCommandRunner::new(options, "dmenu", &["-i", "-p", prompt], elements).run()
+    PromptCommandRunner::new(options, "dmenu", &["-i", "-p", prompt], elements)
+        .with_timeout(get_selection_timeout(options, elements))
+        .run()
+}
+
+// Only dmenu builds with the password patch understand `-P`, and plain dmenu exits with an
+// error on flags it doesn't know, so its usage text is checked first.
+pub(crate) fn run_dmenu_password<O>(options: &O, prompt: &str) -> Result<String, RuwiError>
+where
+    O: Global,
+{
+    let args = if dmenu_supports_password_flag(options) {
+        ["-P", "-p", prompt]
+    } else {
+        eprintln!("[NOTE]: This dmenu was built without the password patch, so the password will be shown as it is typed.");
+        ["-i", "-p", prompt]
+    };
+    PromptCommandRunner::new(options, "dmenu", &args, &[])
+        .with_sensitive_output()
+        .run()
+}
+
+fn dmenu_supports_password_flag<O>(options: &O) -> bool
+where
+    O: Global,
+{
+    SystemCommandRunner::new(options, "dmenu", &["-h"])
+        .run_command_output_raw(
+            RuwiErrorKind::PromptCommandSpawnFailed,
+            "Failed to run dmenu to check for password support.",
+        )
+        .map(|output| dmenu_usage_lists_password_flag(&String::from_utf8_lossy(&output.stderr)))
+        .unwrap_or(false)
+}
+
+// dmenu's usage text starts with its switches, e.g. "usage: dmenu [-bfiv] [-l lines] ...", and
+// builds with the password patch list "P" among them.
+fn dmenu_usage_lists_password_flag(usage: &str) -> bool {
+    usage
+        .split("[-")
+        .nth(1)
+        .and_then(|rest| rest.split(']').next())
+        .map_or(false, |switches| switches.contains('P'))
+}
+
+pub(crate) fn run_bemenu<O>(
+    options: &O,
+    prompt: &str,
+    elements: &[String],
+) -> Result<String, RuwiError>
+where
+    O: Global,
+{
+    PromptCommandRunner::new(options, "bemenu", &["-i", "-p", prompt], elements)
+        .with_timeout(get_selection_timeout(options, elements))
+        .run()
+}
+
+pub(crate) fn run_bemenu_password<O>(options: &O, prompt: &str) -> Result<String, RuwiError>
+where
+    O: Global,
+{
+    PromptCommandRunner::new(options, "bemenu", &["-x", "-p", prompt], &[])
+        .with_sensitive_output()
+        .run()
+}
+
+pub(crate) fn run_fuzzel<O>(
+    options: &O,
+    prompt: &str,
+    elements: &[String],
+) -> Result<String, RuwiError>
+where
+    O: Global,
+{
+    PromptCommandRunner::new(options, "fuzzel", &["--dmenu", "--prompt", prompt], elements)
+        .with_timeout(get_selection_timeout(options, elements))
+        .run()
+}
+
+pub(crate) fn run_fuzzel_password<O>(options: &O, prompt: &str) -> Result<String, RuwiError>
+where
+    O: Global,
+{
+    PromptCommandRunner::new(options, "fuzzel", &["--dmenu", "--password", "--prompt", prompt], &[])
+        .with_sensitive_output()
+        .run()
+}
+
+pub(crate) fn run_rofi<O>(
+    options: &O,
+    prompt: &str,
+    elements: &[String],
+) -> Result<String, RuwiError>
+where
+    O: Global,
+{
+    PromptCommandRunner::new(options, "rofi", &["-dmenu", "-i", "-p", prompt], elements)
+        .with_timeout(get_selection_timeout(options, elements))
+        .run()
+}
+
+pub(crate) fn run_rofi_password<O>(options: &O, prompt: &str) -> Result<String, RuwiError>
+where
+    O: Global,
+{
+    PromptCommandRunner::new(options, "rofi", &["-dmenu", "-password", "-p", prompt], &[])
+        .with_sensitive_output()
+        .run()
 }
 
+pub(crate) fn run_wofi<O>(
+    options: &O,
+    prompt: &str,
+    elements: &[String],
+) -> Result<String, RuwiError>
+where
+    O: Global,
+{
+    PromptCommandRunner::new(options, "wofi", &["--dmenu", "-i", "--prompt", prompt], elements)
+        .with_timeout(get_selection_timeout(options, elements))
+        .run()
+}
+
+pub(crate) fn run_wofi_password<O>(options: &O, prompt: &str) -> Result<String, RuwiError>
+where
+    O: Global,
+{
+    PromptCommandRunner::new(
+        options,
+        "wofi",
+        &["--dmenu", "--password", "--prompt", prompt],
+        &[],
+    )
+    .with_sensitive_output()
+    .run()
+}
+
+// Runs the command given with `--selection-command`. Password and hidden network prompts use the
+// same command, with no elements given on stdin.
+pub(crate) fn run_custom_command<O>(
+    options: &O,
+    prompt: &str,
+    elements: &[String],
+) -> Result<String, RuwiError>
+where
+    O: Global,
+{
+    run_custom_command_impl(options, prompt, elements, false)
+}
+
+pub(crate) fn run_custom_command_password<O>(options: &O, prompt: &str) -> Result<String, RuwiError>
+where
+    O: Global,
+{
+    run_custom_command_impl(options, prompt, &[], true)
+}
+
+fn run_custom_command_impl<O>(
+    options: &O,
+    prompt: &str,
+    elements: &[String],
+    is_password: bool,
+) -> Result<String, RuwiError>
+where
+    O: Global,
+{
+    let template = options.get_selection_command().as_ref().ok_or_else(|| {
+        rerr!(
+            RuwiErrorKind::InvalidCustomSelectionCommand,
+            "The \"custom\" selection method requires a command to be given with `--selection-command`."
+        )
+    })?;
+    let words = split_command_template(template, prompt)?;
+    let (cmd_name, args) = words.split_first().ok_or_else(|| {
+        rerr!(
+            RuwiErrorKind::InvalidCustomSelectionCommand,
+            "The command given with `--selection-command` is empty."
+        )
+    })?;
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let runner = PromptCommandRunner::new(options, cmd_name, &args, elements)
+        .with_timeout(get_selection_timeout(options, elements));
+    if is_password {
+        runner.with_sensitive_output().run()
+    } else {
+        runner.run()
+    }
+}
+
+// When details are given for each element (i.e. for wifi networks), a preview pane shows the
+// details of the highlighted network, and the key actions from `key_actions.rs` are enabled.
 pub(crate) fn run_fzf<O>(
     options: &O,
     prompt: &str,
     elements: &[String],
+    preview_details: Option<&[String]>,
 ) -> Result<String, RuwiError>
 where
     O: Global,
@@ -44,26 +240,52 @@ where
         Err(_) => {}
     };
 
-    PromptCommandRunner::new(options, "fzf", args.as_slice(), elements).run()
+    let preview_file = match preview_details.map(PreviewDetailsFile::create) {
+        Some(Ok(preview_file)) => Some(preview_file),
+        Some(Err(err)) => {
+            eprintln!("[NOTE]: Network details will not be shown: {}", err);
+            None
+        }
+        None => None,
+    };
+    let preview_command = preview_file
+        .as_ref()
+        .and_then(PreviewDetailsFile::get_preview_command);
+    let expectopt = format!("--expect={}", get_key_actions_for_fzf_expect());
+    let header = get_key_actions_header();
+
+    if preview_details.is_some() {
+        args.extend(&[expectopt.as_ref(), "--header", header.as_ref()]);
+    }
+    if let Some(preview_command) = &preview_command {
+        args.extend(&["--preview", preview_command, "--preview-window", "right:50%:wrap"]);
+    }
+
+    PromptCommandRunner::new(options, "fzf", args.as_slice(), elements)
+        .with_timeout(get_selection_timeout(options, elements))
+        .run()
 }
 
-pub(crate) fn run_select_nocurses<O>(
-    options: &O,
-    prompt: &str,
-    elements: &[String],
-) -> Result<String, RuwiError>
+// Only choosing from a list is timed out. Prompts for typed text (passwords, network names) use
+// the same programs with no elements, and are left alone since the user may still be typing.
+fn get_selection_timeout<O>(options: &O, elements: &[String]) -> Option<Duration>
 where
     O: Global,
 {
-    run_stdin_prompt_single_line_impl(options, prompt, elements)
-        .map_err(|e| rerr!(RuwiErrorKind::SingleLinePromptFailed, e.to_string()))
+    if elements.is_empty() {
+        None
+    } else {
+        options.get_select_timeout().map(|timeout| timeout.get_duration())
+    }
 }
 
+// This is also used to read passwords, so the line is read straight into a buffer that is
+// wiped on drop.
 pub(crate) fn run_stdin_prompt_single_line<O>(
     options: &O,
     prompt: &str,
     elements: &[String],
-) -> Result<String, RuwiError>
+) -> Result<Zeroizing<String>, RuwiError>
 where
     O: Global,
 {
@@ -71,11 +293,61 @@ where
         .map_err(|e| rerr!(RuwiErrorKind::SingleLinePromptFailed, e.to_string()))
 }
 
+// Split a command template into words, honoring single and double quotes and backslash escapes,
+// and fill in the prompt. Substitution happens after splitting, so a prompt containing spaces
+// or quotes always stays within the word it was placed in.
+fn split_command_template(template: &str, prompt: &str) -> Result<Vec<String>, RuwiError> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+    let mut chars = template.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"') | None, '\\') => {
+                in_word = true;
+                if let Some(escaped) = chars.next() {
+                    word.push(escaped);
+                }
+            }
+            (Some(_), c) => word.push(c),
+            (None, '\'' | '"') => {
+                in_word = true;
+                quote = Some(c);
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(word.replace("{prompt}", prompt));
+                    word.clear();
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err(rerr!(
+            RuwiErrorKind::InvalidCustomSelectionCommand,
+            format!("Unterminated quote in selection command: {}", template)
+        ));
+    }
+    if in_word {
+        words.push(word.replace("{prompt}", prompt));
+    }
+    Ok(words)
+}
+
 fn run_stdin_prompt_single_line_impl<O>(
     _options: &O,
     prompt: &str,
     elements: &[String],
-) -> io::Result<String>
+) -> io::Result<Zeroizing<String>>
 where
     O: Global,
 {
@@ -84,9 +356,56 @@ where
     }
     eprint!("{}", prompt);
     io::stdout().flush()?;
-    let stdin = io::stdin();
-    let line_res = stdin.lock().lines().next();
+    let mut line = Zeroizing::new(String::with_capacity(1024));
+    if io::stdin().lock().read_line(&mut line)? == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Failed to read from stdin.",
+        ));
+    }
+    let trimmed_len = line.trim_end_matches(&['\r', '\n'][..]).len();
+    line.truncate(trimmed_len);
+    Ok(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    line_res
-        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Failed to read from stdin."))?
+    #[test]
+    fn test_dmenu_usage_lists_password_flag() {
+        assert![dmenu_usage_lists_password_flag(
+            "usage: dmenu [-bfiPv] [-l lines] [-p prompt] [-fn font] [-m monitor]"
+        )];
+        assert![!dmenu_usage_lists_password_flag(
+            "usage: dmenu [-bfiv] [-l lines] [-p prompt] [-fn font] [-m monitor]"
+        )];
+        assert![!dmenu_usage_lists_password_flag("dmenu: command not found")];
+    }
+
+    #[test]
+    fn test_split_command_template() -> Result<(), RuwiError> {
+        let words = split_command_template("my-menu -l 10 --prompt '{prompt}'", "Select a network: ")?;
+        assert_eq![words, vec!["my-menu", "-l", "10", "--prompt", "Select a network: "]];
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_command_template_quotes_and_escapes() -> Result<(), RuwiError> {
+        let words = split_command_template(
+            r#"  menu  "a \"b\" c" 'd\e' f\ g --p={prompt} "" "#,
+            "it's",
+        )?;
+        assert_eq![words, vec!["menu", "a \"b\" c", "d\\e", "f g", "--p=it's", ""]];
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_command_template_unterminated_quote() {
+        let res = split_command_template("menu 'oops", "prompt");
+        assert_eq![
+            res.err().unwrap().kind,
+            RuwiErrorKind::InvalidCustomSelectionCommand
+        ];
+    }
 }
@@ -0,0 +1,113 @@
+use crate::prelude::*;
+
+use std::env;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::PathBuf;
+use std::process;
+
+// fzf runs the preview command once for every highlighted line, so the details for each
+// network are written out once when fzf starts, and `ruwi wifi info` just looks them up by
+// index. This avoids rescanning, and guarantees the indices match what fzf is showing.
+pub(super) struct PreviewDetailsFile {
+    path: PathBuf,
+}
+
+impl PreviewDetailsFile {
+    pub(super) fn create(details: &[String]) -> Result<Self, RuwiError> {
+        let path = env::temp_dir().join(format!("ruwi_preview_{}.json", process::id()));
+        let contents = serde_json::to_string(details).map_err(|e| {
+            rerr!(RuwiErrorKind::FailedToWritePreviewDetails, e.to_string())
+        })?;
+
+        // Anything left over from a previous run with the same pid is stale. create_new is used
+        // so that we never follow a symlink someone else placed in the temp dir.
+        let _ = fs::remove_file(&path);
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(&path)
+            .and_then(|mut file| file.write_all(contents.as_bytes()))
+            .map_err(|e| {
+                rerr!(
+                    RuwiErrorKind::FailedToWritePreviewDetails,
+                    format!("Failed to write \"{}\": {}", path.display(), e)
+                )
+            })?;
+
+        Ok(Self { path })
+    }
+
+    pub(super) fn get_preview_command(&self) -> Option<String> {
+        let exe = env::current_exe().ok()?;
+        Some(format!(
+            "{} -m fzf wifi info --details-file {} {{1}}",
+            quote_for_shell(exe.to_str()?),
+            quote_for_shell(self.path.to_str()?)
+        ))
+    }
+}
+
+impl Drop for PreviewDetailsFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+pub(crate) fn get_preview_for_selected_line(
+    details_file: &str,
+    selected_line: &str,
+) -> Result<String, RuwiError> {
+    let contents = fs::read_to_string(details_file)
+        .map_err(|e| err_failed_to_read_preview_details(details_file, &e))?;
+    let details: Vec<String> = serde_json::from_str(&contents)
+        .map_err(|e| err_failed_to_read_preview_details(details_file, &e))?;
+    Ok(get_preview_from_details(&details, selected_line))
+}
+
+fn err_failed_to_read_preview_details(details_file: &str, e: &dyn fmt::Display) -> RuwiError {
+    rerr!(
+        RuwiErrorKind::FailedToReadPreviewDetails,
+        format!("Failed to read \"{}\": {}", details_file, e)
+    )
+}
+
+// fzf passes along the first word of the highlighted line, which is e.g. "3)" for networks.
+// Lines like "refresh" have no details to show.
+fn get_preview_from_details(details: &[String], selected_line: &str) -> String {
+    selected_line
+        .split_whitespace()
+        .next()
+        .and_then(|word| word.trim_end_matches(')').parse::<usize>().ok())
+        .and_then(|index| details.get(index))
+        .cloned()
+        .unwrap_or_default()
+}
+
+fn quote_for_shell(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_preview_from_details() {
+        let details = vec!["ESSID: Lobby".to_string(), "ESSID: Citadel".to_string()];
+        assert_eq![get_preview_from_details(&details, "1)"), "ESSID: Citadel"];
+        assert_eq![get_preview_from_details(&details, "0) Lobby"), "ESSID: Lobby"];
+        assert_eq![get_preview_from_details(&details, " 0)"), "ESSID: Lobby"];
+        assert_eq![get_preview_from_details(&details, "2)"), ""];
+        assert_eq![get_preview_from_details(&details, "refresh"), ""];
+    }
+
+    #[test]
+    fn test_quote_for_shell() {
+        assert_eq![quote_for_shell("/usr/bin/ruwi"), "'/usr/bin/ruwi'"];
+        assert_eq![quote_for_shell("/tmp/it's here"), "'/tmp/it'\\''s here'"];
+    }
+}
@@ -0,0 +1,82 @@
+use crate::strum_utils::possible_string_vals;
+
+use std::str::FromStr;
+
+use strum::IntoEnumIterator;
+use strum_macros::{AsStaticStr, Display, EnumIter, EnumString};
+
+// Keys which fzf will accept a selection with, via `--expect`. fzf prints the key which was
+// pressed on the line before the selected item (or an empty line if Enter was pressed).
+#[derive(Debug, Clone, PartialEq, Eq, EnumString, EnumIter, Display, AsStaticStr)]
+pub(crate) enum SelectionKeyAction {
+    #[strum(serialize = "alt-p")]
+    ForcePasswordPrompt,
+    #[strum(serialize = "alt-n")]
+    ConnectWithoutPassword,
+    #[strum(serialize = "alt-x")]
+    ForgetNetwork,
+}
+
+impl SelectionKeyAction {
+    fn get_description(&self) -> &'static str {
+        match self {
+            Self::ForcePasswordPrompt => "ask for password",
+            Self::ConnectWithoutPassword => "no password",
+            Self::ForgetNetwork => "forget",
+        }
+    }
+}
+
+pub(super) fn get_key_actions_for_fzf_expect() -> String {
+    possible_string_vals::<SelectionKeyAction, _>().join(",")
+}
+
+pub(super) fn get_key_actions_header() -> String {
+    let key_actions = SelectionKeyAction::iter()
+        .map(|action| format!("{}: {}", action, action.get_description()));
+    std::iter::once("ctrl-r: refresh".to_string())
+        .chain(key_actions)
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+pub(super) fn split_key_action(selector_output: &str) -> (Option<SelectionKeyAction>, &str) {
+    if let Some((first_line, rest)) = selector_output.split_once('\n') {
+        if let Ok(action) = SelectionKeyAction::from_str(first_line.trim()) {
+            return (Some(action), rest.trim());
+        }
+    }
+    (None, selector_output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_key_action() {
+        assert_eq![
+            split_key_action("alt-x\n3) Lobby"),
+            (Some(SelectionKeyAction::ForgetNetwork), "3) Lobby")
+        ];
+        assert_eq![
+            split_key_action("alt-p\n0) [70%] Lobby [K]\n"),
+            (Some(SelectionKeyAction::ForcePasswordPrompt), "0) [70%] Lobby [K]")
+        ];
+        assert_eq![split_key_action("3) Lobby"), (None, "3) Lobby")];
+        assert_eq![split_key_action("refresh"), (None, "refresh")];
+        assert_eq![
+            split_key_action("3) Lobby\n4) Lobby Two"),
+            (None, "3) Lobby\n4) Lobby Two")
+        ];
+    }
+
+    #[test]
+    fn test_key_actions_for_fzf() {
+        assert_eq![get_key_actions_for_fzf_expect(), "alt-p,alt-n,alt-x"];
+        assert_eq![
+            get_key_actions_header(),
+            "ctrl-r: refresh | alt-p: ask for password | alt-n: no password | alt-x: forget"
+        ];
+    }
+}
@@ -1,12 +1,29 @@
-mod additional_options_for_manual_selection;
+pub(crate) mod additional_options_for_manual_selection;
+mod builtin_selector;
+pub(crate) mod display_template;
 mod external_selection_programs;
-mod get_index_of_selected_item;
+mod fzf_preview;
+pub(crate) mod key_actions;
+mod parse_selected_line;
+mod pinentry;
+pub(crate) mod prompt_for_confirmation;
 pub(crate) mod prompt_for_encryption_key;
+pub(crate) mod prompt_for_hidden_essid;
+pub(crate) mod selection_outcome;
+mod terminal_password_prompt;
 mod text_format_for_display;
 
-use additional_options_for_manual_selection::get_possible_selection_options_as_strings;
-use external_selection_programs::{run_dmenu, run_fzf, run_select_nocurses};
-use get_index_of_selected_item::get_index_of_selected_item;
+use additional_options_for_manual_selection::{get_selection_options_as_strings, SelectionOption};
+use builtin_selector::run_builtin_selector;
+use external_selection_programs::{
+    run_bemenu, run_custom_command, run_dmenu, run_fuzzel, run_fzf, run_rofi,
+    run_stdin_prompt_single_line, run_wofi,
+};
+use key_actions::split_key_action;
+use parse_selected_line::{parse_selected_line, SelectedLine};
+use selection_outcome::SelectionOutcome;
+
+pub(crate) use fzf_preview::get_preview_for_selected_line;
 
 use crate::prelude::*;
 
@@ -20,43 +37,91 @@ pub trait Selector: Sized + Debug
     type Item: Selectable + Identifiable + Clone + Debug + Known;
     fn get_networks(&self) -> &[Self::Item];
 
-    fn get_network_tokens(&self) -> Vec<String> {
-        self.get_networks()
+    // Refresh is always available, anything else has to be handled by whoever is selecting.
+    fn get_selection_options(&self) -> Vec<SelectionOption> {
+        vec![SelectionOption::Refresh]
+    }
+
+    fn get_selection_target<O>(&self, options: &O) -> String
+    where
+        O: Global,
+    {
+        options.get_post_parse_context().network_or_device.to_string()
+    }
+
+    // The index prefix is added outside of any display template, so that the selected line can
+    // always be parsed back into an index.
+    fn get_network_tokens<O>(&self, options: &O) -> Vec<String>
+    where
+        O: Global,
+    {
+        let display_strings = match options.get_display_template() {
+            Some(template) => template.render_all(self.get_networks()),
+            None => self
+                .get_networks()
+                .iter()
+                .map(Selectable::get_display_string)
+                .collect(),
+        };
+        display_strings
             .iter()
             .enumerate()
-            .map(|(i, x)| format!("{}) {}", i, x.get_display_string()))
+            .map(|(i, x)| format!("{}) {}", i, x))
             .collect()
     }
-    fn get_tokens_for_selection(&self) -> Vec<String> {
-        self.get_network_tokens()
+    fn get_preview_details(&self) -> Option<Vec<String>> {
+        self.get_networks()
+            .iter()
+            .map(Selectable::get_preview_string)
+            .collect()
+    }
+
+    fn get_tokens_for_selection<O>(&self, options: &O) -> Vec<String>
+    where
+        O: Global,
+    {
+        self.get_network_tokens(options)
             .into_iter()
-            .chain(get_possible_selection_options_as_strings())
+            .chain(get_selection_options_as_strings(
+                &self.get_selection_options(),
+            ))
             .collect()
     }
 
-    fn select_network<O>(&self, options: &O) -> Result<Self::Item, RuwiError>
+    fn select_network<O>(&self, options: &O) -> Result<SelectionOutcome<Self::Item>, RuwiError>
     where
         O: Global + AutoSelect,
     {
         self.select_network_impl(options, Self::prompt_user_for_selection::<O>)
     }
 
-    fn select_network_impl<O, F>(&self, options: &O, manual_selector: F) -> Result<Self::Item, RuwiError>
+    fn select_network_impl<O, F>(
+        &self,
+        options: &O,
+        manual_selector: F,
+    ) -> Result<SelectionOutcome<Self::Item>, RuwiError>
     where
         O: Global + AutoSelect,
-        F: FnOnce(&Self, &O) -> Result<Self::Item, RuwiError>,
+        F: FnOnce(&Self, &O) -> Result<SelectionOutcome<Self::Item>, RuwiError>,
     {
+        let manual_selector = |nws: &Self, opts: &O| {
+            manual_selector(nws, opts).or_else(|err| nws.select_after_timeout(opts, err))
+        };
         let selected_network_res = match options.get_auto_mode() {
             AutoMode::Ask => manual_selector(self, options),
             AutoMode::KnownOrAsk => self
                 .select_first_known(options)
+                .map(SelectionOutcome::from)
                 .or_else(|_| manual_selector(self, options)),
-            AutoMode::KnownOrFail => self.select_first_known(options),
-            AutoMode::First => self.select_first(options),
+            AutoMode::KnownOrFail => self.select_first_known(options).map(SelectionOutcome::from),
+            AutoMode::First => self.select_first(options).map(SelectionOutcome::from),
         };
 
         match &selected_network_res {
-            Ok(nw) => eprintln!("[NOTE]: Selected network: \"{}\"", nw.get_public_name()),
+            Ok(SelectionOutcome::Selected(nw, _)) => {
+                eprintln!("[NOTE]: Selected network: \"{}\"", nw.get_public_name());
+            }
+            Ok(_) => {}
             Err(_) => {
                 if options.get_auto_mode() == &AutoMode::KnownOrFail {
                     eprintln!(
@@ -72,21 +137,53 @@ pub trait Selector: Sized + Debug
         selected_network_res
     }
 
-    fn prompt_user_for_selection<O>(&self, options: &O) -> Result<Self::Item, RuwiError>
+    // A selector closed by `--select-timeout` falls back to an automatic choice. Any other error
+    // is passed through.
+    fn select_after_timeout<O>(
+        &self,
+        options: &O,
+        err: RuwiError,
+    ) -> Result<SelectionOutcome<Self::Item>, RuwiError>
+    where
+        O: Global,
+    {
+        let fallback = match (&err.kind, options.get_select_timeout()) {
+            (RuwiErrorKind::SelectionTimedOut, Some(timeout)) => timeout.get_fallback(),
+            _ => return Err(err),
+        };
+        eprintln!("[NOTE]: {} Falling back to `{}`.", err.desc, fallback);
+        match fallback {
+            SelectTimeoutFallback::Known => self.select_first_known(options),
+            SelectTimeoutFallback::First => self.select_first(options),
+            SelectTimeoutFallback::Fail => Err(err),
+        }
+        .map(SelectionOutcome::from)
+    }
+
+    fn prompt_user_for_selection<O>(
+        &self,
+        options: &O,
+    ) -> Result<SelectionOutcome<Self::Item>, RuwiError>
     where
         O: Global,
     {
         let selector_output = self.run_manual_selector(options)?;
+        let (key_action, selected_line) = split_key_action(&selector_output);
 
-        let index = get_index_of_selected_item(&selector_output)?;
+        let index = match parse_selected_line(selected_line, &self.get_selection_options())? {
+            SelectedLine::Index(index) => index,
+            SelectedLine::Option(option) => {
+                return Ok(SelectionOutcome::from_selection_option(&option))
+            }
+        };
 
         self.get_networks()
             .get(index)
-            .map(Clone::clone)
+            .map(|item| SelectionOutcome::Selected(item.clone(), key_action))
             .ok_or_else(|| {
                 rerr!(
                     RuwiErrorKind::NoNetworksFoundMatchingSelectionResult,
-                    format!("No network matching {} found.", selector_output)
+                    format!("No network matching {} found.", selected_line)
                 )
             })
     }
@@ -95,7 +192,11 @@ pub trait Selector: Sized + Debug
     where
         O: Global,
     {
-        self.run_manual_selector_impl(options, pass_tokens_to_selection_program::<O>)
+        let target = self.get_selection_target(options);
+        let preview_details = self.get_preview_details();
+        self.run_manual_selector_impl(options, |opts, tokens| {
+            pass_tokens_to_selection_program(opts, &target, tokens, preview_details.as_deref())
+        })
     }
 
     fn run_manual_selector_impl<O, F>(&self, options: &O, selector: F) -> Result<String, RuwiError>
@@ -103,7 +204,7 @@ pub trait Selector: Sized + Debug
         O: Global,
         F: FnOnce(&O, &[String]) -> Result<String, RuwiError>,
     {
-        let selection_tokens = self.get_tokens_for_selection();
+        let selection_tokens = self.get_tokens_for_selection(options);
         selector(options, &selection_tokens).map(|x| x.trim().into())
     }
 
@@ -140,7 +241,7 @@ pub trait Selector: Sized + Debug
     }
 
     #[cfg(test)]
-    fn select_last<O>(&self, _options: &O) -> Result<Self::Item, RuwiError>
+    fn select_last<O>(&self, _options: &O) -> Result<SelectionOutcome<Self::Item>, RuwiError>
     where
         O: Global,
     {
@@ -153,20 +254,23 @@ pub trait Selector: Sized + Debug
                     "No networks found!"
                 )
             })
-            .map(Clone::clone)
+            .map(|nw| SelectionOutcome::from(nw.clone()))
     }
 
     #[cfg(test)]
-    fn select_refresh<O>(&self, _options: &O) -> Result<Self::Item, RuwiError>
+    fn select_refresh<O>(&self, _options: &O) -> Result<SelectionOutcome<Self::Item>, RuwiError>
     where
         O: Global,
     {
         dbg![&self];
-        Err(rerr!(RuwiErrorKind::RefreshRequested, "Refresh requested."))
+        Ok(SelectionOutcome::Refresh)
     }
 
     #[cfg(test)]
-    fn err_should_not_have_used_manual<O>(&self, _opt: &O) -> Result<Self::Item, RuwiError>
+    fn err_should_not_have_used_manual<O>(
+        &self,
+        _opt: &O,
+    ) -> Result<SelectionOutcome<Self::Item>, RuwiError>
     where
         O: Global,
     {
@@ -180,40 +284,56 @@ pub trait Selector: Sized + Debug
 
 fn pass_tokens_to_selection_program<O>(
     options: &O,
+    target: &str,
     selection_tokens: &[String],
+    preview_details: Option<&[String]>,
 ) -> Result<String, RuwiError>
 where
     O: Global,
 {
-    let target = options.get_post_parse_context().network_or_device.to_string();
-
+    let menu_prompt = format!("Select a {}: ", target);
     match options.get_selection_method() {
-        SelectionMethod::NoCurses => run_select_nocurses(
+        SelectionMethod::NoCurses => run_stdin_prompt_single_line(
             options,
             &format!("Select a {} (\"refresh\" or \".\" to rescan, Enter to select the top option): ", target),
             &selection_tokens,
-        ),
+        )
+        .map(|line| line.to_string()),
         SelectionMethod::Dmenu => run_dmenu(options, 
             &format!("Select a {}: ", target),
             &selection_tokens),
+        SelectionMethod::Bemenu => run_bemenu(options, &menu_prompt, selection_tokens),
+        SelectionMethod::Fuzzel => run_fuzzel(options, &menu_prompt, selection_tokens),
+        SelectionMethod::Rofi => run_rofi(options, &menu_prompt, selection_tokens),
+        SelectionMethod::Wofi => run_wofi(options, &menu_prompt, selection_tokens),
+        SelectionMethod::Custom => run_custom_command(options, &menu_prompt, selection_tokens),
         SelectionMethod::Fzf => run_fzf(
             options,
             &format!("Select a {} (ctrl-r or \"refresh\" to refresh results): ", target),
             &selection_tokens,
+            preview_details,
+        ),
+        SelectionMethod::Builtin => run_builtin_selector(
+            options,
+            &format!("Select a {} (ctrl-r to refresh results): ", target),
+            &selection_tokens,
         ),
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::additional_options_for_manual_selection::SelectionOption;
     use super::*;
     use crate::options::wifi::connect::WifiConnectOptions;
     use crate::options::wifi::WifiOptions;
-    use crate::sort_networks::SortedFilteredNetworks;
+    use crate::options::GlobalOptions;
+    use crate::select::display_template::DisplayTemplate;
+    use crate::sort_networks::{NetworkSortOrder, SortedFilteredNetworks};
     use crate::structs::AnnotatedWirelessNetwork;
     use crate::strum::AsStaticRef;
 
+    use std::time::Duration;
+
     static FIRST_NW_NAME: &str = "FIRSTNWLOL";
     static SECND_NW_NAME: &str = "SECNDNWWUT";
     static THIRD_NW_NAME: &str = "THIRDNWOKK";
@@ -223,7 +343,7 @@ mod tests {
             .iter()
             .map(|name| AnnotatedWirelessNetwork::from_essid_only(name))
             .collect::<Vec<AnnotatedWirelessNetwork>>();
-        SortedFilteredNetworks::new(networks)
+        SortedFilteredNetworks::new(networks, &NetworkSortOrder::default())
     }
 
     fn get_3_unknown_networks() -> SortedFilteredNetworks<AnnotatedWirelessNetwork> {
@@ -249,8 +369,10 @@ mod tests {
         let options = WifiConnectOptions::default();
         assert_eq![options.get_auto_mode(), &AutoMode::Ask];
         let networks = get_3_unknown_networks();
-        let nw = networks.select_network_impl(&options, SortedFilteredNetworks::select_first)?;
-        assert_eq![networks.get_networks()[0], nw];
+        let nw = networks.select_network_impl(&options, |nws, opts| {
+            nws.select_first(opts).map(SelectionOutcome::from)
+        })?;
+        assert_eq![SelectionOutcome::from(networks.get_networks()[0].clone()), nw];
         Ok(())
     }
 
@@ -260,7 +382,7 @@ mod tests {
         assert_eq![options.get_auto_mode(), &AutoMode::Ask];
         let networks = get_3_unknown_networks();
         let nw = networks.select_network_impl(&options, SortedFilteredNetworks::select_last)?;
-        assert_eq![networks.get_networks()[2], nw];
+        assert_eq![SelectionOutcome::from(networks.get_networks()[2].clone()), nw];
         Ok(())
     }
 
@@ -269,8 +391,9 @@ mod tests {
         let options = WifiConnectOptions::default();
         assert_eq![options.get_auto_mode(), &AutoMode::Ask];
         let networks = get_3_unknown_networks();
-        let res =
-            networks.select_network_impl(&options, SortedFilteredNetworks::select_first_known);
+        let res = networks.select_network_impl(&options, |nws, opts| {
+            nws.select_first_known(opts).map(SelectionOutcome::from)
+        });
         assert_eq![RuwiErrorKind::NoKnownNetworksFound, res.err().unwrap().kind];
     }
 
@@ -285,7 +408,7 @@ mod tests {
             &options,
             SortedFilteredNetworks::err_should_not_have_used_manual,
         )?;
-        assert_eq![networks.get_networks()[2], nw];
+        assert_eq![SelectionOutcome::from(networks.get_networks()[2].clone()), nw];
         Ok(())
     }
 
@@ -300,7 +423,7 @@ mod tests {
             &options,
             SortedFilteredNetworks::err_should_not_have_used_manual,
         )?;
-        assert_eq![networks.get_networks()[0], nw];
+        assert_eq![SelectionOutcome::from(networks.get_networks()[0].clone()), nw];
         Ok(())
     }
 
@@ -315,7 +438,7 @@ mod tests {
             &options,
             SortedFilteredNetworks::err_should_not_have_used_manual,
         )?;
-        assert_eq![networks.get_networks()[2], nw];
+        assert_eq![SelectionOutcome::from(networks.get_networks()[2].clone()), nw];
         Ok(())
     }
 
@@ -341,8 +464,10 @@ mod tests {
             .auto_mode(AutoMode::KnownOrAsk)
             .build();
         let networks = get_3_unknown_networks();
-        let nw = networks.select_network_impl(&options, SortedFilteredNetworks::select_first)?;
-        assert_eq![networks.get_networks()[0], nw];
+        let nw = networks.select_network_impl(&options, |nws, opts| {
+            nws.select_first(opts).map(SelectionOutcome::from)
+        })?;
+        assert_eq![SelectionOutcome::from(networks.get_networks()[0].clone()), nw];
         Ok(())
     }
 
@@ -352,10 +477,10 @@ mod tests {
         assert_eq![options.get_auto_mode(), &AutoMode::Ask];
         let networks = get_3_unknown_networks();
         assert![networks
-            .get_tokens_for_selection()
+            .get_tokens_for_selection(&options)
             .contains(&SelectionOption::Refresh.as_static().into())];
         let res = networks.select_network_impl(&options, SortedFilteredNetworks::select_refresh);
-        assert_eq![RuwiErrorKind::RefreshRequested, res.err().unwrap().kind];
+        assert_eq![SelectionOutcome::Refresh, res.unwrap()];
     }
 
     #[test]
@@ -382,32 +507,122 @@ mod tests {
 
     #[test]
     fn test_get_tokens_for_selection() {
-        let networks = SortedFilteredNetworks::new(vec![
-            AnnotatedWirelessNetwork::builder()
-                .essid("FAKE NEWS LOL OK")
-                .service_identifier(NetworkingServiceIdentifier::netctl_nw("some_id"))
-                .is_encrypted(true)
-                .build(),
-            AnnotatedWirelessNetwork::builder()
-                .essid("WOWWW OK FACEBO")
-                .service_identifier(None)
-                .is_encrypted(true)
-                .build(),
-            AnnotatedWirelessNetwork::builder()
-                .essid("LOOK, DISCOURSE")
-                .service_identifier(NetworkingServiceIdentifier::netctl_nw("some_id"))
-                .is_encrypted(false)
-                .build(),
-            AnnotatedWirelessNetwork::builder()
-                .essid("UWU MAMMMAAAAA")
-                .service_identifier(None)
-                .is_encrypted(false)
-                .build(),
-        ]);
-        let tokens = networks.get_tokens_for_selection();
+        let networks = SortedFilteredNetworks::new(
+            vec![
+                AnnotatedWirelessNetwork::builder()
+                    .essid("FAKE NEWS LOL OK")
+                    .service_identifier(NetworkingServiceIdentifier::netctl_nw("some_id"))
+                    .is_encrypted(true)
+                    .build(),
+                AnnotatedWirelessNetwork::builder()
+                    .essid("WOWWW OK FACEBO")
+                    .service_identifier(None)
+                    .is_encrypted(true)
+                    .build(),
+                AnnotatedWirelessNetwork::builder()
+                    .essid("LOOK, DISCOURSE")
+                    .service_identifier(NetworkingServiceIdentifier::netctl_nw("some_id"))
+                    .is_encrypted(false)
+                    .build(),
+                AnnotatedWirelessNetwork::builder()
+                    .essid("UWU MAMMMAAAAA")
+                    .service_identifier(None)
+                    .is_encrypted(false)
+                    .build(),
+            ],
+            &NetworkSortOrder::default(),
+        );
+        let tokens = networks.get_tokens_for_selection(&WifiConnectOptions::default());
+        assert![!tokens.contains(&"connect to hidden network...".to_string())];
         for (i, (nw, token)) in networks.get_networks().iter().zip(tokens).enumerate() {
             let expected_token = format!("{}) {}", i, nw.get_display_string());
             assert_eq![expected_token, token];
         }
     }
+
+    #[test]
+    fn test_get_tokens_for_selection_with_hidden_network() {
+        let networks = SortedFilteredNetworks::new(
+            vec![
+                AnnotatedWirelessNetwork::builder()
+                    .essid("FAKE NEWS LOL OK")
+                    .build(),
+                AnnotatedWirelessNetwork::builder().essid("").build(),
+            ],
+            &NetworkSortOrder::default(),
+        );
+        let tokens = networks.get_tokens_for_selection(&WifiConnectOptions::default());
+        assert_eq![tokens.len(), 5];
+        assert_eq![tokens[0], format!("0) {}", networks.get_networks()[0].get_display_string())];
+        assert![tokens.contains(&"connect to hidden network...".to_string())];
+    }
+
+    fn get_options_with_select_timeout(fallback: SelectTimeoutFallback) -> WifiConnectOptions {
+        let globals = GlobalOptions::builder()
+            .select_timeout(Some(SelectTimeout::new(Duration::from_secs(1), fallback)))
+            .build();
+        WifiConnectOptions::builder()
+            .wifi(WifiOptions::builder().globals(globals).build())
+            .build()
+    }
+
+    fn err_selection_timed_out<T>(
+        _networks: &SortedFilteredNetworks<AnnotatedWirelessNetwork>,
+        _options: &WifiConnectOptions,
+    ) -> Result<T, RuwiError> {
+        Err(rerr!(RuwiErrorKind::SelectionTimedOut, "Timed out in test."))
+    }
+
+    #[test]
+    fn test_select_timeout_fallback() -> Result<(), RuwiError> {
+        let networks = get_3_networks_last_known();
+
+        let options = get_options_with_select_timeout(SelectTimeoutFallback::Known);
+        let nw = networks.select_network_impl(&options, err_selection_timed_out)?;
+        assert_eq![SelectionOutcome::from(networks.get_networks()[2].clone()), nw];
+
+        let options = get_options_with_select_timeout(SelectTimeoutFallback::First);
+        let nw = networks.select_network_impl(&options, err_selection_timed_out)?;
+        assert_eq![SelectionOutcome::from(networks.get_networks()[0].clone()), nw];
+
+        let options = get_options_with_select_timeout(SelectTimeoutFallback::Fail);
+        let res = networks.select_network_impl(&options, err_selection_timed_out);
+        assert_eq![res.err().unwrap().kind, RuwiErrorKind::SelectionTimedOut];
+
+        let options = get_options_with_select_timeout(SelectTimeoutFallback::Known);
+        let res = get_3_unknown_networks().select_network_impl(&options, err_selection_timed_out);
+        assert_eq![res.err().unwrap().kind, RuwiErrorKind::NoKnownNetworksFound];
+        Ok(())
+    }
+
+    #[test]
+    fn test_other_errors_do_not_fall_back() {
+        let options = get_options_with_select_timeout(SelectTimeoutFallback::First);
+        let res = get_3_unknown_networks().select_network_impl(
+            &options,
+            SortedFilteredNetworks::err_should_not_have_used_manual,
+        );
+        assert_eq![res.err().unwrap().kind, RuwiErrorKind::TestUsedManualWhenNotExpected];
+    }
+
+    #[test]
+    fn test_display_template_keeps_index_prefix() -> Result<(), RuwiError> {
+        let template = DisplayTemplate::parse("{dbm}) {essid} {known}")?;
+        let globals = GlobalOptions::builder()
+            .display_template(Some(template))
+            .build();
+        let options = WifiConnectOptions::builder()
+            .wifi(WifiOptions::builder().globals(globals).build())
+            .build();
+        let networks = get_3_unknown_networks();
+
+        let tokens = networks.get_tokens_for_selection(&options);
+        assert![tokens[1].starts_with("1) ")];
+        assert![tokens[1].contains(networks.get_networks()[1].get_public_name())];
+        assert_eq![
+            parse_selected_line(&tokens[1], &networks.get_selection_options())?,
+            SelectedLine::Index(1)
+        ];
+        Ok(())
+    }
 }
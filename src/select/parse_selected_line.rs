@@ -0,0 +1,97 @@
+use crate::prelude::*;
+use super::additional_options_for_manual_selection::SelectionOption;
+
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum SelectedLine {
+    Index(usize),
+    Option(SelectionOption),
+}
+
+// Only the options which were actually offered to the user are accepted, so that e.g. typing
+// "switch interface..." into the nocurses prompt while selecting a bluetooth device fails.
+pub(super) fn parse_selected_line(
+    line: &str,
+    offered_options: &[SelectionOption],
+) -> Result<SelectedLine, RuwiError> {
+    let line = line.trim();
+
+    if line == "." {
+        return Ok(SelectedLine::Option(SelectionOption::Refresh));
+    }
+
+    if line == "" {
+        return Ok(SelectedLine::Index(0));
+    }
+
+    match SelectionOption::from_str(line) {
+        Ok(selection_option) if offered_options.contains(&selection_option) => {
+            Ok(SelectedLine::Option(selection_option))
+        }
+        _ => line
+            .split(") ")
+            .next()
+            .ok_or_else(|| get_line_parse_err(line))?
+            .parse::<usize>()
+            .map(SelectedLine::Index)
+            .or_else(|_| Err(get_line_parse_err(line))),
+    }
+}
+
+fn get_line_parse_err(line: &str) -> RuwiError {
+    rerr!(
+        RuwiErrorKind::FailedToParseSelectedLine,
+        format!("Failed to parse line {}", line)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_indices() -> Result<(), RuwiError> {
+        let offered_options = [
+            SelectionOption::Refresh,
+            SelectionOption::ConnectToHiddenNetwork,
+        ];
+        let test_cases: Vec<(&str, Result<SelectedLine, RuwiError>)> = vec![
+            ("1) jfdlskajfdlksa", Ok(SelectedLine::Index(1))),
+            ("0) jfdlskajfdlksa", Ok(SelectedLine::Index(0))),
+            ("22) jfdlskajfdlksa", Ok(SelectedLine::Index(22))),
+            ("69) 54) jfdlskajfdlksa", Ok(SelectedLine::Index(69))),
+            ("4000) jfdlskajfdlksa", Ok(SelectedLine::Index(4000))),
+            ("4000000000) jfdlskajfdlksa", Ok(SelectedLine::Index(4_000_000_000))),
+            ("-12) negawifi", Err(get_line_parse_err("-12) negawifi"))),
+            ("jf jfjf", Err(get_line_parse_err("jf jfjf"))),
+            ("!@&*(#@!", Err(get_line_parse_err("!@&*(#@!"))),
+            ("refresh", Ok(SelectedLine::Option(SelectionOption::Refresh))),
+            (".", Ok(SelectedLine::Option(SelectionOption::Refresh))),
+            (
+                "connect to hidden network...",
+                Ok(SelectedLine::Option(SelectionOption::ConnectToHiddenNetwork)),
+            ),
+            (
+                "switch interface...",
+                Err(get_line_parse_err("switch interface...")),
+            ),
+            (" ", Ok(SelectedLine::Index(0))),
+            ("\n", Ok(SelectedLine::Index(0))),
+            ("\t", Ok(SelectedLine::Index(0))),
+            ("\n\n\t \t", Ok(SelectedLine::Index(0))),
+            ("\n\n\t1\t", Ok(SelectedLine::Index(1))),
+            ("\n\t12\t", Ok(SelectedLine::Index(12))),
+        ];
+
+        for (line, res) in test_cases {
+            dbg!(&line, &res);
+            match parse_selected_line(line, &offered_options) {
+                Ok(val) => assert_eq![res?, val],
+                Err(err) => assert_eq![res.err().unwrap().kind, err.kind],
+            }
+        }
+        Ok(())
+    }
+
+}
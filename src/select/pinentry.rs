@@ -0,0 +1,99 @@
+// pinentry speaks the Assuan protocol over stdin/stdout. It handles one command per line, so
+// all of them can be sent up front. GETPIN answers with a "D <pin>" data line followed by "OK",
+// or with "ERR <code> <description>" if the dialog was cancelled.
+use crate::prelude::*;
+use crate::run_commands::PromptCommandRunner;
+
+use zeroize::Zeroizing;
+
+pub(crate) fn run_pinentry<O>(
+    options: &O,
+    description: &str,
+    prompt: &str,
+) -> Result<EncryptionKey, RuwiError>
+where
+    O: Global,
+{
+    let commands = vec![
+        format!("SETTITLE {}", escape_for_assuan("ruwi")),
+        format!("SETDESC {}", escape_for_assuan(description)),
+        format!("SETPROMPT {}", escape_for_assuan(prompt)),
+        "GETPIN".to_string(),
+        "BYE".to_string(),
+    ];
+    let output = Zeroizing::new(
+        PromptCommandRunner::new(options, "pinentry", &[], &commands)
+            .with_sensitive_output()
+            .run()?,
+    );
+    parse_pinentry_output(&output)
+}
+
+fn parse_pinentry_output(output: &str) -> Result<EncryptionKey, RuwiError> {
+    for line in output.lines() {
+        if let Some(data) = line.strip_prefix("D ") {
+            return Ok(EncryptionKey::from(unescape_from_assuan(data)));
+        }
+        if let Some(err) = line.strip_prefix("ERR ") {
+            return Err(rerr!(
+                RuwiErrorKind::PinentryFailed,
+                format!("pinentry did not return a password: {}", err)
+            ));
+        }
+    }
+    // An empty password is sent back as just "OK", with no data line.
+    Ok(EncryptionKey::from(""))
+}
+
+// Assuan lines can't contain raw newlines, and "%" starts an escape, so those get encoded.
+fn escape_for_assuan(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\n', "%0A")
+        .replace('\r', "%0D")
+}
+
+fn unescape_from_assuan(text: &str) -> Zeroizing<String> {
+    let mut bytes = Zeroizing::new(Vec::with_capacity(text.len()));
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let escaped = tail
+            .get(..2)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        if let (b'%', Some(decoded)) = (byte, escaped) {
+            bytes.push(decoded);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    Zeroizing::new(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pinentry_output() -> Result<(), RuwiError> {
+        let output = "OK Pleased to meet you\nOK\nOK\nOK\nD hunter%2522\nOK\nOK closing connection";
+        assert_eq![parse_pinentry_output(output)?.as_str(), "hunter%22"];
+
+        let output = "OK Pleased to meet you\nOK\nOK\nOK\nOK\nOK closing connection";
+        assert_eq![parse_pinentry_output(output)?.as_str(), ""];
+
+        let output = "OK Pleased to meet you\nOK\nOK\nOK\nERR 83886179 Operation cancelled <Pinentry>";
+        assert_eq![
+            parse_pinentry_output(output).err().unwrap().kind,
+            RuwiErrorKind::PinentryFailed
+        ];
+        Ok(())
+    }
+
+    #[test]
+    fn test_assuan_escaping() {
+        assert_eq![escape_for_assuan("100%\nsure"), "100%25%0Asure"];
+        assert_eq![unescape_from_assuan("a%25b%0Ac%zz%").as_str(), "a%b\nc%zz%"];
+    }
+}
@@ -0,0 +1,46 @@
+use super::external_selection_programs::{
+    run_bemenu, run_custom_command, run_dmenu, run_fuzzel, run_rofi, run_stdin_prompt_single_line,
+    run_wofi,
+};
+
+use crate::enums::SelectionMethod;
+use crate::prelude::*;
+
+// Anything other than a "yes" (including just pressing enter) is a no.
+pub(crate) fn prompt_for_confirmation<O>(options: &O, question: &str) -> Result<bool, RuwiError>
+where
+    O: Global,
+{
+    let prompt = format!("{} [y/N]: ", question);
+    let answer = match options.get_selection_method() {
+        SelectionMethod::Dmenu => run_dmenu(options, &prompt, &[]),
+        SelectionMethod::Bemenu => run_bemenu(options, &prompt, &[]),
+        SelectionMethod::Fuzzel => run_fuzzel(options, &prompt, &[]),
+        SelectionMethod::Rofi => run_rofi(options, &prompt, &[]),
+        SelectionMethod::Wofi => run_wofi(options, &prompt, &[]),
+        SelectionMethod::Custom => run_custom_command(options, &prompt, &[]),
+        SelectionMethod::Builtin | SelectionMethod::Fzf | SelectionMethod::NoCurses => {
+            run_stdin_prompt_single_line(options, &prompt, &[]).map(|line| line.to_string())
+        }
+    }?;
+    Ok(is_yes(&answer))
+}
+
+fn is_yes(answer: &str) -> bool {
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_yes() {
+        for answer in &["y", "Y", "yes", "YES\n", " yes "] {
+            assert![is_yes(answer), "{:?}", answer];
+        }
+        for answer in &["", "\n", "n", "no", "yep", "y e s"] {
+            assert![!is_yes(answer), "{:?}", answer];
+        }
+    }
+}
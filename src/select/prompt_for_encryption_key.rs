@@ -1,4 +1,10 @@
-use super::external_selection_programs::{run_dmenu, run_stdin_prompt_single_line};
+use super::builtin_selector::run_builtin_password_prompt;
+use super::external_selection_programs::{
+    run_bemenu_password, run_custom_command_password, run_dmenu_password, run_fuzzel_password,
+    run_rofi_password, run_wofi_password,
+};
+use super::pinentry::run_pinentry;
+use super::terminal_password_prompt::run_terminal_password_prompt;
 
 use crate::enums::SelectionMethod;
 use crate::prelude::*;
@@ -6,16 +12,49 @@ use crate::prelude::*;
 pub(crate) fn prompt_for_encryption_key<O>(
     options: &O,
     network_name: &str,
-) -> Result<String, RuwiError>
+) -> Result<EncryptionKey, RuwiError>
 where
-    O: Global,
+    O: Global + WifiConnect,
 {
+    let key = run_password_prompt(options, network_name, "Password")?;
+    if options.get_confirm_password() {
+        let confirmation = run_password_prompt(options, network_name, "Confirm password")?;
+        if key != confirmation {
+            return Err(rerr!(
+                RuwiErrorKind::PasswordConfirmationDidNotMatch,
+                format!("The passwords given for \"{}\" did not match.", network_name)
+            ));
+        }
+    }
+    Ok(key)
+}
+
+fn run_password_prompt<O>(
+    options: &O,
+    network_name: &str,
+    prompt_name: &str,
+) -> Result<EncryptionKey, RuwiError>
+where
+    O: Global + WifiConnect,
+{
+    if options.get_password_prompt_method() == &PasswordPromptMethod::Pinentry {
+        let description = format!("Enter the password for the wifi network \"{}\".", network_name);
+        return run_pinentry(options, &description, &format!("{}:", prompt_name));
+    }
+
+    let prompt = format!("{} for {}: ", prompt_name, network_name);
     match options.get_selection_method() {
-        SelectionMethod::Dmenu => {
-            run_dmenu(options, &format!("Password for {}: ", network_name), &[])
+        SelectionMethod::Dmenu => run_dmenu_password(options, &prompt).map(EncryptionKey::from),
+        SelectionMethod::Bemenu => run_bemenu_password(options, &prompt).map(EncryptionKey::from),
+        SelectionMethod::Fuzzel => run_fuzzel_password(options, &prompt).map(EncryptionKey::from),
+        SelectionMethod::Rofi => run_rofi_password(options, &prompt).map(EncryptionKey::from),
+        SelectionMethod::Wofi => run_wofi_password(options, &prompt).map(EncryptionKey::from),
+        SelectionMethod::Custom => {
+            run_custom_command_password(options, &prompt).map(EncryptionKey::from)
         }
         SelectionMethod::Fzf | SelectionMethod::NoCurses => {
-            run_stdin_prompt_single_line(options, &format!("Password for {}: ", network_name), &[])
+            run_terminal_password_prompt(options, &prompt)
         }
+        SelectionMethod::Builtin => run_builtin_password_prompt(options, &prompt),
     }
 }
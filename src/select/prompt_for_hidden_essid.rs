@@ -0,0 +1,53 @@
+use super::external_selection_programs::{
+    run_bemenu, run_custom_command, run_dmenu, run_fuzzel, run_rofi, run_stdin_prompt_single_line,
+    run_wofi,
+};
+
+use crate::enums::SelectionMethod;
+use crate::prelude::*;
+
+const HIDDEN_ESSID_PROMPT: &str = "Name (SSID) of hidden network: ";
+const MANUAL_ESSID_PROMPT: &str = "Name (SSID) of network: ";
+
+pub(crate) fn prompt_for_hidden_essid<O>(options: &O) -> Result<String, RuwiError>
+where
+    O: Global,
+{
+    let essid = prompt_for_essid_impl(options, HIDDEN_ESSID_PROMPT)?;
+
+    if essid.is_empty() {
+        Err(rerr!(
+            RuwiErrorKind::HiddenNetworkWithoutESSID,
+            "No name given for hidden network."
+        ))
+    } else {
+        Ok(essid)
+    }
+}
+
+// Unlike for hidden networks, an empty name here is not an error, the caller can just go back
+// to selection.
+pub(crate) fn prompt_for_essid<O>(options: &O) -> Result<String, RuwiError>
+where
+    O: Global,
+{
+    prompt_for_essid_impl(options, MANUAL_ESSID_PROMPT)
+}
+
+fn prompt_for_essid_impl<O>(options: &O, prompt: &str) -> Result<String, RuwiError>
+where
+    O: Global,
+{
+    let essid = match options.get_selection_method() {
+        SelectionMethod::Dmenu => run_dmenu(options, prompt, &[]),
+        SelectionMethod::Bemenu => run_bemenu(options, prompt, &[]),
+        SelectionMethod::Fuzzel => run_fuzzel(options, prompt, &[]),
+        SelectionMethod::Rofi => run_rofi(options, prompt, &[]),
+        SelectionMethod::Wofi => run_wofi(options, prompt, &[]),
+        SelectionMethod::Custom => run_custom_command(options, prompt, &[]),
+        SelectionMethod::Builtin | SelectionMethod::Fzf | SelectionMethod::NoCurses => {
+            run_stdin_prompt_single_line(options, prompt, &[]).map(|line| line.to_string())
+        }
+    }?;
+    Ok(essid.trim_end_matches('\n').to_string())
+}
@@ -0,0 +1,32 @@
+use super::additional_options_for_manual_selection::SelectionOption;
+use super::key_actions::SelectionKeyAction;
+
+// What came out of selection: either an item (along with the key it was accepted with, if
+// any), or one of the additional options offered below the list of items.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SelectionOutcome<T> {
+    Selected(T, Option<SelectionKeyAction>),
+    Refresh,
+    EnterEssidManually,
+    ConnectToHiddenNetwork,
+    ForgetKnownNetwork,
+    SwitchInterface,
+}
+
+impl<T> From<T> for SelectionOutcome<T> {
+    fn from(item: T) -> Self {
+        Self::Selected(item, None)
+    }
+}
+
+impl<T> SelectionOutcome<T> {
+    pub(super) fn from_selection_option(option: &SelectionOption) -> Self {
+        match option {
+            SelectionOption::Refresh => Self::Refresh,
+            SelectionOption::EnterEssidManually => Self::EnterEssidManually,
+            SelectionOption::ConnectToHiddenNetwork => Self::ConnectToHiddenNetwork,
+            SelectionOption::ForgetKnownNetwork => Self::ForgetKnownNetwork,
+            SelectionOption::SwitchInterface => Self::SwitchInterface,
+        }
+    }
+}
@@ -0,0 +1,105 @@
+// Reads a password from the terminal with echo turned off, like `read -s` in a shell. The
+// terminal's line editing is left alone, so backspace etc. work as usual.
+use super::external_selection_programs::run_stdin_prompt_single_line;
+use crate::prelude::*;
+
+use nix::sys::termios::{tcgetattr, tcsetattr, LocalFlags, SetArg, Termios};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::os::unix::io::AsRawFd;
+use zeroize::Zeroizing;
+
+const TTY_PATH: &str = "/dev/tty";
+// The buffer is allocated once up front, since growing it would leave copies of the password
+// behind in memory that was already freed.
+const MAX_PASSWORD_BYTES: usize = 1024;
+
+// Without a controlling terminal (e.g. when run from a script), there is nothing to hide the
+// input from, so the password is read from stdin instead.
+pub(crate) fn run_terminal_password_prompt<O>(
+    options: &O,
+    prompt: &str,
+) -> Result<EncryptionKey, RuwiError>
+where
+    O: Global,
+{
+    match NoEchoTerminal::open() {
+        Ok(mut terminal) => terminal
+            .read_password(prompt)
+            .map_err(err_terminal_password_prompt_failed),
+        Err(_) => run_stdin_prompt_single_line(options, prompt, &[]).map(EncryptionKey::from),
+    }
+}
+
+struct NoEchoTerminal {
+    tty: File,
+    original_termios: Termios,
+}
+
+impl NoEchoTerminal {
+    fn open() -> io::Result<Self> {
+        let tty = OpenOptions::new().read(true).write(true).open(TTY_PATH)?;
+        let original_termios = tcgetattr(tty.as_raw_fd()).map_err(nix_to_io_err)?;
+        let mut no_echo_termios = original_termios.clone();
+        no_echo_termios.local_flags.remove(LocalFlags::ECHO);
+        tcsetattr(tty.as_raw_fd(), SetArg::TCSAFLUSH, &no_echo_termios).map_err(nix_to_io_err)?;
+        Ok(Self {
+            tty,
+            original_termios,
+        })
+    }
+
+    fn read_password(&mut self, prompt: &str) -> io::Result<EncryptionKey> {
+        self.tty.write_all(prompt.as_bytes())?;
+        self.tty.flush()?;
+
+        let line = self.read_line_unbuffered()?;
+        // The newline typed by the user wasn't echoed either.
+        self.tty.write_all(b"\n")?;
+
+        let password = std::str::from_utf8(&line)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        let mut key = Zeroizing::new(String::with_capacity(password.len()));
+        key.push_str(password);
+        Ok(EncryptionKey::from(key))
+    }
+
+    // Reads byte by byte, so that no part of the password is left in a reader's buffer.
+    fn read_line_unbuffered(&mut self) -> io::Result<Zeroizing<Vec<u8>>> {
+        let mut line = Zeroizing::new(Vec::with_capacity(MAX_PASSWORD_BYTES));
+        let mut byte = Zeroizing::new([0_u8; 1]);
+        while self.tty.read(&mut byte[..])? == 1 && byte[0] != b'\n' {
+            if line.len() == MAX_PASSWORD_BYTES {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "Passwords longer than {} bytes are not supported.",
+                        MAX_PASSWORD_BYTES
+                    ),
+                ));
+            }
+            line.push(byte[0]);
+        }
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        Ok(line)
+    }
+}
+
+impl Drop for NoEchoTerminal {
+    fn drop(&mut self) {
+        let _ = tcsetattr(self.tty.as_raw_fd(), SetArg::TCSANOW, &self.original_termios);
+    }
+}
+
+fn nix_to_io_err(e: nix::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, e.to_string())
+}
+
+fn err_terminal_password_prompt_failed(e: io::Error) -> RuwiError {
+    rerr!(
+        RuwiErrorKind::TerminalPasswordPromptFailed,
+        format!("Failed to read a password from {}: {}", TTY_PATH, e)
+    )
+}
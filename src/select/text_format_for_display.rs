@@ -1,14 +1,51 @@
 use crate::bluetooth::BluetoothDevice;
+use crate::interface_management::ip_interfaces::{LinuxIPInterface, WifiIPInterface};
+use crate::iwd::get_iwd_known_network_path;
+use crate::netctl::DEFAULT_NETCTL_CFG_DIR;
 use crate::prelude::*;
 
 pub static KNOWN_TOKEN: &str = "K";
 pub static OPEN_TOKEN: &str = "O";
 
+const SIGNAL_BAR_ICONS: [char; 4] = ['▂', '▄', '▆', '█'];
+const KNOWN_ICON: &str = "★";
+const ENCRYPTED_ICON: &str = "🔒";
+const OPEN_ICON: &str = "🔓";
+
 impl Selectable for AnnotatedWirelessNetwork {
     fn get_display_string(&self) -> String {
         let tags = self.get_tags_string();
         let strength = self.get_strenth_string();
-        format!("{}{}{}", strength, self.get_public_name(), tags)
+        let details = self.get_details_string();
+        format!("{}{}{}{}", strength, self.get_public_name(), details, tags)
+    }
+
+    fn get_display_field(&self, field: DisplayField, use_icons: bool) -> Option<String> {
+        match field {
+            DisplayField::SignalBars => self
+                .get_signal_strength()
+                .map(|st| get_signal_bars(st, use_icons)),
+            DisplayField::Dbm => self.get_signal_strength().map(|st| st.get_dbm().to_string()),
+            DisplayField::Quality => self
+                .get_signal_strength()
+                .map(|st| st.get_quality().to_string()),
+            DisplayField::Essid => Some(self.get_public_name().to_string()),
+            DisplayField::Band => self.get_band().map(|x| x.to_string()),
+            DisplayField::Security if use_icons => Some(
+                if self.is_encrypted() { ENCRYPTED_ICON } else { OPEN_ICON }.to_string(),
+            ),
+            DisplayField::Security => Some(self.get_security_string()),
+            DisplayField::Known => get_known_string(self.is_known(), use_icons),
+            DisplayField::Bssid => self._get_bssid().cloned(),
+        }
+    }
+
+    fn get_preview_string(&self) -> Option<String> {
+        Some(self.get_preview_fields()
+            .iter()
+            .map(|(name, value)| format!("{:<10}{}", name, value))
+            .collect::<Vec<_>>()
+            .join("\n"))
     }
 }
 
@@ -16,12 +53,65 @@ impl Selectable for AnnotatedWiredNetwork {
     fn get_display_string(&self) -> String {
         self.get_public_name().to_string()
     }
+
+    fn get_display_field(&self, field: DisplayField, use_icons: bool) -> Option<String> {
+        match field {
+            DisplayField::Essid => Some(self.get_public_name().to_string()),
+            DisplayField::Known => get_known_string(self.is_known(), use_icons),
+            _ => None,
+        }
+    }
 }
 
 impl Selectable for BluetoothDevice {
     fn get_display_string(&self) -> String {
         self.get_name().to_string()
     }
+
+    fn get_display_field(&self, field: DisplayField, use_icons: bool) -> Option<String> {
+        match field {
+            DisplayField::Essid => Some(self.get_name().to_string()),
+            DisplayField::Known => get_known_string(self.is_known(), use_icons),
+            DisplayField::Bssid => Some(self.get_addr().to_string()),
+            _ => None,
+        }
+    }
+}
+
+impl Selectable for WifiIPInterface {
+    fn get_display_string(&self) -> String {
+        self.get_ifname().to_string()
+    }
+
+    fn get_display_field(&self, field: DisplayField, _use_icons: bool) -> Option<String> {
+        match field {
+            DisplayField::Essid => Some(self.get_ifname().to_string()),
+            _ => None,
+        }
+    }
+}
+
+// Four bars, like nmcli: each one is a quarter of the signal quality.
+fn get_signal_bars(signal_strength: SignalStrength, use_icons: bool) -> String {
+    let num_bars = (usize::from(signal_strength.get_quality()) * 4 + 99) / 100;
+    SIGNAL_BAR_ICONS
+        .iter()
+        .enumerate()
+        .map(|(i, icon)| match (i < num_bars, use_icons) {
+            (true, true) => *icon,
+            (true, false) => '*',
+            (false, true) => '_',
+            (false, false) => '-',
+        })
+        .collect()
+}
+
+fn get_known_string(is_known: bool, use_icons: bool) -> Option<String> {
+    match (is_known, use_icons) {
+        (true, true) => Some(KNOWN_ICON.to_string()),
+        (true, false) => Some("known".to_string()),
+        (false, _) => None,
+    }
 }
 
 impl AnnotatedWirelessNetwork {
@@ -38,9 +128,75 @@ impl AnnotatedWirelessNetwork {
         }
     }
 
+    pub(crate) fn get_details_string(&self) -> String {
+        let details = vec![
+            self.get_security().map(|x| x.to_string()),
+            self.get_band().map(|x| x.to_string()),
+            self.get_channel().map(|x| format!("ch {}", x)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        if details.is_empty() {
+            "".to_string()
+        } else {
+            format!(" ({})", details.join(", "))
+        }
+    }
+
+    fn get_preview_fields(&self) -> Vec<(&'static str, String)> {
+        let unknown = || "-".to_string();
+        let signal = self.get_signal_strength().map_or_else(unknown, |st| {
+            format!("{} dBm ({}%)", st.get_dbm(), st.get_quality())
+        });
+        let band = vec![
+            self.get_band().map(|x| x.to_string()),
+            self.get_channel().map(|x| format!("channel {}", x)),
+            self.get_frequency().map(|x| format!("{} MHz", x)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+        let security = self.get_security_string();
+        let (profile, config_path) = match self.get_service_identifier() {
+            Some(NetworkingServiceIdentifier::Netctl(ident)) => (
+                format!("netctl ({})", ident),
+                format!("{}{}", DEFAULT_NETCTL_CFG_DIR, ident),
+            ),
+            Some(NetworkingServiceIdentifier::NetworkManager) => (
+                format!("NetworkManager ({})", self.get_public_name()),
+                unknown(),
+            ),
+            Some(NetworkingServiceIdentifier::Iwd) => (
+                "iwd".to_string(),
+                get_iwd_known_network_path(self.get_public_name(), self.is_encrypted()),
+            ),
+            None => ("not known".to_string(), unknown()),
+        };
+
+        vec![
+            ("ESSID", self.get_public_name().to_string()),
+            ("BSSID", self._get_bssid().cloned().unwrap_or_else(unknown)),
+            ("Signal", signal),
+            ("Band", if band.is_empty() { unknown() } else { band.join(", ") }),
+            ("Security", security),
+            ("Profile", profile),
+            ("Config", config_path),
+        ]
+    }
+
+    fn get_security_string(&self) -> String {
+        match self.get_security() {
+            Some(security) => security.to_string(),
+            None if self.is_encrypted() => "encrypted".to_string(),
+            None => "open".to_string(),
+        }
+    }
+
     pub(crate) fn get_strenth_string(&self) -> String {
         match self.get_signal_strength() {
-            Some(st) => format!("[{}] ", st),
+            Some(st) => format!("[{}%] ", st.get_quality()),
             None => "".to_string(),
         }
     }
@@ -53,7 +209,7 @@ mod tests {
     fn verify_display_strength_and_tags(
         is_known: bool,
         is_open: bool,
-        signal_strength: Option<i32>,
+        signal_strength: Option<SignalStrength>,
     ) {
         let essid = "YEEEEEEE".to_string();
         let service_identifier = if is_known {
@@ -79,17 +235,66 @@ mod tests {
         assert_eq![is_open, tags_string.contains(&OPEN_TOKEN)];
 
         if let Some(st) = signal_strength {
-            assert![strength_string.contains(&format!("{}", st))];
+            assert![strength_string.contains(&format!("{}%", st.get_quality()))];
         } else {
             assert![strength_string.is_empty()];
         }
     }
 
+    #[test]
+    fn test_display_details() {
+        let nw = AnnotatedWirelessNetwork::builder()
+            .essid("Citadel")
+            .is_encrypted(true)
+            .signal_strength(Some(SignalStrength::from_dbm(-74)))
+            .channel(Some(36))
+            .frequency(Some(5180))
+            .security(Some(WifiSecurity::Wpa3Sae))
+            .build();
+        assert_eq![nw.get_display_string(), "[52%] Citadel (WPA3-SAE, 5GHz, ch 36)"];
+
+        let nw = AnnotatedWirelessNetwork::builder()
+            .essid("xfinitywifi")
+            .security(Some(WifiSecurity::Open))
+            .build();
+        assert_eq![nw.get_display_string(), "xfinitywifi (Open) [O]"];
+    }
+
+    #[test]
+    fn test_preview_string() {
+        let nw = AnnotatedWirelessNetwork::builder()
+            .essid("Citadel")
+            .bssid(Some("f4:28:53:fe:a5:d0".to_string()))
+            .is_encrypted(true)
+            .signal_strength(Some(SignalStrength::from_dbm(-74)))
+            .channel(Some(36))
+            .frequency(Some(5180))
+            .security(Some(WifiSecurity::Wpa2Psk))
+            .service_identifier(NetworkingServiceIdentifier::netctl_nw("ruwi_citadel"))
+            .build();
+        assert_eq![
+            nw.get_preview_string().unwrap(),
+            "ESSID     Citadel\n\
+             BSSID     f4:28:53:fe:a5:d0\n\
+             Signal    -74 dBm (52%)\n\
+             Band      5GHz, channel 36, 5180 MHz\n\
+             Security  WPA2-PSK\n\
+             Profile   netctl (ruwi_citadel)\n\
+             Config    /etc/netctl/ruwi_citadel"
+        ];
+
+        let nw = AnnotatedWirelessNetwork::from_essid_only("xfinitywifi");
+        let preview = nw.get_preview_string().unwrap();
+        assert![preview.contains("Signal    -\n")];
+        assert![preview.contains("Security  open\n")];
+        assert![preview.ends_with("Profile   not known\nConfig    -")];
+    }
+
     #[test]
     fn test_display() {
         verify_display_strength_and_tags(true, true, None);
         verify_display_strength_and_tags(false, true, None);
-        verify_display_strength_and_tags(true, false, Some(32));
-        verify_display_strength_and_tags(false, false, Some(-90));
+        verify_display_strength_and_tags(true, false, Some(SignalStrength::from_dbm(-58)));
+        verify_display_strength_and_tags(false, false, Some(SignalStrength::from_quality(20)));
     }
 }
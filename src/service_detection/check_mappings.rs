@@ -1,4 +1,7 @@
-use super::{HasSystemCheckMapping, SystemCheckPredicate, WifiConnectionType, WifiScanType, WiredConnectionType};
+use super::{
+    HasSystemCheckMapping, SelectionMethod, SystemCheckPredicate, WifiConnectionType, WifiScanType,
+    WiredConnectionType,
+};
 
 // if connection type isn't given:
 //    check NetworkingServices installed/running, pick a WifiConnectionType
@@ -6,6 +9,10 @@ use super::{HasSystemCheckMapping, SystemCheckPredicate, WifiConnectionType, Wif
 //
 // if scanning type isn't given:
 //    check NetworkingServices installed/running, check scanning binaries installed/running, pick a WifiScanType
+//
+// if selection method isn't given:
+//    prefer terminal selectors when attached to a terminal, otherwise pick a graphical
+//    selector which is installed and works with the running display server
 
 impl HasSystemCheckMapping for WiredConnectionType {
     fn get_system_check_mapping() -> Vec<(SystemCheckPredicate, Self)> {
@@ -25,6 +32,7 @@ impl HasSystemCheckMapping for WifiConnectionType {
         vec![
             (SystemCheckPredicate::NetworkManagerRunning, Self::Nmcli),
             (SystemCheckPredicate::NetctlRunning, Self::Netctl),
+            (SystemCheckPredicate::IwdRunning, Self::Iwd),
             (SystemCheckPredicate::NetctlInstalled, Self::Netctl),
             (SystemCheckPredicate::NetworkManagerInstalled, Self::Nmcli),
         ]
@@ -33,6 +41,25 @@ impl HasSystemCheckMapping for WifiConnectionType {
 
 impl HasSystemCheckMapping for WifiScanType {
     fn get_system_check_mapping() -> Vec<(SystemCheckPredicate, Self)> {
-        vec![(SystemCheckPredicate::NetworkManagerRunning, Self::Nmcli)]
+        vec![
+            (SystemCheckPredicate::NetworkManagerRunning, Self::Nmcli),
+            (SystemCheckPredicate::IwdRunning, Self::Iwctl),
+            (SystemCheckPredicate::IWInstalled, Self::IW),
+            (SystemCheckPredicate::IWListInstalled, Self::IWList),
+        ]
+    }
+}
+
+impl HasSystemCheckMapping for SelectionMethod {
+    fn get_system_check_mapping() -> Vec<(SystemCheckPredicate, Self)> {
+        vec![
+            (SystemCheckPredicate::FzfInstalledInTerminal, Self::Fzf),
+            (SystemCheckPredicate::TerminalAttached, Self::Builtin),
+            (SystemCheckPredicate::FuzzelInstalledOnWayland, Self::Fuzzel),
+            (SystemCheckPredicate::WofiInstalledOnWayland, Self::Wofi),
+            (SystemCheckPredicate::BemenuInstalledWithDisplay, Self::Bemenu),
+            (SystemCheckPredicate::RofiInstalledOnX, Self::Rofi),
+            (SystemCheckPredicate::DmenuInstalledOnX, Self::Dmenu),
+        ]
     }
 }
@@ -71,10 +71,22 @@ where
 pub(crate) trait SystemChecksImpl {
     fn check_networkmanager_running(&self) -> bool;
     fn check_netctl_running(&self) -> bool;
+    fn check_iwd_running(&self) -> bool;
     fn check_netctl_installed(&self) -> bool;
     fn check_networkmanager_installed(&self) -> bool;
     fn check_dhclient_installed(&self) -> bool;
     fn check_dhcpcd_installed(&self) -> bool;
+    fn check_iw_installed(&self) -> bool;
+    fn check_iwlist_installed(&self) -> bool;
+    fn check_bemenu_installed(&self) -> bool;
+    fn check_dmenu_installed(&self) -> bool;
+    fn check_fuzzel_installed(&self) -> bool;
+    fn check_fzf_installed(&self) -> bool;
+    fn check_rofi_installed(&self) -> bool;
+    fn check_wofi_installed(&self) -> bool;
+    fn check_terminal_attached(&self) -> bool;
+    fn check_wayland_running(&self) -> bool;
+    fn check_x_running(&self) -> bool;
 }
 
 use strum_macros::Display;
@@ -82,20 +94,53 @@ use strum_macros::Display;
 pub(crate) enum SystemCheckPredicate {
     NetworkManagerRunning,
     NetctlRunning,
+    IwdRunning,
     NetctlInstalled,
     NetworkManagerInstalled,
     DhclientInstalled,
     DhcpcdInstalled,
+    IWInstalled,
+    IWListInstalled,
+    FzfInstalledInTerminal,
+    TerminalAttached,
+    FuzzelInstalledOnWayland,
+    WofiInstalledOnWayland,
+    BemenuInstalledWithDisplay,
+    RofiInstalledOnX,
+    DmenuInstalledOnX,
 }
 
 fn check_predicate<T: SystemChecksImpl>(checker: &T, check: &SystemCheckPredicate) -> bool {
     match check {
         SystemCheckPredicate::NetworkManagerRunning => checker.check_networkmanager_running(),
         SystemCheckPredicate::NetctlRunning => checker.check_netctl_running(),
+        SystemCheckPredicate::IwdRunning => checker.check_iwd_running(),
         SystemCheckPredicate::NetctlInstalled => checker.check_netctl_installed(),
         SystemCheckPredicate::NetworkManagerInstalled => checker.check_networkmanager_installed(),
         SystemCheckPredicate::DhclientInstalled => checker.check_dhclient_installed(),
         SystemCheckPredicate::DhcpcdInstalled => checker.check_dhcpcd_installed(),
+        SystemCheckPredicate::IWInstalled => checker.check_iw_installed(),
+        SystemCheckPredicate::IWListInstalled => checker.check_iwlist_installed(),
+        SystemCheckPredicate::FzfInstalledInTerminal => {
+            checker.check_terminal_attached() && checker.check_fzf_installed()
+        }
+        SystemCheckPredicate::TerminalAttached => checker.check_terminal_attached(),
+        SystemCheckPredicate::FuzzelInstalledOnWayland => {
+            checker.check_wayland_running() && checker.check_fuzzel_installed()
+        }
+        SystemCheckPredicate::WofiInstalledOnWayland => {
+            checker.check_wayland_running() && checker.check_wofi_installed()
+        }
+        SystemCheckPredicate::BemenuInstalledWithDisplay => {
+            (checker.check_wayland_running() || checker.check_x_running())
+                && checker.check_bemenu_installed()
+        }
+        SystemCheckPredicate::RofiInstalledOnX => {
+            checker.check_x_running() && checker.check_rofi_installed()
+        }
+        SystemCheckPredicate::DmenuInstalledOnX => {
+            checker.check_x_running() && checker.check_dmenu_installed()
+        }
     }
 }
 
@@ -140,6 +185,7 @@ mod tests {
         let mut mock = MockSystemChecksImpl::new();
         mock_func!(mock, 1, check_networkmanager_running, false);
         mock_func!(mock, 1, check_netctl_running, false);
+        mock_func!(mock, 1, check_iwd_running, false);
         mock_func!(mock, 1, check_netctl_installed, false);
         mock_func!(mock, 1, check_networkmanager_installed, false);
         assert_eq!(
@@ -153,6 +199,7 @@ mod tests {
         let mut mock = MockSystemChecksImpl::new();
         mock_func!(mock, 1, check_networkmanager_running, false);
         mock_func!(mock, 1, check_netctl_running, false);
+        mock_func!(mock, 1, check_iwd_running, false);
         mock_func!(mock, 1, check_netctl_installed, true);
         mock_func_not_called!(mock, check_networkmanager_installed);
         assert_eq!(
@@ -161,6 +208,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_wifiscan_iw_installed() {
+        let mut mock = MockSystemChecksImpl::new();
+        mock_func!(mock, 1, check_networkmanager_running, false);
+        mock_func!(mock, 1, check_iwd_running, false);
+        mock_func!(mock, 1, check_iw_installed, true);
+        mock_func_not_called!(mock, check_iwlist_installed);
+        assert_eq!(
+            WifiScanType::IW,
+            choose_best_from_system_impl(&mock, "fake_name")
+        );
+    }
+
+    #[test]
+    fn test_wifiscan_iwlist_installed_but_not_iw() {
+        let mut mock = MockSystemChecksImpl::new();
+        mock_func!(mock, 1, check_networkmanager_running, false);
+        mock_func!(mock, 1, check_iwd_running, false);
+        mock_func!(mock, 1, check_iw_installed, false);
+        mock_func!(mock, 1, check_iwlist_installed, true);
+        assert_eq!(
+            WifiScanType::IWList,
+            choose_best_from_system_impl(&mock, "fake_name")
+        );
+    }
+
+    #[test]
+    fn test_wificonn_iwd_running() {
+        let mut mock = MockSystemChecksImpl::new();
+        mock_func!(mock, 1, check_networkmanager_running, false);
+        mock_func!(mock, 1, check_netctl_running, false);
+        mock_func!(mock, 1, check_iwd_running, true);
+        mock_func_not_called!(mock, check_netctl_installed);
+        mock_func_not_called!(mock, check_networkmanager_installed);
+        assert_eq!(
+            WifiConnectionType::Iwd,
+            choose_best_from_system_impl(&mock, "fake_name")
+        );
+    }
+
+    #[test]
+    fn test_wifiscan_iwd_running() {
+        let mut mock = MockSystemChecksImpl::new();
+        mock_func!(mock, 1, check_networkmanager_running, false);
+        mock_func!(mock, 1, check_iwd_running, true);
+        mock_func_not_called!(mock, check_iw_installed);
+        mock_func_not_called!(mock, check_iwlist_installed);
+        assert_eq!(
+            WifiScanType::Iwctl,
+            choose_best_from_system_impl(&mock, "fake_name")
+        );
+    }
+
     #[test]
     fn test_wiredconn_dhcpcd_installed() {
         let mut mock = MockSystemChecksImpl::new();
@@ -176,6 +276,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_selection_method_fzf_in_terminal() {
+        let mut mock = MockSystemChecksImpl::new();
+        mock_func!(mock, 1, check_terminal_attached, true);
+        mock_func!(mock, 1, check_fzf_installed, true);
+        mock_func_not_called!(mock, check_wayland_running);
+        mock_func_not_called!(mock, check_x_running);
+        assert_eq!(
+            SelectionMethod::Fzf,
+            choose_best_from_system_impl(&mock, "fake_name")
+        );
+    }
+
+    #[test]
+    fn test_selection_method_builtin_in_terminal_without_fzf() {
+        let mut mock = MockSystemChecksImpl::new();
+        mock_func!(mock, 2, check_terminal_attached, true);
+        mock_func!(mock, 1, check_fzf_installed, false);
+        mock_func_not_called!(mock, check_wayland_running);
+        assert_eq!(
+            SelectionMethod::Builtin,
+            choose_best_from_system_impl(&mock, "fake_name")
+        );
+    }
+
+    #[test]
+    fn test_selection_method_wofi_on_wayland() {
+        let mut mock = MockSystemChecksImpl::new();
+        mock_func!(mock, 2, check_terminal_attached, false);
+        mock_func_not_called!(mock, check_fzf_installed);
+        mock_func!(mock, 2, check_wayland_running, true);
+        mock_func!(mock, 1, check_fuzzel_installed, false);
+        mock_func!(mock, 1, check_wofi_installed, true);
+        mock_func_not_called!(mock, check_dmenu_installed);
+        assert_eq!(
+            SelectionMethod::Wofi,
+            choose_best_from_system_impl(&mock, "fake_name")
+        );
+    }
+
+    #[test]
+    fn test_selection_method_dmenu_on_x() {
+        let mut mock = MockSystemChecksImpl::new();
+        mock_func!(mock, 2, check_terminal_attached, false);
+        mock_func!(mock, 3, check_wayland_running, false);
+        mock_func!(mock, 3, check_x_running, true);
+        mock_func!(mock, 1, check_bemenu_installed, false);
+        mock_func!(mock, 1, check_rofi_installed, false);
+        mock_func!(mock, 1, check_dmenu_installed, true);
+        mock_func_not_called!(mock, check_fuzzel_installed);
+        mock_func_not_called!(mock, check_wofi_installed);
+        assert_eq!(
+            SelectionMethod::Dmenu,
+            choose_best_from_system_impl(&mock, "fake_name")
+        );
+    }
+
     #[test]
     fn test_wiredconn_netctl_installed() {
         let mut mock = MockSystemChecksImpl::new();
@@ -3,6 +3,9 @@ use super::SystemChecksImpl;
 use crate::options::traits::PreParseGlobal;
 use crate::run_commands::SystemCommandRunner;
 
+use std::env;
+use std::fs::OpenOptions;
+
 pub(crate) struct SystemCheckerReal<'a, O: PreParseGlobal> {
     opts: &'a O,
 }
@@ -16,6 +19,10 @@ impl<'a, O: PreParseGlobal> SystemCheckerReal<'a, O> {
         SystemCommandRunner::new(self.opts, "systemctl", &["is-active", "--quiet", unit])
             .run_command_status_dumb()
     }
+
+    fn check_env_var_is_set(name: &str) -> bool {
+        env::var(name).map(|val| !val.is_empty()).unwrap_or(false)
+    }
 }
 
 impl<'a, O: PreParseGlobal> SystemChecksImpl for SystemCheckerReal<'a, O> {
@@ -27,6 +34,10 @@ impl<'a, O: PreParseGlobal> SystemChecksImpl for SystemCheckerReal<'a, O> {
         self.check_systemd_unit("netctl")
     }
 
+    fn check_iwd_running(&self) -> bool {
+        self.check_systemd_unit("iwd")
+    }
+
     fn check_netctl_installed(&self) -> bool {
         SystemCommandRunner::new(self.opts, "netctl", &[]).check_command_exists()
     }
@@ -42,4 +53,53 @@ impl<'a, O: PreParseGlobal> SystemChecksImpl for SystemCheckerReal<'a, O> {
     fn check_dhcpcd_installed(&self) -> bool {
         SystemCommandRunner::new(self.opts, "dhcpcd", &[]).check_command_exists()
     }
+
+    fn check_iw_installed(&self) -> bool {
+        SystemCommandRunner::new(self.opts, "iw", &[]).check_command_exists()
+    }
+
+    fn check_iwlist_installed(&self) -> bool {
+        SystemCommandRunner::new(self.opts, "iwlist", &[]).check_command_exists()
+    }
+
+    fn check_bemenu_installed(&self) -> bool {
+        SystemCommandRunner::new(self.opts, "bemenu", &[]).check_command_exists()
+    }
+
+    fn check_dmenu_installed(&self) -> bool {
+        SystemCommandRunner::new(self.opts, "dmenu", &[]).check_command_exists()
+    }
+
+    fn check_fuzzel_installed(&self) -> bool {
+        SystemCommandRunner::new(self.opts, "fuzzel", &[]).check_command_exists()
+    }
+
+    fn check_fzf_installed(&self) -> bool {
+        SystemCommandRunner::new(self.opts, "fzf", &[]).check_command_exists()
+    }
+
+    fn check_rofi_installed(&self) -> bool {
+        SystemCommandRunner::new(self.opts, "rofi", &[]).check_command_exists()
+    }
+
+    fn check_wofi_installed(&self) -> bool {
+        SystemCommandRunner::new(self.opts, "wofi", &[]).check_command_exists()
+    }
+
+    // Both fzf and the builtin selector draw directly on the controlling terminal.
+    fn check_terminal_attached(&self) -> bool {
+        OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open("/dev/tty")
+            .is_ok()
+    }
+
+    fn check_wayland_running(&self) -> bool {
+        Self::check_env_var_is_set("WAYLAND_DISPLAY")
+    }
+
+    fn check_x_running(&self) -> bool {
+        Self::check_env_var_is_set("DISPLAY")
+    }
 }
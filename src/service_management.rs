@@ -1,7 +1,9 @@
 use crate::prelude::*;
 use crate::interface_management::ip_interfaces::WifiIPInterface;
 use crate::run_commands::SystemCommandRunner;
-use crate::wpa_cli_initialize::{initialize_wpa_supplicant, kill_wpa_supplicant};
+use crate::wpa_cli_initialize::{
+    initialize_wpa_supplicant, is_wpa_supplicant_running, kill_wpa_supplicant,
+};
 
 use strum::IntoEnumIterator;
 
@@ -17,6 +19,7 @@ impl NetworkingService {
         match self {
             Self::Netctl => start_netctl(options),
             Self::NetworkManager => start_networkmanager(options),
+            Self::Iwd => start_iwd(options),
             Self::WpaSupplicant(interface) => initialize_wpa_supplicant(options, interface),
             Self::None => Ok(()),
         }
@@ -29,11 +32,52 @@ impl NetworkingService {
         match self {
             Self::Netctl => stop_netctl(options),
             Self::NetworkManager => stop_networkmanager(options),
+            Self::Iwd => stop_iwd(options),
             Self::WpaSupplicant(_) => kill_wpa_supplicant(options),
             Self::None => Ok(()),
         }
     }
 
+    // iwd and wpa_supplicant will both try to manage the wifi interface, so only one
+    // of them can be allowed to run at a time. NetworkManager can be configured to use
+    // either as its backend, so it is left alone.
+    fn get_conflicting_services(&self, interface: &WifiIPInterface) -> Vec<Self> {
+        match self {
+            Self::Iwd => vec![Self::WpaSupplicant(interface.clone())],
+            Self::Netctl | Self::WpaSupplicant(_) => vec![Self::Iwd],
+            Self::NetworkManager | Self::None => vec![],
+        }
+    }
+
+    fn is_active<O>(&self, options: &O) -> bool
+    where
+        O: Global,
+    {
+        match self {
+            Self::Netctl => is_systemd_unit_active(options, "netctl"),
+            Self::NetworkManager => is_systemd_unit_active(options, "NetworkManager"),
+            Self::Iwd => is_systemd_unit_active(options, "iwd"),
+            Self::WpaSupplicant(_) => is_wpa_supplicant_running(options),
+            Self::None => false,
+        }
+    }
+
+    pub(crate) fn stop_conflicting_services<O>(
+        &self,
+        options: &O,
+        interface: &WifiIPInterface,
+    ) -> Result<(), RuwiError>
+    where
+        O: Global,
+    {
+        for service in self.get_conflicting_services(interface) {
+            if service.is_active(options) {
+                service.stop(options)?;
+            }
+        }
+        Ok(())
+    }
+
     pub(crate) fn stop_all<O: 'static>(options: &O) -> Result<(), RuwiError>
     where
         O: Global + Send + Sync + Clone,
@@ -103,11 +147,46 @@ where
     )
 }
 
+fn start_iwd<O>(options: &O) -> Result<(), RuwiError>
+where
+    O: Global,
+{
+    SystemCommandRunner::new(options, "systemctl", &["start", "iwd"]).run_command_pass(
+        RuwiErrorKind::FailedToStartIwd,
+        "Failed to start iwd. Is it installed? Are you running as root?",
+    )
+}
+
+fn stop_iwd<O>(options: &O) -> Result<(), RuwiError>
+where
+    O: Global,
+{
+    // Most systems running wpa_supplicant won't have iwd installed at all, and `systemctl stop`
+    // fails on units which don't exist, so only stop iwd if it's actually running.
+    if is_systemd_unit_active(options, "iwd") {
+        SystemCommandRunner::new(options, "systemctl", &["stop", "iwd"]).run_command_pass(
+            RuwiErrorKind::FailedToStopIwd,
+            "Failed to stop iwd. Are you running as root?",
+        )
+    } else {
+        Ok(())
+    }
+}
+
+fn is_systemd_unit_active<O>(options: &O, unit: &str) -> bool
+where
+    O: Global,
+{
+    SystemCommandRunner::new(options, "systemctl", &["is-active", "--quiet", unit])
+        .run_command_status_dumb()
+}
+
 impl GetService for WifiConnectionType {
     fn get_service(&self, _interface: Option<&WifiIPInterface>) -> NetworkingService {
         match self {
             Self::Nmcli => NetworkingService::NetworkManager,
             Self::Netctl => NetworkingService::Netctl,
+            Self::Iwd => NetworkingService::Iwd,
             Self::None | Self::Print => NetworkingService::None,
         }
     }
@@ -117,12 +196,15 @@ impl GetService for WifiScanType {
     fn get_service(&self, interface: Option<&WifiIPInterface>) -> NetworkingService {
         match self {
             WifiScanType::Nmcli => NetworkingService::NetworkManager,
+            WifiScanType::Iwctl => NetworkingService::Iwd,
             WifiScanType::WpaCli => NetworkingService::WpaSupplicant(
                 interface
                     .expect("Interface must be provided to wpa_supplicant!")
                     .clone(),
             ),
-            WifiScanType::IW | WifiScanType::RuwiJSON => NetworkingService::None,
+            WifiScanType::IW | WifiScanType::IWList | WifiScanType::RuwiJSON => {
+                NetworkingService::None
+            }
         }
     }
 }
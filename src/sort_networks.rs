@@ -4,11 +4,14 @@ use std::cmp::Ordering;
 use std::collections::HashSet;
 use std::fmt::Debug;
 
+use crate::essid_pattern::EssidPattern;
+use crate::select::additional_options_for_manual_selection::SelectionOption;
 use crate::select::Selector;
 
 #[derive(Debug)]
 pub(crate) struct SortedFilteredNetworks<N: Debug> {
     networks: Vec<N>,
+    hidden_networks: Vec<N>,
 }
 
 impl<N: AnnotatedRuwiNetwork> Selector for SortedFilteredNetworks<N> {
@@ -17,28 +20,67 @@ impl<N: AnnotatedRuwiNetwork> Selector for SortedFilteredNetworks<N> {
     fn get_networks(&self) -> &[N] {
         &self.networks
     }
+
+    // Hidden networks can only be connected to if some were seen, and only known networks can
+    // be forgotten, so those options are left out when they'd do nothing.
+    fn get_selection_options(&self) -> Vec<SelectionOption> {
+        let mut options = vec![SelectionOption::Refresh, SelectionOption::EnterEssidManually];
+        if self.has_hidden_networks() {
+            options.push(SelectionOption::ConnectToHiddenNetwork);
+        }
+        if self.networks.iter().any(Known::is_known) {
+            options.push(SelectionOption::ForgetKnownNetwork);
+        }
+        options.push(SelectionOption::SwitchInterface);
+        options
+    }
+}
+
+impl SortedFilteredNetworks<AnnotatedWirelessNetwork> {
+    pub(crate) fn new(
+        networks: Vec<AnnotatedWirelessNetwork>,
+        sort_order: &NetworkSortOrder,
+    ) -> Self {
+        let (hidden_networks, mut networks): (Vec<_>, Vec<_>) = networks
+            .into_iter()
+            .partition(|nw| is_hidden_network_name(nw.get_public_name()));
+        networks.sort_by(|a, b| sort_order.compare(a, b));
+        let networks = Self::dedup_networks(networks);
+        Self {
+            networks,
+            hidden_networks,
+        }
+    }
 }
 
-impl<N: Ord + Identifiable + Clone + Debug> SortedFilteredNetworks<N> {
+impl<N: Identifiable + Clone + Debug> SortedFilteredNetworks<N> {
     #[cfg(test)]
     pub(crate) fn get_networks_mut(&mut self) -> &mut [N] {
         &mut self.networks
     }
 
-    pub(crate) fn new(networks: Vec<N>) -> Self {
-        let mut networks = networks;
-        Self::put_best_networks_first(&mut networks);
-        let networks = Self::dedup_networks(networks);
-        Self { networks }
+    pub(crate) fn get_hidden_networks(&self) -> &[N] {
+        &self.hidden_networks
     }
 
-    fn put_best_networks_first(networks: &mut Vec<N>) {
-        networks.sort();
-        networks.reverse();
+    pub(crate) fn has_hidden_networks(&self) -> bool {
+        !self.hidden_networks.is_empty()
+    }
+
+    // Hidden networks have no name to match against, so they're dropped.
+    pub(crate) fn filter_by_essid_pattern(&self, pattern: &EssidPattern) -> Self {
+        let networks = self
+            .networks
+            .iter()
+            .filter(|nw| pattern.matches(nw.get_public_name()))
+            .cloned()
+            .collect();
+        Self {
+            networks,
+            hidden_networks: vec![],
+        }
     }
-}
 
-impl<N: Identifiable + Clone + Debug> SortedFilteredNetworks<N> {
     fn dedup_networks(networks: Vec<N>) -> Vec<N> {
         // Once partition_dedup_by is stable:
         //let (unique_networks, _dups) = sorted_networks.partition_dedup_by(|a, b| a.essid == b.essid);
@@ -56,6 +98,64 @@ impl<N: Identifiable + Clone + Debug> SortedFilteredNetworks<N> {
     }
 }
 
+// Hidden networks show up in scans with an empty SSID, or one made up entirely of NUL bytes.
+pub(crate) fn is_hidden_network_name(name: &str) -> bool {
+    name.chars().all(|c| c == '\0')
+}
+
+// Decides the order networks are shown in, best first. Since the auto modes pick the first
+// (known) network in that order, this also decides which network gets connected to. Networks
+// which a policy considers equal fall back to known-first, and then signal strength.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct NetworkSortOrder {
+    policy: SortPolicy,
+    essid_priority: Vec<String>,
+}
+
+impl NetworkSortOrder {
+    pub(crate) fn new(policy: SortPolicy, essid_priority: Vec<String>) -> Self {
+        Self {
+            policy,
+            essid_priority,
+        }
+    }
+
+    pub(crate) fn from_options<O: Wifi>(options: &O) -> Self {
+        Self::new(
+            options.get_sort_policy().clone(),
+            options.get_essid_priority().to_vec(),
+        )
+    }
+
+    fn compare(&self, a: &AnnotatedWirelessNetwork, b: &AnnotatedWirelessNetwork) -> Ordering {
+        let by_policy = match self.policy {
+            SortPolicy::KnownFirst | SortPolicy::Signal => Ordering::Equal,
+            SortPolicy::Prefer5GHz => is_5ghz_or_above(b).cmp(&is_5ghz_or_above(a)),
+            SortPolicy::OpenLast => a.is_encrypted().cmp(&b.is_encrypted()).reverse(),
+            SortPolicy::Priority => self.get_priority(a).cmp(&self.get_priority(b)),
+        };
+        // Networks are ordered worst first, so the best ones go first by comparing b to a.
+        let by_known_and_signal = if self.policy == SortPolicy::Signal {
+            b.get_signal_strength().cmp(&a.get_signal_strength())
+        } else {
+            b.cmp(a)
+        };
+        by_policy.then(by_known_and_signal)
+    }
+
+    // Networks not in the priority list go after all of those which are.
+    fn get_priority(&self, nw: &AnnotatedWirelessNetwork) -> usize {
+        self.essid_priority
+            .iter()
+            .position(|essid| essid == nw.get_public_name())
+            .unwrap_or(usize::MAX)
+    }
+}
+
+fn is_5ghz_or_above(nw: &AnnotatedWirelessNetwork) -> bool {
+    matches!(nw.get_band(), Some(WifiBand::Five | WifiBand::Six))
+}
+
 impl Ord for AnnotatedWirelessNetwork {
     fn cmp(&self, other: &Self) -> Ordering {
         if self.is_known() ^ other.is_known() {
@@ -92,12 +192,12 @@ mod tests {
     fn test_strength_sorting() {
         let higher_signal = AnnotatedWirelessNetwork::builder()
             .essid("Valparaiso_Guest_House 1".to_string())
-            .signal_strength(Some(-66))
+            .signal_strength(Some(SignalStrength::from_dbm(-66)))
             .build();
 
         let lower_signal = AnnotatedWirelessNetwork::builder()
             .essid("Valparaiso_Guest_House 1".to_string())
-            .signal_strength(Some(-69))
+            .signal_strength(Some(SignalStrength::from_dbm(-69)))
             .build();
 
         compare_order(lower_signal, higher_signal);
@@ -118,18 +218,31 @@ mod tests {
         compare_order(not_known, known);
     }
 
+    #[test]
+    fn test_quality_and_dbm_signals_are_comparable() {
+        let higher_signal = AnnotatedWirelessNetwork::builder()
+            .essid("From nmcli")
+            .signal_strength(Some(SignalStrength::from_quality(80)))
+            .build();
+        let lower_signal = AnnotatedWirelessNetwork::builder()
+            .essid("From iw")
+            .signal_strength(Some(SignalStrength::from_dbm(-75)))
+            .build();
+        compare_order(lower_signal, higher_signal);
+    }
+
     #[test]
     fn test_known_higher_than_unknown_with_higher_signal() {
         let known = AnnotatedWirelessNetwork::builder()
             .essid("Valparaiso_Guest_House 1".to_string())
             .service_identifier(NetworkingServiceIdentifier::netctl_nw("some_id"))
-            .signal_strength(Some(-80))
+            .signal_strength(Some(SignalStrength::from_dbm(-80)))
             .build();
 
         let not_known = AnnotatedWirelessNetwork::builder()
             .essid("Valparaiso_Guest_House 1".to_string())
             .service_identifier(None)
-            .signal_strength(Some(-20))
+            .signal_strength(Some(SignalStrength::from_dbm(-20)))
             .build();
 
         compare_order(not_known, known);
@@ -140,19 +253,19 @@ mod tests {
         let networks = vec![
             AnnotatedWirelessNetwork::builder()
                 .essid("DOOK".to_string())
-                .signal_strength(Some(-5))
+                .signal_strength(Some(SignalStrength::from_dbm(-5)))
                 .build(),
             AnnotatedWirelessNetwork::builder()
                 .essid("BOYS".to_string())
-                .signal_strength(Some(-47))
+                .signal_strength(Some(SignalStrength::from_dbm(-47)))
                 .build(),
             AnnotatedWirelessNetwork::builder()
                 .essid("DOOK".to_string())
-                .signal_strength(Some(-49))
+                .signal_strength(Some(SignalStrength::from_dbm(-49)))
                 .build(),
             AnnotatedWirelessNetwork::builder()
                 .essid("YES".to_string())
-                .signal_strength(Some(-89))
+                .signal_strength(Some(SignalStrength::from_dbm(-89)))
                 .build(),
         ];
 
@@ -162,7 +275,155 @@ mod tests {
             networks[3].clone(),
         ];
 
-        let sorted_unique_networks = SortedFilteredNetworks::new(networks);
+        let sorted_unique_networks = SortedFilteredNetworks::new(networks, &NetworkSortOrder::default());
         assert_eq![expected_networks, sorted_unique_networks.get_networks()];
     }
+
+    fn get_essids_in_order(
+        networks: &[AnnotatedWirelessNetwork],
+        policy: SortPolicy,
+        essid_priority: &[&str],
+    ) -> Vec<String> {
+        let essid_priority = essid_priority.iter().map(|x| (*x).to_string()).collect();
+        let sort_order = NetworkSortOrder::new(policy, essid_priority);
+        SortedFilteredNetworks::new(networks.to_vec(), &sort_order)
+            .get_networks()
+            .iter()
+            .map(|nw| nw.get_public_name().to_string())
+            .collect()
+    }
+
+    fn get_networks_for_policies() -> Vec<AnnotatedWirelessNetwork> {
+        vec![
+            AnnotatedWirelessNetwork::builder()
+                .essid("KnownWeak")
+                .service_identifier(NetworkingServiceIdentifier::netctl_nw("some_id"))
+                .is_encrypted(true)
+                .signal_strength(Some(SignalStrength::from_dbm(-80)))
+                .frequency(Some(2412))
+                .build(),
+            AnnotatedWirelessNetwork::builder()
+                .essid("OpenStrong")
+                .signal_strength(Some(SignalStrength::from_dbm(-40)))
+                .frequency(Some(2437))
+                .build(),
+            AnnotatedWirelessNetwork::builder()
+                .essid("FiveGHz")
+                .is_encrypted(true)
+                .signal_strength(Some(SignalStrength::from_dbm(-70)))
+                .frequency(Some(5180))
+                .build(),
+            AnnotatedWirelessNetwork::builder()
+                .essid("Medium")
+                .is_encrypted(true)
+                .signal_strength(Some(SignalStrength::from_dbm(-60)))
+                .build(),
+        ]
+    }
+
+    #[test]
+    fn test_sort_policies() {
+        let networks = get_networks_for_policies();
+        assert_eq![
+            get_essids_in_order(&networks, SortPolicy::KnownFirst, &[]),
+            vec!["KnownWeak", "OpenStrong", "Medium", "FiveGHz"]
+        ];
+        assert_eq![
+            get_essids_in_order(&networks, SortPolicy::Signal, &[]),
+            vec!["OpenStrong", "Medium", "FiveGHz", "KnownWeak"]
+        ];
+        assert_eq![
+            get_essids_in_order(&networks, SortPolicy::Prefer5GHz, &[]),
+            vec!["FiveGHz", "KnownWeak", "OpenStrong", "Medium"]
+        ];
+        assert_eq![
+            get_essids_in_order(&networks, SortPolicy::OpenLast, &[]),
+            vec!["KnownWeak", "Medium", "FiveGHz", "OpenStrong"]
+        ];
+        assert_eq![
+            get_essids_in_order(&networks, SortPolicy::Priority, &["Medium", "FiveGHz"]),
+            vec!["Medium", "FiveGHz", "KnownWeak", "OpenStrong"]
+        ];
+    }
+
+    #[test]
+    fn test_sort_policy_decides_first_known() {
+        let mut networks = get_networks_for_policies();
+        networks[2].set_service_identifier_for_tests(NetworkingServiceIdentifier::netctl_nw("five"));
+        let sort_order = NetworkSortOrder::new(SortPolicy::Prefer5GHz, vec![]);
+        let sorted = SortedFilteredNetworks::new(networks, &sort_order);
+        let options = crate::options::wifi::connect::WifiConnectOptions::default();
+        assert_eq![
+            sorted.select_first_known(&options).unwrap().get_public_name(),
+            "FiveGHz"
+        ];
+    }
+
+    #[test]
+    fn test_hidden_networks_are_separated() {
+        let networks = SortedFilteredNetworks::new(
+            vec![
+                AnnotatedWirelessNetwork::builder()
+                    .essid("Valparaiso_Guest_House 1")
+                    .build(),
+                AnnotatedWirelessNetwork::builder()
+                    .essid("")
+                    .is_encrypted(true)
+                    .build(),
+                AnnotatedWirelessNetwork::builder()
+                    .essid("\0\0\0\0")
+                    .build(),
+            ],
+            &NetworkSortOrder::default(),
+        );
+        assert_eq![
+            networks
+                .get_networks()
+                .iter()
+                .map(Identifiable::get_public_name)
+                .collect::<Vec<_>>(),
+            vec!["Valparaiso_Guest_House 1"]
+        ];
+        assert_eq![networks.get_hidden_networks().len(), 2];
+        assert![networks.has_hidden_networks()];
+    }
+
+    #[test]
+    fn test_selection_options_depend_on_networks() {
+        let unknown = SortedFilteredNetworks::new(
+            vec![AnnotatedWirelessNetwork::builder()
+                .essid("Valparaiso_Guest_House 1")
+                .build()],
+            &NetworkSortOrder::default(),
+        );
+        assert_eq![
+            unknown.get_selection_options(),
+            vec![
+                SelectionOption::Refresh,
+                SelectionOption::EnterEssidManually,
+                SelectionOption::SwitchInterface
+            ]
+        ];
+
+        let known_and_hidden = SortedFilteredNetworks::new(
+            vec![
+                AnnotatedWirelessNetwork::builder()
+                    .essid("Valparaiso_Guest_House 1")
+                    .service_identifier(NetworkingServiceIdentifier::netctl_nw("some_id"))
+                    .build(),
+                AnnotatedWirelessNetwork::builder().essid("").build(),
+            ],
+            &NetworkSortOrder::default(),
+        );
+        assert_eq![
+            known_and_hidden.get_selection_options(),
+            vec![
+                SelectionOption::Refresh,
+                SelectionOption::EnterEssidManually,
+                SelectionOption::ConnectToHiddenNetwork,
+                SelectionOption::ForgetKnownNetwork,
+                SelectionOption::SwitchInterface
+            ]
+        ];
+    }
 }
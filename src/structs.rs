@@ -4,8 +4,12 @@
 use crate::prelude::*;
 
 use crate::interface_management::ip_interfaces::{LinuxIPInterface, WiredIPInterface};
-use std::fmt::Debug;
+use serde_derive::Serialize;
+use std::convert::TryFrom;
+use std::fmt::{self, Debug};
+use std::time::Duration;
 use typed_builder::TypedBuilder;
+use zeroize::Zeroizing;
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct ScanResult {
@@ -20,15 +24,110 @@ pub struct ParseResult {
     pub line_parse_errors: Vec<(String, IndividualParseError)>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum IndividualParseError {
     FailedToParseSignalLevel,
     FailedToUnescapeSSIDField,
+    MalformedRuwiJSONNetwork,
     MissingIWCapabilityField,
+    MissingIWListEncryptionKeyField,
+    MissingIWListESSIDField,
     MissingIWSSIDField,
+    MissingIwctlField,
     MissingNmcliSeparator,
     MissingWpaCliResultField,
     ZeroLengthIWChunk,
+    ZeroLengthIWListChunk,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SelectTimeout {
+    duration: Duration,
+    fallback: SelectTimeoutFallback,
+}
+
+impl SelectTimeout {
+    pub fn new(duration: Duration, fallback: SelectTimeoutFallback) -> Self {
+        Self { duration, fallback }
+    }
+
+    pub fn get_duration(&self) -> Duration {
+        self.duration
+    }
+
+    pub fn get_fallback(&self) -> SelectTimeoutFallback {
+        self.fallback
+    }
+}
+
+// Scanning programs report signal either in dBm (iw, iwlist, wpa_cli, iwd) or as a quality
+// percentage (nmcli), so both are kept here and the missing one is derived from the other,
+// using the same linear mapping NetworkManager uses (-100 dBm is 0%, -50 dBm and above is 100%).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SignalStrength {
+    dbm: i32,
+    quality: u8,
+}
+
+impl SignalStrength {
+    const MIN_DBM: i32 = -100;
+    const MAX_QUALITY: u8 = 100;
+
+    pub fn from_dbm(dbm: i32) -> Self {
+        let quality = (2 * (dbm - Self::MIN_DBM)).clamp(0, Self::MAX_QUALITY.into());
+        let quality = u8::try_from(quality).unwrap_or(Self::MAX_QUALITY);
+        Self { dbm, quality }
+    }
+
+    pub fn from_quality(quality: u32) -> Self {
+        let quality = u8::try_from(quality)
+            .unwrap_or(Self::MAX_QUALITY)
+            .min(Self::MAX_QUALITY);
+        let dbm = i32::from(quality) / 2 + Self::MIN_DBM;
+        Self { dbm, quality }
+    }
+
+    pub fn get_dbm(self) -> i32 {
+        self.dbm
+    }
+
+    pub fn get_quality(self) -> u8 {
+        self.quality
+    }
+}
+
+// Wifi passwords are wiped from memory when dropped, and never show up in debug output.
+#[derive(Clone, PartialEq, Eq)]
+pub struct EncryptionKey(Zeroizing<String>);
+
+impl EncryptionKey {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for EncryptionKey {
+    fn from(key: String) -> Self {
+        Self(Zeroizing::new(key))
+    }
+}
+
+impl From<Zeroizing<String>> for EncryptionKey {
+    fn from(key: Zeroizing<String>) -> Self {
+        Self(key)
+    }
+}
+
+impl From<&str> for EncryptionKey {
+    fn from(key: &str) -> Self {
+        Self::from(key.to_string())
+    }
+}
+
+impl fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("EncryptionKey(<redacted>)")
+    }
 }
 
 // TODO: make private, provide interface?
@@ -40,9 +139,17 @@ pub struct WirelessNetwork {
     #[builder(default = None)]
     bssid: Option<String>,
     #[builder(default = None)]
-    signal_strength: Option<i32>,
+    signal_strength: Option<SignalStrength>,
     #[builder(default = None)]
     channel_utilisation: Option<String>,
+    #[builder(default = None)]
+    channel: Option<u32>,
+    #[builder(default = None)]
+    frequency: Option<u32>,
+    #[builder(default = None)]
+    security: Option<WifiSecurity>,
+    #[builder(default = None)]
+    rate: Option<u32>,
 }
 
 impl RuwiNetwork for WirelessNetwork {}
@@ -56,11 +163,21 @@ pub struct AnnotatedWirelessNetwork {
     #[builder(default = None)]
     bssid: Option<String>,
     #[builder(default = None)]
-    signal_strength: Option<i32>,
+    signal_strength: Option<SignalStrength>,
     #[builder(default = None)]
     channel_utilisation: Option<String>,
+    #[builder(default = None)]
+    channel: Option<u32>,
+    #[builder(default = None)]
+    frequency: Option<u32>,
+    #[builder(default = None)]
+    security: Option<WifiSecurity>,
+    #[builder(default = None)]
+    rate: Option<u32>,
 
     // Non-scan annotated fields
+    #[builder(default = false)]
+    is_hidden: bool,
     #[builder(default = None)]
     service_identifier: Option<NetworkingServiceIdentifier>,
 }
@@ -77,6 +194,10 @@ impl Annotated for AnnotatedWirelessNetwork {
         let bssid = nw.bssid;
         let signal_strength = nw.signal_strength;
         let channel_utilisation = nw.channel_utilisation;
+        let channel = nw.channel;
+        let frequency = nw.frequency;
+        let security = nw.security;
+        let rate = nw.rate;
         let service_identifier = service_identifier.map(Clone::clone);
         Self {
             essid,
@@ -84,6 +205,11 @@ impl Annotated for AnnotatedWirelessNetwork {
             bssid,
             signal_strength,
             channel_utilisation,
+            channel,
+            frequency,
+            security,
+            rate,
+            is_hidden: false,
             service_identifier,
         }
     }
@@ -93,15 +219,33 @@ impl AnnotatedWirelessNetwork {
     pub fn is_encrypted(&self) -> bool {
         self.is_encrypted
     }
+    pub fn is_hidden(&self) -> bool {
+        self.is_hidden
+    }
     pub fn _get_bssid(&self) -> Option<&String> {
         self.bssid.as_ref()
     }
-    pub fn get_signal_strength(&self) -> Option<i32> {
+    pub fn get_signal_strength(&self) -> Option<SignalStrength> {
         self.signal_strength
     }
     pub fn _get_channel_utilisation(&self) -> Option<&String> {
         self.channel_utilisation.as_ref()
     }
+    pub fn get_channel(&self) -> Option<u32> {
+        self.channel
+    }
+    pub fn get_frequency(&self) -> Option<u32> {
+        self.frequency
+    }
+    pub fn get_band(&self) -> Option<WifiBand> {
+        self.frequency.and_then(WifiBand::from_frequency)
+    }
+    pub fn get_security(&self) -> Option<WifiSecurity> {
+        self.security
+    }
+    pub fn _get_rate(&self) -> Option<u32> {
+        self.rate
+    }
 
     #[cfg(test)]
     pub fn from_essid_only(essid: &str) -> Self {
@@ -178,7 +322,9 @@ impl Identifiable for AnnotatedWiredNetwork {
                     // TODO: just the identifier? mention netctl?
                     netident.as_ref()
                 }
-                NetworkingServiceIdentifier::NetworkManager => ifname,
+                NetworkingServiceIdentifier::NetworkManager | NetworkingServiceIdentifier::Iwd => {
+                    ifname
+                }
             }
         } else {
             ifname
@@ -211,3 +357,37 @@ pub struct PostParseContext {
     pub network_or_device: NetworkOrDevice,
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signal_strength_from_dbm() {
+        assert_eq![SignalStrength::from_dbm(-66).get_quality(), 68];
+        assert_eq![SignalStrength::from_dbm(-66).get_dbm(), -66];
+        assert_eq![SignalStrength::from_dbm(-20).get_quality(), 100];
+        assert_eq![SignalStrength::from_dbm(-105).get_quality(), 0];
+    }
+
+    #[test]
+    fn test_signal_strength_from_quality() {
+        assert_eq![SignalStrength::from_quality(68).get_dbm(), -66];
+        assert_eq![SignalStrength::from_quality(68).get_quality(), 68];
+        assert_eq![SignalStrength::from_quality(0).get_dbm(), -100];
+        assert_eq![SignalStrength::from_quality(150).get_quality(), 100];
+    }
+
+    #[test]
+    fn test_signal_strength_ordering() {
+        assert![SignalStrength::from_dbm(-50) > SignalStrength::from_dbm(-66)];
+        assert![SignalStrength::from_quality(90) > SignalStrength::from_dbm(-66)];
+        assert![Some(SignalStrength::from_dbm(-90)) > None];
+    }
+
+    #[test]
+    fn test_encryption_key_is_not_printed() {
+        let key = EncryptionKey::from("hunter22");
+        assert_eq![key.as_str(), "hunter22"];
+        assert_eq![format!("{:?}", Some(key)), "Some(EncryptionKey(<redacted>))"];
+    }
+}
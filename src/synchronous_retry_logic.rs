@@ -19,15 +19,6 @@ where
             })
 }
 
-pub(crate) fn manual_refresh_requested<T>(res: &Result<T, RuwiError>) -> bool {
-    if let Err(err) = res {
-        if err.kind == RuwiErrorKind::RefreshRequested {
-            return true;
-        }
-    }
-    false
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,7 +2,11 @@ use crate::errors::{RuwiError, RuwiErrorKind};
 use std::thread;
 
 use std::ffi::OsStr;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
 use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
 
 #[inline]
 pub(crate) fn convert_osstr_to_string(osstr_name: &OsStr) -> String {
@@ -19,6 +23,25 @@ pub(crate) fn await_thread<T>(handle: thread::JoinHandle<T>) -> Result<T, RuwiEr
     })
 }
 
+// Some tools (e.g. iwctl) colorize their output even when it isn't going to a terminal.
+pub(crate) fn strip_ansi_escape_codes(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' && chars.peek() == Some(&'[') {
+            chars.next();
+            for code_char in chars.by_ref() {
+                if code_char.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
+
 pub(crate) fn loop_check(loop_protection: &mut u16, loop_max: u16) -> Result<(), RuwiError> {
     if *loop_protection >= loop_max {
         return Err(rerr!(
@@ -31,6 +54,40 @@ pub(crate) fn loop_check(loop_protection: &mut u16, loop_max: u16) -> Result<(),
     Ok(())
 }
 
+// For files which can contain keys, so only root may read them. They're written to a temporary
+// file which is then renamed over the real one, so that nothing ever sees one that's only partly
+// written. The temporary file starts with a ".", so that e.g. netctl doesn't list it as a profile.
+pub(crate) fn write_private_file_atomically(fullpath: &str, contents: &str) -> io::Result<()> {
+    let path = Path::new(fullpath);
+    let tmp_path = get_tmp_path(path);
+    // Left over from an earlier run which was interrupted.
+    let _ = fs::remove_file(&tmp_path);
+
+    let res = write_private_file(&tmp_path, contents).and_then(|()| fs::rename(&tmp_path, path));
+    if res.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    res
+}
+
+fn get_tmp_path(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.ruwi-tmp", file_name))
+}
+
+fn write_private_file(path: &Path, contents: &str) -> io::Result<()> {
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -42,6 +99,15 @@ mod tests {
         assert_eq!(lawl, convert_osstr_to_string(osstr));
     }
 
+    #[test]
+    fn test_strip_ansi_escape_codes() {
+        assert_eq!(
+            "  >   Valparaiso_Guest_House 2  psk",
+            strip_ansi_escape_codes("\u{1b}[0m  \u{1b}[1;90m>\u{1b}[0m   Valparaiso_Guest_House 2  psk")
+        );
+        assert_eq!("no codes here", strip_ansi_escape_codes("no codes here"));
+    }
+
     #[test]
     fn test_big_loop_check_allows_at_least_5_loops() -> Result<(), RuwiError> {
         let mut start = 0;
@@ -74,4 +140,33 @@ mod tests {
             panic!("Loop protection did not fail when expected!");
         }
     }
+
+    #[test]
+    fn test_write_private_file_atomically() -> io::Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir()?;
+        let fullpath = dir.path().join("Citadel");
+        let fullpath = fullpath.to_str().unwrap();
+
+        fs::write(fullpath, "old contents")?;
+        fs::set_permissions(fullpath, fs::Permissions::from_mode(0o644))?;
+        write_private_file_atomically(fullpath, "Key='hunter2'")?;
+
+        assert_eq![fs::read_to_string(fullpath)?, "Key='hunter2'"];
+        assert_eq![fs::metadata(fullpath)?.permissions().mode() & 0o777, 0o600];
+        assert_eq![fs::read_dir(dir.path())?.count(), 1];
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_private_file_atomically_failure_leaves_nothing_behind() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let fullpath = dir.path().join("a_directory");
+        fs::create_dir(&fullpath)?;
+
+        assert![write_private_file_atomically(fullpath.to_str().unwrap(), "IP=dhcp").is_err()];
+        assert_eq![fs::read_dir(dir.path())?.count(), 1];
+        Ok(())
+    }
 }
@@ -0,0 +1,123 @@
+use crate::prelude::*;
+use crate::interface_management::ip_interfaces::{LinuxIPInterface, WifiIPInterface};
+use crate::run_commands::SystemCommandRunner;
+use crate::utils::strip_ansi_escape_codes;
+
+#[cfg(not(test))]
+use std::thread;
+#[cfg(not(test))]
+use std::time::Duration;
+
+use crate::wifi_scan::{ALLOWED_SYNCHRONOUS_RETRIES, SYNCHRONOUS_RETRY_DELAY_SECS};
+
+const IWCTL_SCAN_ERR_MSG: &str = concat!(
+    "Failed to scan with `iwctl`. Is iwd installed and running? ",
+    "You can also select a different scanning method with -s (try 'iw' or 'iwlist'), ",
+    "or you can manually specify an essid with -e.",
+);
+
+pub(crate) fn run_iwctl_scan<O>(
+    options: &O,
+    interface: &WifiIPInterface,
+    wifi_scan_type: WifiScanType,
+    synchronous_rescan: &Option<SynchronousRescanType>,
+) -> Result<ScanResult, RuwiError>
+where
+    O: Global + Wifi,
+{
+    eprintln!("[NOTE]: Scanning for wifi networks using iwctl...");
+
+    let scan_output = if options.get_force_synchronous_scan() || synchronous_rescan.is_some() {
+        run_iwctl_scan_synchronous(options, interface)?
+    } else {
+        let scan_output = run_iwctl_get_networks(options, interface)?;
+        if scan_output.contains("No networks available") {
+            run_iwctl_scan_synchronous(options, interface)?
+        } else {
+            run_iwctl_scan_trigger(options, interface).ok();
+            scan_output
+        }
+    };
+
+    Ok(ScanResult {
+        scan_type: ScanType::Wifi(wifi_scan_type),
+        scan_output,
+    })
+}
+
+// `iwctl station <iface> scan` returns immediately, so wait for iwd to report
+// that it has finished scanning before asking for the results.
+fn run_iwctl_scan_synchronous<O>(options: &O, interface: &WifiIPInterface) -> Result<String, RuwiError>
+where
+    O: Global,
+{
+    run_iwctl_scan_trigger(options, interface)?;
+
+    let mut retries = ALLOWED_SYNCHRONOUS_RETRIES;
+    while is_iwctl_scanning(options, interface)? {
+        retries -= 1;
+        if retries == 0 {
+            return Err(rerr!(
+                RuwiErrorKind::IwctlSynchronousScanRanOutOfRetries,
+                format!(
+                    "Ran out of retries waiting for iwd to finish scanning on {}.",
+                    interface.get_ifname()
+                ),
+            ));
+        }
+
+        #[cfg(not(test))]
+        thread::sleep(Duration::from_secs_f64(SYNCHRONOUS_RETRY_DELAY_SECS));
+        #[cfg(test)]
+        dbg!(SYNCHRONOUS_RETRY_DELAY_SECS);
+    }
+
+    run_iwctl_get_networks(options, interface)
+}
+
+fn is_iwctl_scanning<O>(options: &O, interface: &WifiIPInterface) -> Result<bool, RuwiError>
+where
+    O: Global,
+{
+    let output = SystemCommandRunner::new(
+        options,
+        "iwctl",
+        &["station", interface.get_ifname(), "show"],
+    ).run_command_pass_stdout(
+        RuwiErrorKind::FailedToRunIwctlShow,
+        IWCTL_SCAN_ERR_MSG,
+    )?;
+
+    Ok(strip_ansi_escape_codes(&output).lines().any(|line| {
+        let mut fields = line.split_whitespace();
+        fields.next() == Some("Scanning") && fields.next() == Some("yes")
+    }))
+}
+
+fn run_iwctl_get_networks<O>(options: &O, interface: &WifiIPInterface) -> Result<String, RuwiError>
+where
+    O: Global,
+{
+    SystemCommandRunner::new(
+        options,
+        "iwctl",
+        &["station", interface.get_ifname(), "get-networks", "rssi-dbms"],
+    ).run_command_pass_stdout(
+        RuwiErrorKind::FailedToRunIwctlGetNetworks,
+        IWCTL_SCAN_ERR_MSG,
+    )
+}
+
+fn run_iwctl_scan_trigger<O>(options: &O, interface: &WifiIPInterface) -> Result<String, RuwiError>
+where
+    O: Global,
+{
+    SystemCommandRunner::new(
+        options,
+        "iwctl",
+        &["station", interface.get_ifname(), "scan"],
+    ).run_command_pass_stdout(
+        RuwiErrorKind::FailedToRunIwctlScan,
+        IWCTL_SCAN_ERR_MSG,
+    )
+}
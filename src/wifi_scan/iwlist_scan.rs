@@ -0,0 +1,41 @@
+use crate::prelude::*;
+use crate::interface_management::ip_interfaces::{LinuxIPInterface, WifiIPInterface};
+use crate::run_commands::SystemCommandRunner;
+
+const IWLIST_SCAN_ERR_MSG: &str = concat!(
+    "Failed to scan with `iwlist`. Is it installed? ",
+    "You can also select a different scanning method with -s (try 'iw' or 'wpa_cli'), ",
+    "or you can manually specify an essid with -e.",
+);
+
+// `iwlist scanning` always performs a synchronous scan, so there is no cached
+// results mode like there is for iw.
+pub(crate) fn run_iwlist_scan<O>(
+    options: &O,
+    interface: &WifiIPInterface,
+    wifi_scan_type: WifiScanType,
+) -> Result<ScanResult, RuwiError>
+where
+    O: Global + Wifi,
+{
+    interface.bring_up(options)?;
+
+    eprintln!("[NOTE]: Scanning for wifi networks using iwlist...");
+
+    let scan_output = SystemCommandRunner::new(
+        options,
+        "iwlist",
+        &[interface.get_ifname(), "scanning"],
+    ).run_command_pass_stdout(
+        RuwiErrorKind::FailedToRunIWListScan,
+        IWLIST_SCAN_ERR_MSG,
+    )?;
+
+    if options.d() {
+        dbg![&scan_output];
+    }
+    Ok(ScanResult {
+        scan_type: ScanType::Wifi(wifi_scan_type),
+        scan_output,
+    })
+}
@@ -1,6 +1,12 @@
 mod iw_scan;
 use iw_scan::run_iw_scan;
 
+mod iwctl_scan;
+use iwctl_scan::run_iwctl_scan;
+
+mod iwlist_scan;
+use iwlist_scan::run_iwlist_scan;
+
 mod nmcli_scan;
 use nmcli_scan::run_nmcli_scan;
 
@@ -9,6 +15,7 @@ use wpa_cli_scan::run_wpa_cli_scan;
 
 use crate::prelude::*;
 use crate::interface_management::ip_interfaces::WifiIPInterface;
+use crate::parse::detect_scan_type::{detect_scan_type, err_failed_to_detect_scan_type};
 
 use std::fs::File;
 use std::io;
@@ -23,7 +30,7 @@ pub(crate) fn wifi_scan<O>(
     options: &O,
     interface: &WifiIPInterface,
     synchronous_rescan: &Option<SynchronousRescanType>
-) -> Result<ScanResult, RuwiError>
+) -> Result<Vec<ScanResult>, RuwiError>
 where
     O: Global + Wifi
 {
@@ -33,21 +40,30 @@ where
     let res = match sm {
         ScanMethod::ByRunning => {
             // TODO: integration test that service is only started on byrunning scan
-            st.get_service(Some(interface)).start(options)?;
+            // Services which conflict with the connection manager are stopped when connecting,
+            // so scanning (e.g. for `wifi list`) leaves them alone.
+            let scan_service = st.get_service(Some(interface));
+            scan_service.start(options)?;
 
-            match &st {
+            let res = match &st {
                 WifiScanType::Nmcli => run_nmcli_scan(options, interface, st, synchronous_rescan),
                 WifiScanType::WpaCli => run_wpa_cli_scan(options, st),
                 WifiScanType::IW => run_iw_scan(options, interface, st, synchronous_rescan),
+                WifiScanType::IWList => run_iwlist_scan(options, interface, st),
+                WifiScanType::Iwctl => run_iwctl_scan(options, interface, st, synchronous_rescan),
                 WifiScanType::RuwiJSON =>
                     Err(rerr!(
                         RuwiErrorKind::InvalidScanTypeAndMethod,
                         "There is currently no binary for providing JSON results, you must format them yourself and pass in via stdin or from a file.",
                     ))
-            }
+            };
+            res.map(|scan_result| vec![scan_result])
         },
-        ScanMethod::FromFile(filename) => get_scan_contents_from_file(options, ScanType::Wifi(st), &filename),
-        ScanMethod::FromStdin => get_scan_contents_from_stdin(options, ScanType::Wifi(st)),
+        ScanMethod::FromFiles(filenames) => filenames
+            .iter()
+            .map(|filename| get_scan_contents_from_file(options, st.clone(), filename))
+            .collect(),
+        ScanMethod::FromStdin => get_scan_contents_from_stdin(options, st).map(|scan_result| vec![scan_result]),
     };
 
     if options.d() {
@@ -56,10 +72,26 @@ where
     res
 }
 
+fn get_scan_type_for_input<O>(
+    options: &O,
+    given_scan_type: WifiScanType,
+    scan_output: &str,
+    source: &str,
+) -> Result<ScanType, RuwiError> where O: Global + Wifi {
+    if !options.get_detect_scan_type() {
+        return Ok(ScanType::Wifi(given_scan_type));
+    }
+    let scan_type = detect_scan_type(scan_output).ok_or_else(|| err_failed_to_detect_scan_type(source))?;
+    if options.d() {
+        eprintln!("[NOTE]: Detected scan type for {}: {}", source, scan_type);
+    }
+    Ok(ScanType::Wifi(scan_type))
+}
+
 fn get_scan_contents_from_stdin<O>(
-    _options: &O,
-    scan_type: ScanType,
-) -> Result<ScanResult, RuwiError> where O: Global {
+    options: &O,
+    given_scan_type: WifiScanType,
+) -> Result<ScanResult, RuwiError> where O: Global + Wifi {
     let mut stdin_contents = "".into();
     io::stdin().read_to_end(&mut stdin_contents).map_err(|_e| {
         rerr!(
@@ -68,7 +100,8 @@ fn get_scan_contents_from_stdin<O>(
         )
     })?;
 
-    let scan_output = String::from_utf8_lossy(&stdin_contents).into();
+    let scan_output: String = String::from_utf8_lossy(&stdin_contents).into();
+    let scan_type = get_scan_type_for_input(options, given_scan_type, &scan_output, "stdin")?;
 
     Ok(ScanResult {
         scan_type,
@@ -77,10 +110,10 @@ fn get_scan_contents_from_stdin<O>(
 }
 
 fn get_scan_contents_from_file<O>(
-    _options: &O,
-    scan_type: ScanType,
+    options: &O,
+    given_scan_type: WifiScanType,
     filename: &str,
-) -> Result<ScanResult, RuwiError> where O: Global {
+) -> Result<ScanResult, RuwiError> where O: Global + Wifi {
     let file_read_err = |_e: io::Error| {
         rerr!(
             RuwiErrorKind::FailedToReadScanResultsFromFile,
@@ -93,7 +126,8 @@ fn get_scan_contents_from_file<O>(
         .read_to_end(&mut file_contents)
         .map_err(file_read_err)?;
 
-    let scan_output = String::from_utf8_lossy(&file_contents).into();
+    let scan_output: String = String::from_utf8_lossy(&file_contents).into();
+    let scan_type = get_scan_type_for_input(options, given_scan_type, &scan_output, &format!("`{}`", filename))?;
 
     Ok(ScanResult {
         scan_type,
@@ -1,5 +1,6 @@
 use crate::prelude::*;
 use crate::interface_management::ip_interfaces::{LinuxIPInterface, WifiIPInterface};
+use crate::parse::nmcli::NMCLI_SCAN_FIELDS;
 use crate::run_commands::SystemCommandRunner;
 
 const NMCLI_SCAN_ERR_MSG: &str = concat!(
@@ -42,11 +43,11 @@ where
         "nmcli",
         &[
             "--escape",
-            "no",
+            "yes",
             "--color",
             "no",
             "-g",
-            "SECURITY,SIGNAL,SSID",
+            NMCLI_SCAN_FIELDS,
             "device",
             "wifi",
             "list",
@@ -64,11 +65,11 @@ where
         "nmcli",
         &[
             "--escape",
-            "no",
+            "yes",
             "--color",
             "no",
             "-g",
-            "SECURITY,SIGNAL,SSID",
+            NMCLI_SCAN_FIELDS,
             "device",
             "wifi",
             "list",
@@ -63,15 +63,20 @@ where
     SystemCommandRunner::new(options, "wpa_cli", &["ping"]).run_command_status_dumb()
 }
 
+pub(crate) fn is_wpa_supplicant_running<O>(options: &O) -> bool
+where
+    O: Global,
+{
+    SystemCommandRunner::new(options, "pgrep", &["wpa_supplicant"]).run_command_status_dumb()
+}
+
 pub(crate) fn kill_wpa_supplicant<O>(options: &O) -> Result<(), RuwiError>
 where
     O: Global,
 {
     // pkill doesn't differentiate "could not kill a process" and "no processes with that name found" except
     // in its stderr, so we instead check pgrep first and just pkill if a process was seen.
-    let running_wpa_supplicant =
-        SystemCommandRunner::new(options, "pgrep", &["wpa_supplicant"]).run_command_status_dumb();
-    if running_wpa_supplicant {
+    if is_wpa_supplicant_running(options) {
         SystemCommandRunner::new(options, "pkill", &["wpa_supplicant"]).run_command_pass(
             RuwiErrorKind::FailedToStopWpaSupplicant,
             "Failed to stop wpa_supplicant! Are you running as root?",
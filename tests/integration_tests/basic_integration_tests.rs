@@ -37,6 +37,67 @@ fn test_iw_first_network_from_file() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_iwlist_first_network_from_file() -> Result<()> {
+    let mut p = spawn_dryrun(
+        "wifi -F src/parse/samples/iwlist_two_different_networks.txt -s iwlist connect -c print -A first",
+    )?;
+    p.exp_string("[NOTE]: Selected network: \"Valparaiso_Guest_House 2\"")?;
+    p.exp_string("Valparaiso_Guest_House 2")?;
+    Ok(())
+}
+
+#[test]
+fn test_iwctl_first_network_from_file() -> Result<()> {
+    let mut p = spawn_dryrun(
+        "wifi -F src/parse/samples/iwctl_two_different_networks.txt -s iwctl connect -c print -A first",
+    )?;
+    p.exp_string("[NOTE]: Selected network: \"Valparaiso_Guest_House 2\"")?;
+    p.exp_string("Valparaiso_Guest_House 2")?;
+    Ok(())
+}
+
+#[test]
+fn test_ruwi_json_first_network_from_file() -> Result<()> {
+    let mut p = spawn_dryrun(
+        "wifi -F src/parse/samples/ruwi_json_two_networks.json -s ruwi_json connect -c print -A first",
+    )?;
+    p.exp_string("[NOTE]: Selected network: \"Valparaiso_Guest_House 1\"")?;
+    p.exp_string("Valparaiso_Guest_House 1")?;
+    Ok(())
+}
+
+#[test]
+fn test_list_networks_from_file_as_tsv() -> Result<()> {
+    let mut p = spawn_dryrun(
+        "wifi -F src/parse/samples/iw_two_different_networks.txt -s iw list -c print --format tsv",
+    )?;
+    p.exp_string("ESSID\tBSSID\tSIGNAL\tQUALITY\tENCRYPTION\tKNOWN\tSERVICE")?;
+    p.exp_string("Valparaiso_Guest_House 2\t68:72:51:68:73:da\t-46\t100%\tencrypted\tno\t-")?;
+    p.exp_string("Valparaiso_Guest_House 1\tf4:28:53:fe:a5:d0\t-65\t70%\tencrypted\tno\t-")?;
+    Ok(())
+}
+
+#[test]
+fn test_list_networks_from_multiple_files_with_detected_scan_types() -> Result<()> {
+    let mut p = spawn_dryrun(
+        "wifi -F src/parse/samples/iwlist_two_different_networks.txt -F src/parse/samples/ruwi_json_two_networks.json list -c print --format tsv",
+    )?;
+    p.exp_string("ESSID\tBSSID\tSIGNAL\tQUALITY\tENCRYPTION\tKNOWN\tSERVICE")?;
+    p.exp_string("xfinitywifi\t")?;
+    Ok(())
+}
+
+#[test]
+fn test_strict_parse_fails_on_broken_line() -> Result<()> {
+    let mut p = spawn_dryrun(
+        "wifi -F src/parse/samples/iwctl_four_networks_one_broken.txt -s iwctl --strict-parse list -c print",
+    )?;
+    p.exp_string("StrictParseFailedOnScanLines")?;
+    p.exp_string("* MissingIwctlField: ")?;
+    Ok(())
+}
+
 #[test]
 fn test_print_given_essid() -> Result<()> {
     let mut p = spawn_dryrun("wifi connect -e FUCKAHOL -c print")?;
@@ -44,6 +105,66 @@ fn test_print_given_essid() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_netctl_hidden_given_essid() -> Result<()> {
+    let mut p = spawn_dryrun("wifi -s iw connect -c netctl -e HIDDEN_ESSID --hidden -p hunter2")?;
+    p.exp_string("ESSID='HIDDEN_ESSID'")?;
+    p.exp_string("Hidden=yes")?;
+    Ok(())
+}
+
+#[test]
+fn test_forget_given_essid_with_netctl() -> Result<()> {
+    let mut p = spawn_dryrun("wifi -s iw forget -c netctl -e MADE_UP_ESSID -y")?;
+    p.exp_string("netctl stop MADE_UP_ESSID")?;
+    p.exp_string("Would remove a netctl profile named like \"/etc/netctl/MADE_UP_ESSID\"")?;
+    Ok(())
+}
+
+#[test]
+fn test_forget_asks_for_confirmation() -> Result<()> {
+    let mut p = spawn_bash(Some(5000))?;
+    p.execute(
+        &get_dryrun_cmd_with_args("-m nocurses wifi -s iw forget -c nmcli -e Cafe"),
+        "Forget \"Cafe\"",
+    )?;
+    p.send_line("n")?;
+    p.exp_string("Not forgetting \"Cafe\"")?;
+    p.wait_for_prompt()?;
+    Ok(())
+}
+
+#[test]
+fn test_match_network_from_file() -> Result<()> {
+    let mut p = spawn_dryrun(
+        "wifi -F src/parse/samples/iw_two_different_networks.txt -s iw connect -c print --match Valp.*1",
+    )?;
+    p.exp_string("[NOTE]: Found 1 network(s) matching \"Valp.*1\", using the best: Valparaiso_Guest_House 1")?;
+    p.exp_string("Valparaiso_Guest_House 1")?;
+    Ok(())
+}
+
+#[test]
+fn test_match_network_from_file_lists_near_misses() -> Result<()> {
+    let mut p = spawn_dryrun(
+        "wifi -F src/parse/samples/iw_two_different_networks.txt -s iw connect -c print --match valparaiso",
+    )?;
+    p.exp_string("No networks found matching \"valparaiso\". Did you mean one of: \"Valparaiso_Guest_House 2\", \"Valparaiso_Guest_House 1\"?")?;
+    Ok(())
+}
+
+#[test]
+fn test_select_timeout_falls_back_to_first() -> Result<()> {
+    let mut p = spawn_bash(Some(5000))?;
+    let connect_cmd = get_dryrun_cmd_with_args(
+        "-m custom --selection-command 'sleep 10' --select-timeout 1 --select-timeout-fallback first wifi -F src/parse/samples/iw_two_different_networks.txt -s iw connect -c print",
+    );
+    p.send_line(&connect_cmd)?;
+    p.exp_string("[NOTE]: `sleep` was closed after no selection was made within 1 seconds. Falling back to `first`.")?;
+    p.exp_string("[NOTE]: Selected network: \"Valparaiso_Guest_House 2\"")?;
+    Ok(())
+}
+
 #[test]
 fn test_wired_connect() -> Result<()> {
     let mut p = spawn_dryrun("wired -i DONALD connect -c dhcpcd")?;